@@ -22,17 +22,25 @@ contract MintLiquidityScript is Script {
 
     address constant TEST_USER = 0x79dAa774769334aF120f6CAA57E828FBBF56b39a;
 
-    LiquidityManager liquidityManager =
-        LiquidityManager(0x29491F712637415f4E27B7471F4DD8147094C555);
+    // Mainnet deployment, overridden with LIQUIDITY_MANAGER_ADDRESS (e.g. one deployed on anvil)
+    address constant DEFAULT_LIQUIDITY_MANAGER =
+        0x29491F712637415f4E27B7471F4DD8147094C555;
 
     function run() external {
         uint256 usdcAmount = 0.1 * 1e6;
         uint256 wseiAmount = 0.3 * 1e18;
 
-        uint256 deployerPrivateKey = vm.envUint("PRIVATE_KEY");
+        LiquidityManager liquidityManager = LiquidityManager(
+            vm.envOr("LIQUIDITY_MANAGER_ADDRESS", DEFAULT_LIQUIDITY_MANAGER)
+        );
 
-        // Start broadcasting tx from your signer
-        vm.startBroadcast(deployerPrivateKey);
+        // Start broadcasting tx from your signer, or from --sender with --unlocked on anvil
+        uint256 deployerPrivateKey = vm.envOr("PRIVATE_KEY", uint256(0));
+        if (deployerPrivateKey == 0) {
+            vm.startBroadcast();
+        } else {
+            vm.startBroadcast(deployerPrivateKey);
+        }
 
         // Approve tokens
         IERC20(USDC).approve(address(liquidityManager), usdcAmount);
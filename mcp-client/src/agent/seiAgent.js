@@ -3,6 +3,8 @@ import { ChatPromptTemplate } from "@langchain/core/prompts";
 import { mcpBalanceTool } from "../tools/mcpBalanceTool.js";
 import { mcpTokenBalanceTool } from "../tools/mcpTokenBalanceTool.js";
 import { poolsDataTool } from "../tools/poolsDataTool.js";
+import { pairPoolsTool } from "../tools/pairPoolsTool.js";
+import { aprSustainabilityTool } from "../tools/aprSustainabilityTool.js";
 import {tokenPairPriceHistoryTool } from "../tools/tokenPairPriceHistoryTool.js";
 import { createToolCallingAgent } from "langchain/agents";
 import { createLLM } from "../llm/LLM.js";
@@ -61,7 +63,7 @@ Example: "POOL_RECOMMENDATION|Based on current market data, I recommend the SEI/
 
 **For Pool Recommendations:**
 - STEP 1: MUST call poolsDataTool to get available pools
-- STEP 2: Select the best pool based on user needs
+- STEP 2: Select the best pool based on user needs; when the pair trades in several pools, call pairPoolsTool and prefer the top-ranked venue; when the user asks whether a pool's APR will last, call aprSustainabilityTool; weigh each pool's risk score against its APR and mention the biggest risk factors
 - STEP 3: MUST call tokenPairPriceHistoryTool for the chosen pool's token pair
 - STEP 4: Calculate min_price and max_price from the price data
 - STEP 5: Return POOL_RECOMMENDATION format with real data
@@ -134,7 +136,9 @@ Let's help users with accurate, data-driven recommendations!`
     mcpBalanceTool,
     mcpTokenBalanceTool, 
     tokenPairPriceHistoryTool,
-    poolsDataTool
+    poolsDataTool,
+    pairPoolsTool,
+    aprSustainabilityTool
   ];
 
   // 3. Create the agent with the enhanced prompt
@@ -0,0 +1,40 @@
+import { DynamicStructuredTool } from "@langchain/core/tools";
+import { z } from "zod";
+import { RestClient } from "../infra/rest/RestClient.js";
+import { env } from "../config/env.js";
+
+/**
+ * apr_sustainability(poolAddress: string, days?: number = 30) → string
+ *
+ * Returns whether a pool's APR is likely to hold, based on its stored TVL, volume and APR history.
+ */
+export const aprSustainabilityTool = new DynamicStructuredTool({
+  name: "apr_sustainability",
+  description:
+    "Check whether a pool's APR is sustainable. Uses the pool's TVL, volume and APR history to return a verdict (SUSTAINABLE, DECLINING, VOLATILE, INCENTIVE_DRIVEN or INSUFFICIENT_DATA) with reasons.",
+  schema: z.object({
+    poolAddress: z.string().min(1).describe("Pool id/address as returned by get_pools_data"),
+    days: z.number().optional().default(30).describe("Lookback window in days (max 365)"),
+  }),
+  func: async ({ poolAddress, days = 30 }) => {
+    const restClient = new RestClient(env.backendUrl);
+
+    try {
+      console.log(`🔍 apr_sustainability for ${poolAddress} over ${days} days`);
+
+      const res = await restClient.post("/tools/apr-sustainability", { pool_address: poolAddress, days });
+
+      if (res.isError) {
+        throw new Error(res.error);
+      }
+
+      console.log(`✅ APR sustainability fetched successfully`);
+      return JSON.stringify(res.data, null, 2);
+    } catch (err) {
+      console.error(`❌ aprSustainabilityTool error: ${err.message}`);
+      throw new Error(`Failed to assess APR sustainability – ${err.message}`);
+    } finally {
+      await restClient.disconnect();
+    }
+  },
+});
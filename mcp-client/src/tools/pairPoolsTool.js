@@ -0,0 +1,45 @@
+import { DynamicStructuredTool } from "@langchain/core/tools";
+import { z } from "zod";
+import { RestClient } from "../infra/rest/RestClient.js";
+import { env } from "../config/env.js";
+import { getTokenFunctionalSymbol } from "../utils/tokenRegistry.js";
+
+/**
+ * get_pair_pools(tokenA: string, tokenB: string, tradeSize?: number) → string
+ *
+ * Returns every pool for a token pair across DEXes, ranked by depth, fee APR and price impact.
+ */
+export const pairPoolsTool = new DynamicStructuredTool({
+  name: "get_pair_pools",
+  description:
+    "Compare all pools for one token pair across DEXes and fee tiers. Returns each pool ranked by liquidity depth near the current price, fee APR and price impact for a trade size, best venue first.",
+  schema: z.object({
+    tokenA: z.string().min(1).describe("Symbol of the first token in lowercase, e.g., 'sei'"),
+    tokenB: z.string().min(1).describe("Symbol of the second token in lowercase, e.g., 'usdc'"),
+    tradeSize: z.number().optional().describe("Amount of tokenB used to measure price impact (default 1000)"),
+  }),
+  func: async ({ tokenA, tokenB, tradeSize }) => {
+    const restClient = new RestClient(env.backendUrl);
+
+    try {
+      console.log(`🔍 get_pair_pools for ${tokenA}/${tokenB}`);
+      const symbolA = getTokenFunctionalSymbol(tokenA, "rest");
+      const symbolB = getTokenFunctionalSymbol(tokenB, "rest");
+      const params = tradeSize ? { trade_size: tradeSize } : {};
+
+      const res = await restClient.get(`/data/pairs/${symbolA}/${symbolB}`, params);
+
+      if (res.isError) {
+        throw new Error(res.error);
+      }
+
+      console.log(`✅ Pair pools fetched successfully`);
+      return JSON.stringify(res.data, null, 2);
+    } catch (err) {
+      console.error(`❌ pairPoolsTool error: ${err.message}`);
+      throw new Error(`Failed to fetch pair pools – ${err.message}`);
+    } finally {
+      await restClient.disconnect();
+    }
+  },
+});
@@ -11,7 +11,7 @@ import { env } from "../config/env.js";
 export const poolsDataTool = new DynamicStructuredTool({
   name: "get_pools_data",
   description:
-    "Fetch all liquidity pools with data such as id, the protocol (dex), fee tier, daily volume, TVL, and token symbols.",
+    "Fetch all liquidity pools with data such as id, the protocol (dex), fee tier, daily volume, TVL, token symbols and a risk score (0 safest to 100 riskiest, with the factors behind it).",
   schema: z.object({}).nullable().default({}),
   func: async () => {
     const restClient = new RestClient(env.backendUrl);
@@ -19,7 +19,7 @@ export const poolsDataTool = new DynamicStructuredTool({
     try {
       console.log(`🔍 get_pools_data`);
 
-      const res = await restClient.get("/data/pools");
+      const res = await restClient.get("/data/pools", { schema: "v2" });
 
       if (res.isError) {
         throw new Error(res.error);
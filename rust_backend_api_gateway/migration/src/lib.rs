@@ -1,6 +1,11 @@
 use sea_orm_migration::prelude::*;
 
 pub mod m20250806_062648_create_post_table;
+pub mod m20251018_000001_create_price_divergences_table;
+pub mod m20251018_000002_create_snapshot_tables;
+pub mod m20251018_000003_add_chain_ids;
+pub mod m20251018_000004_position_schema_v2;
+pub mod m20251018_000005_position_indexer;
 
 pub struct Migrator;
 
@@ -9,6 +14,11 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20250806_062648_create_post_table::Migration),
+            Box::new(m20251018_000001_create_price_divergences_table::Migration),
+            Box::new(m20251018_000002_create_snapshot_tables::Migration),
+            Box::new(m20251018_000003_add_chain_ids::Migration),
+            Box::new(m20251018_000004_position_schema_v2::Migration),
+            Box::new(m20251018_000005_position_indexer::Migration),
         ]
     }
 }
\ No newline at end of file
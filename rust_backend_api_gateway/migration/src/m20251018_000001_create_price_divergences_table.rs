@@ -0,0 +1,117 @@
+// migration/src/m20251018_000001_create_price_divergences_table.rs
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceDivergences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceDivergences::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PriceDivergences::Pair).string().not_null())
+                    .col(ColumnDef::new(PriceDivergences::BaseAddress).string().not_null())
+                    .col(ColumnDef::new(PriceDivergences::QuoteAddress).string().not_null())
+                    .col(ColumnDef::new(PriceDivergences::CheapVenue).string().not_null())
+                    .col(ColumnDef::new(PriceDivergences::CheapPool).string().null())
+                    .col(ColumnDef::new(PriceDivergences::CheapPrice).double().not_null())
+                    .col(ColumnDef::new(PriceDivergences::RichVenue).string().not_null())
+                    .col(ColumnDef::new(PriceDivergences::RichPool).string().null())
+                    .col(ColumnDef::new(PriceDivergences::RichPrice).double().not_null())
+                    .col(ColumnDef::new(PriceDivergences::SpreadPercent).double().not_null())
+                    .col(ColumnDef::new(PriceDivergences::MaxSpreadPercent).double().not_null())
+                    .col(ColumnDef::new(PriceDivergences::VenueCount).integer().not_null())
+                    .col(
+                        ColumnDef::new(PriceDivergences::ObservedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PriceDivergences::LastSeenAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PriceDivergences::ClosedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // History is read per pair, newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-price_divergences-pair-observed_at")
+                    .table(PriceDivergences::Table)
+                    .col(PriceDivergences::Pair)
+                    .col(PriceDivergences::ObservedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // A row spans a divergence from the scan that opened it to the one that closed it, and
+        // each pair has at most one open episode for the monitor to upsert into
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "idx-price_divergences-open-pair"
+                ON price_divergences (base_address, quote_address)
+                WHERE closed_at IS NULL"#,
+            )
+            .await?;
+
+        // Retention prunes closed episodes by age
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-price_divergences-closed_at")
+                    .table(PriceDivergences::Table)
+                    .col(PriceDivergences::ClosedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceDivergences::Table).to_owned())
+            .await
+    }
+}
+
+// Enum for the 'price_divergences' table and its columns
+#[derive(DeriveIden)]
+enum PriceDivergences {
+    Table,
+    Id,
+    Pair,
+    BaseAddress,
+    QuoteAddress,
+    CheapVenue,
+    CheapPool,
+    CheapPrice,
+    RichVenue,
+    RichPool,
+    RichPrice,
+    SpreadPercent,
+    MaxSpreadPercent,
+    VenueCount,
+    ObservedAt,
+    LastSeenAt,
+    ClosedAt,
+}
@@ -0,0 +1,127 @@
+// migration/src/m20251018_000002_create_snapshot_tables.rs
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PoolSnapshots::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PoolSnapshots::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    // Stored lowercase so lookups can use the index
+                    .col(ColumnDef::new(PoolSnapshots::PoolAddress).string().not_null())
+                    .col(ColumnDef::new(PoolSnapshots::Protocol).string().not_null())
+                    .col(ColumnDef::new(PoolSnapshots::Token0Symbol).string().not_null())
+                    .col(ColumnDef::new(PoolSnapshots::Token1Symbol).string().not_null())
+                    .col(ColumnDef::new(PoolSnapshots::FeeTier).integer().null())
+                    .col(ColumnDef::new(PoolSnapshots::Tvl).decimal().null())
+                    .col(ColumnDef::new(PoolSnapshots::DailyVolume).decimal().null())
+                    .col(ColumnDef::new(PoolSnapshots::Apr).double().null())
+                    .col(ColumnDef::new(PoolSnapshots::Price).double().null())
+                    .col(
+                        ColumnDef::new(PoolSnapshots::CapturedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-pool_snapshots-pool_address-captured_at")
+                    .table(PoolSnapshots::Table)
+                    .col(PoolSnapshots::PoolAddress)
+                    .col(PoolSnapshots::CapturedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TokenSnapshots::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TokenSnapshots::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TokenSnapshots::TokenAddress).string().not_null())
+                    .col(ColumnDef::new(TokenSnapshots::Symbol).string().not_null())
+                    .col(ColumnDef::new(TokenSnapshots::Source).string().not_null())
+                    .col(ColumnDef::new(TokenSnapshots::UsdPrice).double().not_null())
+                    .col(
+                        ColumnDef::new(TokenSnapshots::CapturedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-token_snapshots-token_address-captured_at")
+                    .table(TokenSnapshots::Table)
+                    .col(TokenSnapshots::TokenAddress)
+                    .col(TokenSnapshots::CapturedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TokenSnapshots::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PoolSnapshots::Table).to_owned())
+            .await
+    }
+}
+
+// Enum for the 'pool_snapshots' table and its columns
+#[derive(DeriveIden)]
+enum PoolSnapshots {
+    Table,
+    Id,
+    PoolAddress,
+    Protocol,
+    Token0Symbol,
+    Token1Symbol,
+    FeeTier,
+    Tvl,
+    DailyVolume,
+    Apr,
+    Price,
+    CapturedAt,
+}
+
+// Enum for the 'token_snapshots' table and its columns
+#[derive(DeriveIden)]
+enum TokenSnapshots {
+    Table,
+    Id,
+    TokenAddress,
+    Symbol,
+    Source,
+    UsdPrice,
+    CapturedAt,
+}
@@ -0,0 +1,169 @@
+// migration/src/m20251018_000003_add_chain_ids.rs
+use sea_orm_migration::prelude::*;
+
+/// Every row stored before multi-chain support came from Sei mainnet
+const SEI_CHAIN_ID: i64 = 1329;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            ChainTables::Positions,
+            ChainTables::PoolSnapshots,
+            ChainTables::TokenSnapshots,
+            ChainTables::PriceDivergences,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(ChainTables::ChainId)
+                                .big_integer()
+                                .not_null()
+                                .default(SEI_CHAIN_ID),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Snapshot lookups are always scoped to one chain
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-pool_snapshots-pool_address-captured_at")
+                    .table(ChainTables::PoolSnapshots)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-pool_snapshots-chain_id-pool_address-captured_at")
+                    .table(ChainTables::PoolSnapshots)
+                    .col(ChainTables::ChainId)
+                    .col(ChainTables::PoolAddress)
+                    .col(ChainTables::CapturedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-token_snapshots-token_address-captured_at")
+                    .table(ChainTables::TokenSnapshots)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-token_snapshots-chain_id-token_address-captured_at")
+                    .table(ChainTables::TokenSnapshots)
+                    .col(ChainTables::ChainId)
+                    .col(ChainTables::TokenAddress)
+                    .col(ChainTables::CapturedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Pairs are only unique within a chain, so an open episode is too
+        let db = manager.get_connection();
+        db.execute_unprepared(r#"DROP INDEX "idx-price_divergences-open-pair""#)
+            .await?;
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX "idx-price_divergences-open-pair"
+            ON price_divergences (chain_id, base_address, quote_address)
+            WHERE closed_at IS NULL"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(r#"DROP INDEX "idx-price_divergences-open-pair""#)
+            .await?;
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX "idx-price_divergences-open-pair"
+            ON price_divergences (base_address, quote_address)
+            WHERE closed_at IS NULL"#,
+        )
+        .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-token_snapshots-chain_id-token_address-captured_at")
+                    .table(ChainTables::TokenSnapshots)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-token_snapshots-token_address-captured_at")
+                    .table(ChainTables::TokenSnapshots)
+                    .col(ChainTables::TokenAddress)
+                    .col(ChainTables::CapturedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-pool_snapshots-chain_id-pool_address-captured_at")
+                    .table(ChainTables::PoolSnapshots)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-pool_snapshots-pool_address-captured_at")
+                    .table(ChainTables::PoolSnapshots)
+                    .col(ChainTables::PoolAddress)
+                    .col(ChainTables::CapturedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in [
+            ChainTables::PriceDivergences,
+            ChainTables::TokenSnapshots,
+            ChainTables::PoolSnapshots,
+            ChainTables::Positions,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(ChainTables::ChainId)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+// Enum for the tables gaining a 'chain_id' column and the columns indexed with it
+#[derive(DeriveIden, Clone, Copy)]
+enum ChainTables {
+    Positions,
+    PoolSnapshots,
+    TokenSnapshots,
+    PriceDivergences,
+    ChainId,
+    PoolAddress,
+    TokenAddress,
+    CapturedAt,
+}
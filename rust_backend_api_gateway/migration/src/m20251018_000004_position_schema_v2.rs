@@ -0,0 +1,232 @@
+// migration/src/m20251018_000004_position_schema_v2.rs
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Keep the v1 rows aside while the new table is created under the same name
+        manager
+            .rename_table(
+                Table::rename()
+                    .table(Positions::Table, PositionsV1::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Positions::Table)
+                    .col(
+                        ColumnDef::new(Positions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Positions::ChainId).big_integer().not_null())
+                    .col(ColumnDef::new(Positions::PbKey).string().not_null())
+                    // Unknown for positions recorded before v2
+                    .col(ColumnDef::new(Positions::PoolAddress).string().null())
+                    .col(ColumnDef::new(Positions::Protocol).string().null())
+                    .col(ColumnDef::new(Positions::Token0Address).string().null())
+                    .col(ColumnDef::new(Positions::Token1Address).string().null())
+                    .col(ColumnDef::new(Positions::TickLower).integer().null())
+                    .col(ColumnDef::new(Positions::TickUpper).integer().null())
+                    // uint128 and uint256 amounts in raw token units
+                    .col(ColumnDef::new(Positions::Liquidity).decimal_len(39, 0).null())
+                    .col(ColumnDef::new(Positions::Amount0).decimal_len(78, 0).null())
+                    .col(ColumnDef::new(Positions::Amount1).decimal_len(78, 0).null())
+                    .col(ColumnDef::new(Positions::NftTokenId).decimal_len(78, 0).null())
+                    .col(ColumnDef::new(Positions::TxHash).string().null())
+                    // The v1 range and value, kept for rows that have no ticks or amounts
+                    .col(ColumnDef::new(Positions::PriceLower).double().null())
+                    .col(ColumnDef::new(Positions::PriceUpper).double().null())
+                    .col(ColumnDef::new(Positions::ValueUsd).double().null())
+                    .col(ColumnDef::new(Positions::LegacyTransId).integer().null())
+                    .col(
+                        ColumnDef::new(Positions::Status)
+                            .string()
+                            .not_null()
+                            .default("open"),
+                    )
+                    .col(
+                        ColumnDef::new(Positions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Positions::ClosedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-positions-pb_key")
+                            .from(Positions::Table, Positions::PbKey)
+                            .to(Wallets::Table, Wallets::PbKey)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Positions are listed per wallet and looked up per pool
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-positions-pb_key-status")
+                    .table(Positions::Table)
+                    .col(Positions::PbKey)
+                    .col(Positions::Status)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-positions-chain_id-pool_address")
+                    .table(Positions::Table)
+                    .col(Positions::ChainId)
+                    .col(Positions::PoolAddress)
+                    .to_owned(),
+            )
+            .await?;
+
+        // f32 columns widen to double precision as they are copied
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"INSERT INTO positions (chain_id, pb_key, price_lower, price_upper, value_usd, legacy_trans_id, status)
+                SELECT chain_id, pb_key, "left", "right", value_locker, trans_id, 'open'
+                FROM positions_v1
+                ORDER BY pb_key, trans_id"#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PositionsV1::Table).to_owned())
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // v1 keys positions by an INTEGER trans_id, so refuse before changing anything when a
+        // position without a legacy id has outgrown it
+        let db = manager.get_connection();
+        let overflowing: i64 = db
+            .query_one(sea_orm::Statement::from_string(
+                db.get_database_backend(),
+                r#"SELECT COUNT(*) AS count FROM positions
+                WHERE legacy_trans_id IS NULL AND id > 2147483647"#,
+            ))
+            .await?
+            .map(|row| row.try_get("", "count"))
+            .transpose()?
+            .unwrap_or(0);
+        if overflowing > 0 {
+            return Err(DbErr::Migration(format!(
+                "{} positions have ids above the v1 INTEGER trans_id; delete them before rolling back",
+                overflowing
+            )));
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PositionsV1::Table)
+                    .col(ColumnDef::new(Positions::ChainId).big_integer().not_null())
+                    .col(ColumnDef::new(Positions::PbKey).string().not_null())
+                    .col(ColumnDef::new(PositionsV1::TransId).integer().not_null())
+                    .col(ColumnDef::new(PositionsV1::Left).float().not_null())
+                    .col(ColumnDef::new(PositionsV1::Right).float().not_null())
+                    .col(ColumnDef::new(PositionsV1::ValueLocker).float().not_null())
+                    .primary_key(
+                        Index::create()
+                            .name("pk-positions-pb_key-trans_id")
+                            .col(Positions::PbKey)
+                            .col(PositionsV1::TransId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PositionsV1::Table, Positions::PbKey)
+                            .to(Wallets::Table, Wallets::PbKey)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // v1 cannot hold ticks, amounts or hashes; positions created since v2 keep only their
+        // id as trans_id, and their range and value when they were recorded
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"INSERT INTO positions_v1 (chain_id, pb_key, trans_id, "left", "right", value_locker)
+                SELECT chain_id, pb_key, COALESCE(legacy_trans_id, id::INTEGER),
+                    COALESCE(price_lower, 0), COALESCE(price_upper, 0), COALESCE(value_usd, 0)
+                FROM positions
+                ON CONFLICT DO NOTHING"#,
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Positions::Table).to_owned())
+            .await?;
+        manager
+            .rename_table(
+                Table::rename()
+                    .table(PositionsV1::Table, Positions::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Wallets {
+    Table,
+    PbKey,
+}
+
+// Enum for the v2 'positions' table and its columns
+#[derive(DeriveIden)]
+enum Positions {
+    Table,
+    Id,
+    ChainId,
+    PbKey,
+    PoolAddress,
+    Protocol,
+    Token0Address,
+    Token1Address,
+    TickLower,
+    TickUpper,
+    Liquidity,
+    Amount0,
+    Amount1,
+    NftTokenId,
+    TxHash,
+    PriceLower,
+    PriceUpper,
+    ValueUsd,
+    LegacyTransId,
+    Status,
+    CreatedAt,
+    ClosedAt,
+}
+
+// The v1 table while it is migrated, and the v1-only columns
+#[derive(DeriveIden)]
+enum PositionsV1 {
+    Table,
+    TransId,
+    Left,
+    Right,
+    ValueLocker,
+}
@@ -0,0 +1,112 @@
+// migration/src/m20251018_000005_position_indexer.rs
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Set once the indexer has seen the position's LiquidityMinted log
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Positions::Table)
+                    .add_column(ColumnDef::new(Positions::BlockNumber).big_integer().null())
+                    .add_column(ColumnDef::new(Positions::LogIndex).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // A mint log is indexed into at most one position
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-positions-chain_id-tx_hash-log_index")
+                    .table(Positions::Table)
+                    .col(Positions::ChainId)
+                    .col(Positions::TxHash)
+                    .col(Positions::LogIndex)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(IndexerCursors::Table)
+                    .col(
+                        ColumnDef::new(IndexerCursors::ChainId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IndexerCursors::ContractAddress)
+                            .string()
+                            .not_null(),
+                    )
+                    // Last block whose logs are applied to `positions`
+                    .col(
+                        ColumnDef::new(IndexerCursors::BlockNumber)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(IndexerCursors::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk-indexer_cursors-chain_id-contract_address")
+                            .col(IndexerCursors::ChainId)
+                            .col(IndexerCursors::ContractAddress),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(IndexerCursors::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-positions-chain_id-tx_hash-log_index")
+                    .table(Positions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Positions::Table)
+                    .drop_column(Positions::BlockNumber)
+                    .drop_column(Positions::LogIndex)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Positions {
+    Table,
+    ChainId,
+    TxHash,
+    BlockNumber,
+    LogIndex,
+}
+
+#[derive(DeriveIden)]
+enum IndexerCursors {
+    Table,
+    ChainId,
+    ContractAddress,
+    BlockNumber,
+    UpdatedAt,
+}
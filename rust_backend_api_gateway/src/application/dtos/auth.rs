@@ -7,6 +7,9 @@ pub struct AuthRequest {
     pub signature: String,
     pub wallet_type: String,
     pub pub_key: Option<String>,
+    /// EVM chain id the session is for, the default chain when omitted
+    #[serde(default)]
+    pub chain_id: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct BacktestRequest {
+    pub token0: String,
+    pub token1: String,
+    pub interval: Option<u32>,
+    pub limit: Option<u32>,
+    /// Capital to deploy, in token1 units
+    pub capital: f64,
+    /// Pool fee tier in percent, e.g. 0.3
+    pub fee_tier: f64,
+    /// Pool TVL in token1 units
+    pub pool_tvl: f64,
+    /// Half-widths (±%) around the starting price to compare
+    pub range_widths_percent: Option<Vec<f64>>,
+}
+
+/// Most candles a backtest or strategy simulation replays
+pub const MAX_BACKTEST_LIMIT: u32 = 1000;
+
+impl BacktestRequest {
+    /// Message for the first invalid field
+    pub fn validate(&self) -> Result<(), String> {
+        validate_replay(
+            &self.token0,
+            &self.token1,
+            self.limit,
+            self.capital,
+            self.fee_tier,
+            self.pool_tvl,
+        )?;
+        if let Some(widths) = &self.range_widths_percent
+            && (widths.is_empty() || widths.iter().any(|w| *w <= 0.0 || *w >= 100.0))
+        {
+            return Err("range_widths_percent must contain values between 0 and 100".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Checks shared by the range backtest and the strategy simulator
+fn validate_replay(
+    token0: &str,
+    token1: &str,
+    limit: Option<u32>,
+    capital: f64,
+    fee_tier: f64,
+    pool_tvl: f64,
+) -> Result<(), String> {
+    if token0.is_empty() || token1.is_empty() {
+        return Err("Both token0 and token1 are required".to_string());
+    }
+    if limit.is_some_and(|limit| limit == 0 || limit > MAX_BACKTEST_LIMIT) {
+        return Err(format!(
+            "limit must be between 1 and {}",
+            MAX_BACKTEST_LIMIT
+        ));
+    }
+    if capital <= 0.0 || pool_tvl < 0.0 || fee_tier < 0.0 {
+        return Err(
+            "capital must be positive, pool_tvl and fee_tier cannot be negative".to_string(),
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BacktestResponse {
+    pub pair: String,
+    pub interval_minutes: u32,
+    pub data_points: usize,
+    pub start_price: f64,
+    pub end_price: f64,
+    pub capital: f64,
+    pub results: Vec<RangeBacktestResult>,
+    pub best_range_width_percent: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RangeBacktestResult {
+    pub range_width_percent: f64,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub liquidity: f64,
+    pub fees_earned: f64,
+    pub fee_apr_percent: f64,
+    pub final_position_value: f64,
+    pub hold_value: f64,
+    pub impermanent_loss_percent: f64,
+    pub time_in_range_percent: f64,
+    pub pnl: f64,
+    pub pnl_percent: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StrategySimulationRequest {
+    pub token0: String,
+    pub token1: String,
+    pub interval: Option<u32>,
+    pub limit: Option<u32>,
+    /// Capital to deploy, in token1 units
+    pub capital: f64,
+    /// Pool fee tier in percent, e.g. 0.3
+    pub fee_tier: f64,
+    /// Pool TVL in token1 units
+    pub pool_tvl: f64,
+    /// Flat gas cost per rebalance, in token1 units
+    pub gas_cost: Option<f64>,
+    /// Swap cost per rebalance in percent of the swapped amount, defaults to the fee tier
+    pub swap_cost_percent: Option<f64>,
+    pub strategies: Option<Vec<StrategyConfig>>,
+}
+
+impl StrategySimulationRequest {
+    /// Message for the first invalid field or strategy
+    pub fn validate(&self) -> Result<(), String> {
+        validate_replay(
+            &self.token0,
+            &self.token1,
+            self.limit,
+            self.capital,
+            self.fee_tier,
+            self.pool_tvl,
+        )?;
+        if self.gas_cost.is_some_and(|c| c < 0.0) || self.swap_cost_percent.is_some_and(|c| c < 0.0)
+        {
+            return Err("gas_cost and swap_cost_percent cannot be negative".to_string());
+        }
+        if let Some(strategies) = &self.strategies {
+            if strategies.is_empty() {
+                return Err("strategies cannot be empty; omit it for the defaults".to_string());
+            }
+            strategies.iter().try_for_each(StrategyConfig::validate)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StrategyConfig {
+    Fixed {
+        width_percent: f64,
+    },
+    RecenterOnExit {
+        width_percent: f64,
+    },
+    Trailing {
+        width_percent: f64,
+        inner_fraction: Option<f64>,
+    },
+    VolatilityScaled {
+        window: Option<usize>,
+        multiplier: Option<f64>,
+        min_width_percent: Option<f64>,
+        max_width_percent: Option<f64>,
+    },
+}
+
+pub const DEFAULT_INNER_FRACTION: f64 = 0.5;
+pub const DEFAULT_VOLATILITY_WINDOW: usize = 24;
+pub const DEFAULT_VOLATILITY_MULTIPLIER: f64 = 2.0;
+pub const DEFAULT_MIN_WIDTH_PERCENT: f64 = 2.0;
+pub const DEFAULT_MAX_WIDTH_PERCENT: f64 = 30.0;
+
+impl StrategyConfig {
+    /// Message for the first invalid parameter, checked after defaults are applied
+    pub fn validate(&self) -> Result<(), String> {
+        let valid_width = |width: f64| width > 0.0 && width < 100.0;
+        match self {
+            StrategyConfig::Fixed { width_percent }
+            | StrategyConfig::RecenterOnExit { width_percent } => {
+                if !valid_width(*width_percent) {
+                    return Err("width_percent must be between 0 and 100".to_string());
+                }
+            }
+            StrategyConfig::Trailing {
+                width_percent,
+                inner_fraction,
+            } => {
+                if !valid_width(*width_percent) {
+                    return Err("width_percent must be between 0 and 100".to_string());
+                }
+                let inner_fraction = inner_fraction.unwrap_or(DEFAULT_INNER_FRACTION);
+                if !(inner_fraction > 0.0 && inner_fraction <= 1.0) {
+                    return Err("inner_fraction must be above 0 and at most 1".to_string());
+                }
+            }
+            StrategyConfig::VolatilityScaled {
+                window,
+                multiplier,
+                min_width_percent,
+                max_width_percent,
+            } => {
+                if window.unwrap_or(DEFAULT_VOLATILITY_WINDOW) < 2 {
+                    return Err("window must be at least 2".to_string());
+                }
+                let multiplier = multiplier.unwrap_or(DEFAULT_VOLATILITY_MULTIPLIER);
+                if !(multiplier.is_finite() && multiplier > 0.0) {
+                    return Err("multiplier must be a positive number".to_string());
+                }
+                let min = min_width_percent.unwrap_or(DEFAULT_MIN_WIDTH_PERCENT);
+                let max = max_width_percent.unwrap_or(DEFAULT_MAX_WIDTH_PERCENT);
+                if !valid_width(min) || !valid_width(max) || min > max {
+                    return Err(
+                        "min_width_percent and max_width_percent must be between 0 and 100, \
+                         min not above max"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrategySimulationResponse {
+    pub pair: String,
+    pub interval_minutes: u32,
+    pub data_points: usize,
+    pub capital: f64,
+    pub results: Vec<StrategyResult>,
+    pub best_strategy: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrategyResult {
+    pub strategy: String,
+    pub summary: StrategySummary,
+    pub equity_curve: Vec<EquityCurvePoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrategySummary {
+    pub final_equity: f64,
+    pub hold_value: f64,
+    pub fees_earned: f64,
+    pub rebalance_costs: f64,
+    pub rebalance_count: usize,
+    pub time_in_range_percent: f64,
+    pub pnl: f64,
+    pub pnl_percent: f64,
+    pub max_drawdown_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EquityCurvePoint {
+    pub timestamp: i64,
+    pub price: f64,
+    pub equity: f64,
+    pub hold_value: f64,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub in_range: bool,
+}
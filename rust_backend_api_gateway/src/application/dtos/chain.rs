@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+/// `chain` query parameter accepted by every `/data` route
+#[derive(Debug, Deserialize)]
+pub struct ChainQuery {
+    /// Chain name (e.g. `sei`) or EVM chain id; the default chain when omitted
+    pub chain: Option<String>,
+}
@@ -0,0 +1,31 @@
+use crate::application::dtos::price_history::PairRelationshipInfo;
+use serde::{Deserialize, Serialize};
+
+/// Either `token0`/`token1` (legs priced in USD) or `pair_a`/`pair_b` such as `SEI/USDC`
+#[derive(Debug, Deserialize)]
+pub struct CorrelationQuery {
+    pub token0: Option<String>,
+    pub token1: Option<String>,
+    pub pair_a: Option<String>,
+    pub pair_b: Option<String>,
+    pub interval: Option<u32>,
+    pub limit: Option<u32>,
+    /// Rolling correlation window in candles
+    pub window: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CorrelationResponse {
+    pub pair_a: String,
+    pub pair_b: String,
+    pub interval_minutes: u32,
+    pub data_points: usize,
+    pub relationship: PairRelationshipInfo,
+    pub rolling_correlation: Vec<RollingCorrelation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RollingCorrelation {
+    pub timestamp: i64,
+    pub value: Option<f64>,
+}
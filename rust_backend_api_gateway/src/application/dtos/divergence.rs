@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct DivergenceQuery {
+    /// e.g. SEI/USDC, in either order
+    pub pair: Option<String>,
+    /// RFC 3339 timestamps bounding `observed_at`
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub min_spread: Option<f64>,
+    pub limit: Option<u64>,
+}
+
+/// Cheapest and richest quote for a pair at one scan, before it is stored
+#[derive(Debug, Clone)]
+pub struct DivergenceObservation {
+    pub chain_id: u64,
+    /// Base and quote symbols; the pair is identified by the lowercase token addresses, base
+    /// sorting first, and prices are quote per base
+    pub pair: String,
+    pub base_address: String,
+    pub quote_address: String,
+    pub cheap_venue: String,
+    pub cheap_pool: Option<String>,
+    pub cheap_price: f64,
+    pub rich_venue: String,
+    pub rich_pool: Option<String>,
+    pub rich_price: f64,
+    pub spread_percent: f64,
+    pub venue_count: i32,
+}
+
+/// One divergence episode: opened by the first scan that found the spread, updated with the
+/// latest quotes while it persists, and closed by the first scan that no longer finds it
+#[derive(Debug, FromQueryResult, Serialize)]
+pub struct PriceDivergence {
+    pub id: i64,
+    pub chain_id: i64,
+    pub pair: String,
+    /// `None` for episodes stored before pairs were keyed by address
+    pub base_address: Option<String>,
+    pub quote_address: Option<String>,
+    pub cheap_venue: String,
+    pub cheap_pool: Option<String>,
+    pub cheap_price: f64,
+    pub rich_venue: String,
+    pub rich_pool: Option<String>,
+    pub rich_price: f64,
+    /// Latest spread, and the widest one seen during the episode
+    pub spread_percent: f64,
+    pub max_spread_percent: f64,
+    pub venue_count: i32,
+    /// When the episode opened
+    pub observed_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// `None` while the divergence persists
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DivergenceHistoryResponse {
+    pub threshold_percent: f64,
+    pub divergences: Vec<PriceDivergence>,
+}
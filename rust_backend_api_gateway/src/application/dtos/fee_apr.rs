@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct FeeAprRequest {
+    pub pool_address: String,
+    /// Range bounds, token1 per token0
+    pub price_lower: f64,
+    pub price_upper: f64,
+    /// Position size in USD, defaults to 1000
+    pub capital: Option<f64>,
+    /// Current price, token1 per token0; fetched from klines when omitted
+    pub current_price: Option<f64>,
+    /// USD price of token1; assumed from a stablecoin side of the pair when omitted
+    pub token1_usd_price: Option<f64>,
+}
+
+/// Query string for `GET /data/pools/{address}/fee-apr`
+#[derive(Debug, Deserialize)]
+pub struct FeeAprQuery {
+    pub price_lower: f64,
+    pub price_upper: f64,
+    pub capital: Option<f64>,
+    pub current_price: Option<f64>,
+    pub token1_usd_price: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeeAprResponse {
+    pub pool_address: String,
+    pub protocol: String,
+    pub pair: String,
+    pub fee_tier: f64,
+    pub current_price: f64,
+    pub price_lower: f64,
+    pub price_upper: f64,
+    pub in_range: bool,
+    pub capital: f64,
+    pub daily_volume: f64,
+    /// Raw liquidity active at the current tick before the position is added
+    pub active_liquidity: f64,
+    pub position_liquidity: f64,
+    pub liquidity_share_percent: f64,
+    pub estimated_daily_fees: f64,
+    pub estimated_apr_percent: f64,
+    /// `daily_volume * fee / tvl * 365`, what a full-range position earns on average
+    pub pool_apr_percent: Option<f64>,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+pub struct IndicatorQuery {
+    /// Comma separated indicators, e.g. "ema20,rsi14,bb20,macd"
+    pub set: Option<String>,
+    pub interval: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndicatorResponse {
+    pub pair: String,
+    pub interval_minutes: u32,
+    pub timestamps: Vec<i64>,
+    pub close: Vec<f64>,
+    pub indicators: Vec<IndicatorSeries>,
+}
+
+/// One indicator's output lines, each aligned index-for-index with `timestamps`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndicatorSeries {
+    pub name: String,
+    pub lines: BTreeMap<String, Vec<Option<f64>>>,
+}
@@ -1,6 +1,20 @@
 pub mod ask;
 pub mod auth;
+pub mod backtest;
+pub mod chain;
 pub mod chat;
+pub mod correlation;
+pub mod divergence;
+pub mod fee_apr;
+pub mod indicators;
 pub mod liquidity_data;
+pub mod onchain_pool;
+pub mod pair_pools;
+pub mod pool_detail;
+pub mod pool_history;
+pub mod pool_list;
 pub mod position;
 pub mod price_history;
+pub mod range_optimizer;
+pub mod stream;
+pub mod token_price;
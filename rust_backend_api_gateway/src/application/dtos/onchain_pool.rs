@@ -0,0 +1,33 @@
+use crate::domain::services::data::Token;
+use serde::{Deserialize, Serialize};
+
+/// Pool state read from the contract, compared with what the REST provider listing it reports
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnChainPoolResponse {
+    pub chain_id: u64,
+    pub address: String,
+    pub token0: Token,
+    pub token1: Token,
+    /// Fee tier in percent, e.g. 0.3
+    pub fee_tier: f64,
+    pub tick_spacing: i32,
+    /// Raw uint values as decimal strings
+    pub sqrt_price_x96: String,
+    pub tick: i32,
+    pub liquidity: String,
+    pub fee_growth_global0_x128: String,
+    pub fee_growth_global1_x128: String,
+    /// Token1 per token0 from `sqrtPriceX96`
+    pub price: f64,
+    pub cross_check: Option<ProviderCrossCheck>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProviderCrossCheck {
+    pub provider: String,
+    pub provider_price: Option<f64>,
+    /// Provider price relative to the on-chain price
+    pub price_deviation_percent: Option<f64>,
+    pub provider_fee_tier: Option<f64>,
+    pub fee_tier_matches: bool,
+}
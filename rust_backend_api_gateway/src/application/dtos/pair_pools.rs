@@ -0,0 +1,41 @@
+use crate::application::dtos::pool_list::SourceStatus;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct PairPoolsQuery {
+    /// Amount of token B swapped into each pool when measuring price impact
+    pub trade_size: Option<f64>,
+    /// score (default), depth, fee_apr or price_impact
+    pub sort_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PairPoolsResponse {
+    pub token_a: String,
+    pub token_b: String,
+    /// Token B per token A
+    pub current_price: Option<f64>,
+    /// In token B
+    pub trade_size: f64,
+    pub sort_by: String,
+    pub pools: Vec<PairPoolRanking>,
+    pub sources: Vec<SourceStatus>,
+}
+
+/// One venue for the pair; depth and trade size are expressed in token B
+#[derive(Debug, Serialize)]
+pub struct PairPoolRanking {
+    pub rank: usize,
+    pub address: String,
+    pub protocol: String,
+    pub fee_tier: Option<f64>,
+    pub tvl: Option<f64>,
+    pub daily_volume: Option<f64>,
+    pub fee_apr_percent: Option<f64>,
+    /// Token B it takes to move the price 2% either way, both sides combined
+    pub depth_2_percent: Option<f64>,
+    /// Mid-price move caused by selling `trade_size` token B into the pool
+    pub price_impact_percent: Option<f64>,
+    /// 0-100, average of depth, fee APR and price impact relative to the best venue
+    pub score: f64,
+}
@@ -0,0 +1,51 @@
+use crate::domain::services::data::Token;
+use crate::domain::services::pool_risk::PoolRiskScore;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PoolDetailResponse {
+    pub chain_id: u64,
+    pub address: String,
+    pub protocol: String,
+    pub token0: Token,
+    pub token1: Token,
+    /// Fee tier in percent, e.g. 0.3
+    pub fee_tier: Option<f64>,
+    pub tick_spacing: Option<i32>,
+    /// Latest close, token1 per token0
+    pub current_price: Option<f64>,
+    pub current_tick: Option<i32>,
+    pub tvl: Option<f64>,
+    pub daily_volume: Option<f64>,
+    pub apr: Option<f64>,
+    pub liquidity: Option<LiquiditySummary>,
+    pub price_change_24h_percent: Option<f64>,
+    pub price_change_7d_percent: Option<f64>,
+    /// Only computed by the pool detail endpoint
+    pub risk: Option<PoolRiskScore>,
+    /// Fee APR of a position in the default range; only computed by the pool detail endpoint
+    pub fee_apr: Option<RangeFeeApr>,
+}
+
+/// Fee APR estimate for a position of `capital` USD in `[price_lower, price_upper]`, from its
+/// share of the liquidity active at the current price
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RangeFeeApr {
+    pub price_lower: f64,
+    pub price_upper: f64,
+    pub capital: f64,
+    pub liquidity_share_percent: f64,
+    pub estimated_daily_fees: f64,
+    pub estimated_apr_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiquiditySummary {
+    pub initialized_ticks: usize,
+    pub lowest_tick: Option<i32>,
+    pub highest_tick: Option<i32>,
+    /// Raw liquidity active at the current tick
+    pub active_liquidity: Option<f64>,
+    /// Share of liquidity placed within ±2% of the current price
+    pub concentration_2_percent: Option<f64>,
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use sea_orm::FromQueryResult;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct PoolHistoryQuery {
+    /// tvl (default), daily_volume, apr or price
+    pub metric: Option<String>,
+    /// RFC 3339; defaults to 30 days before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// RFC 3339; defaults to now
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PoolHistoryResponse {
+    pub chain_id: u64,
+    pub pool_address: String,
+    pub metric: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub points: Vec<MetricPoint>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MetricPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// One stored pool snapshot, amounts read back as floats
+#[derive(Debug, FromQueryResult)]
+pub struct PoolSnapshot {
+    pub captured_at: DateTime<Utc>,
+    pub fee_tier: Option<i32>,
+    pub tvl: Option<f64>,
+    pub daily_volume: Option<f64>,
+    pub apr: Option<f64>,
+    pub price: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AprSustainabilityRequest {
+    pub pool_address: String,
+    /// Lookback window, defaults to 30
+    pub days: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AprSustainabilityResponse {
+    pub pool_address: String,
+    pub days: u32,
+    pub snapshots: usize,
+    /// SUSTAINABLE, DECLINING, VOLATILE, INCENTIVE_DRIVEN or INSUFFICIENT_DATA
+    pub verdict: String,
+    pub sustainable: bool,
+    pub current_apr: Option<f64>,
+    pub mean_apr: Option<f64>,
+    /// Standard deviation over mean
+    pub apr_variation: Option<f64>,
+    /// Fitted APR change over the window, in APR points
+    pub apr_trend: Option<f64>,
+    /// APR explained by swap fees alone (volume × fee / TVL)
+    pub mean_fee_apr: Option<f64>,
+    /// Share of the reported APR not explained by fees, e.g. incentives
+    pub incentive_share: Option<f64>,
+    pub tvl_change_percent: Option<f64>,
+    pub volume_change_percent: Option<f64>,
+    pub reasons: Vec<String>,
+}
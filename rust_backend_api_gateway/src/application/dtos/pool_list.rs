@@ -0,0 +1,44 @@
+use crate::domain::services::data::UnifiedPool;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PoolListQuery {
+    /// Symbol or address of either token
+    pub token: Option<String>,
+    pub protocol: Option<String>,
+    /// Defaults to 1000; pass 0 to include every pool
+    pub min_tvl: Option<f64>,
+    /// Defaults to 1000; pass 0 to include every pool
+    pub min_volume: Option<f64>,
+    pub min_apr: Option<f64>,
+    /// Fee tier in percent, e.g. 0.3
+    pub fee_tier: Option<f64>,
+    /// tvl, daily_volume, apr or fee_tier
+    pub sort_by: Option<String>,
+    /// asc or desc (default)
+    pub order: Option<String>,
+    pub limit: Option<usize>,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// v1 (default, deprecated) or v2 pool shape
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PoolListResponse {
+    pub pools: Vec<UnifiedPool>,
+    /// Matching pools across all pages
+    pub total: usize,
+    pub next_cursor: Option<String>,
+    pub sources: Vec<SourceStatus>,
+}
+
+/// Outcome of querying one provider, so callers can tell degraded data from a full list
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourceStatus {
+    pub provider: String,
+    pub status: String, // ok, error
+    pub latency_ms: u64,
+    pub pool_count: usize,
+    pub error: Option<String>,
+}
@@ -1,8 +1,114 @@
+use crate::domain::services::position::{NewPosition, Position};
+use crate::math::{MAX_TICK, MIN_TICK};
+
+/// A position opened on-chain, as recorded by the frontend after the mint transaction
 #[derive(serde::Deserialize)]
 pub struct AddPositionRequest {
     pub pb_key: String,
-    pub trans_id: i32,
-    pub left: f32,
-    pub right: f32,
-    pub value_locker: f32,
+    /// EVM chain id of the position, the default chain when omitted
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    pub pool_address: String,
+    /// DEX the pool belongs to, e.g. DragonSwap
+    #[serde(default)]
+    pub protocol: Option<String>,
+    pub token0_address: String,
+    pub token1_address: String,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    /// uint128 liquidity as a decimal string, since it does not fit an f64 exactly
+    pub liquidity: String,
+    /// Deposited raw token amounts as decimal strings
+    pub amount0: String,
+    pub amount1: String,
+    /// Position NFT id when minted through a position manager
+    #[serde(default)]
+    pub nft_token_id: Option<String>,
+    pub tx_hash: String,
+}
+
+impl AddPositionRequest {
+    /// Message for the first invalid field
+    pub fn validate(&self) -> Result<(), String> {
+        for (field, address) in [
+            ("pool_address", &self.pool_address),
+            ("token0_address", &self.token0_address),
+            ("token1_address", &self.token1_address),
+        ] {
+            if !is_hex(address, 40) {
+                return Err(format!("{} must be a 0x-prefixed 20-byte address", field));
+            }
+        }
+        if !is_hex(&self.tx_hash, 64) {
+            return Err("tx_hash must be a 0x-prefixed 32-byte hash".to_string());
+        }
+        if self.tick_lower >= self.tick_upper
+            || self.tick_lower < MIN_TICK
+            || self.tick_upper > MAX_TICK
+        {
+            return Err(format!(
+                "tick_lower must be below tick_upper, both within {} and {}",
+                MIN_TICK, MAX_TICK
+            ));
+        }
+
+        let amounts = [
+            ("liquidity", Some(&self.liquidity)),
+            ("amount0", Some(&self.amount0)),
+            ("amount1", Some(&self.amount1)),
+            ("nft_token_id", self.nft_token_id.as_ref()),
+        ];
+        for (field, value) in amounts {
+            if let Some(value) = value
+                && !is_unsigned_integer(value)
+            {
+                return Err(format!("{} must be an unsigned integer string", field));
+            }
+        }
+        // Stored as NUMERIC(39, 0), which holds any uint128 but not every 39-digit number
+        if self.liquidity.parse::<u128>().is_err() {
+            return Err("liquidity must fit a uint128".to_string());
+        }
+        Ok(())
+    }
+
+    /// Addresses and the tx hash are lower-cased so they match indexed events
+    pub fn into_new_position(self, chain_id: u64) -> NewPosition {
+        NewPosition {
+            chain_id,
+            pb_key: self.pb_key,
+            pool_address: self.pool_address.to_lowercase(),
+            protocol: self.protocol,
+            token0_address: self.token0_address.to_lowercase(),
+            token1_address: self.token1_address.to_lowercase(),
+            tick_lower: self.tick_lower,
+            tick_upper: self.tick_upper,
+            liquidity: self.liquidity,
+            amount0: self.amount0,
+            amount1: self.amount1,
+            nft_token_id: self.nft_token_id,
+            tx_hash: self.tx_hash.to_lowercase(),
+        }
+    }
+}
+
+/// A stored position with its deposit valued at current prices
+#[derive(serde::Serialize)]
+pub struct PositionResponse {
+    #[serde(flatten)]
+    pub position: Position,
+    /// Deposited `amount0` and `amount1` at the price oracle's current USD prices; `None` when
+    /// either token cannot be priced, e.g. for positions recorded before schema v2
+    pub deposit_value_usd: Option<f64>,
+}
+
+fn is_hex(value: &str, digits: usize) -> bool {
+    value.len() == digits + 2
+        && value.starts_with("0x")
+        && value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Digits only, at most 78 of them (the width of a uint256)
+fn is_unsigned_integer(value: &str) -> bool {
+    !value.is_empty() && value.len() <= 78 && value.chars().all(|c| c.is_ascii_digit())
 }
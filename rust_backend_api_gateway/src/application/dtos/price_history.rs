@@ -5,6 +5,8 @@ pub struct PriceHistoryResponse {
     pub pair: String,
     pub price_range: PriceRange,
     pub volatility: VolatilityInfo,
+    pub trend: TrendInfo,
+    pub pair_relationship: PairRelationshipInfo,
     pub data_points: usize,
     pub interval_minutes: u32,
     pub pool_info: Option<PoolInfo>,
@@ -18,13 +20,77 @@ pub struct PriceHistoryRequest {
     pub token1: String,
     pub interval: Option<u32>,
     pub limit: Option<u32>,
+    /// Monte Carlo return model: "gbm" (default) or "bootstrap"
+    pub model: Option<String>,
+    pub paths: Option<usize>,
+    /// Fixes the simulation seed so results are reproducible
+    pub seed: Option<u64>,
+    /// conservative, balanced (default) or aggressive
+    pub risk_profile: Option<String>,
+    /// Pool-wide fee APR in percent used by the range optimizer
+    pub pool_apr: Option<f64>,
+    /// Pool to describe in `pool_info`; defaults to the deepest pool for the pair
+    pub pool_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RecommendationOptions {
+    pub model: Option<String>,
+    pub paths: Option<usize>,
+    pub seed: Option<u64>,
+    pub risk_profile: Option<String>,
+    pub pool_apr: Option<f64>,
+    pub pool_address: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RecommendationContext {
+    /// Last close, which the suggested and candidate ranges are centred on
     pub center_price: f64,
     pub suggested_range_width_percent: f64,
-    pub trend: String, // UPWARD, DOWNWARD, SIDEWAYS
+    pub trend: String,             // UPWARD, DOWNWARD, SIDEWAYS
+    pub pair_relationship: String, // PEGGED, CORRELATED, UNCORRELATED
+    pub suggested_range: Option<SuggestedRange>,
+    pub range_survival: Vec<RangeSurvival>,
+}
+
+/// How the two tokens of a pair move against each other
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PairRelationshipInfo {
+    pub classification: String, // PEGGED, CORRELATED, UNCORRELATED
+    /// Correlation of the legs' USD log returns, when both legs could be priced
+    pub correlation: Option<f64>,
+    pub latest_rolling_correlation: Option<f64>,
+    pub correlation_window: usize,
+    /// Mean-reversion half-life of the price ratio, `None` when it does not revert
+    pub half_life_hours: Option<f64>,
+    pub ratio_daily_volatility_percent: f64,
+    pub max_deviation_percent: f64,
+    /// Quote token the legs were priced in
+    pub legs_quote: Option<String>,
+}
+
+/// Range chosen by the optimizer around `center_price`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuggestedRange {
+    pub risk_profile: String,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub expected_fee_return_percent: f64,
+    pub expected_il_percent: f64,
+    pub expected_time_in_range_percent: f64,
+    pub horizon_days: u32,
+}
+
+/// Monte Carlo probability of the price staying inside a ±width range around the centre
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RangeSurvival {
+    pub range_width_percent: f64,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub probability_1d: f64,
+    pub probability_7d: f64,
+    pub probability_30d: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,16 +102,44 @@ pub struct PriceRange {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VolatilityInfo {
+    /// Daily close-to-close volatility of log returns
     pub value: f64,
     pub percentage: f64,
     pub level: String, // LOW, MEDIUM, HIGH
+    pub realized_annualized: f64,
+    pub parkinson_annualized: f64,
+    pub garman_klass_annualized: f64,
+    pub atr: Option<f64>,
+    pub atr_percent: Option<f64>,
+    pub rolling_window: usize,
+    pub rolling_annualized: Vec<RollingVolatility>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RollingVolatility {
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrendInfo {
+    pub direction: String, // UPWARD, DOWNWARD, SIDEWAYS
+    /// Regression slope of ln(price) per candle
+    pub slope: f64,
+    pub r_squared: f64,
+    pub change_percent: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PoolInfo {
     pub pool_id: Option<String>,
+    pub protocol: Option<String>,
     pub tvl: Option<f64>,
     pub fee_tier: Option<f64>,
+    pub tick_spacing: Option<i32>,
+    pub current_tick: Option<i32>,
+    pub daily_volume: Option<f64>,
+    pub apr: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
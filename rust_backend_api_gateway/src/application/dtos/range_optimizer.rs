@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct OptimalRangeRequest {
+    pub token0: String,
+    pub token1: String,
+    pub interval: Option<u32>,
+    pub limit: Option<u32>,
+    /// Pool fee tier in percent, e.g. 0.3; determines the tick spacing
+    pub fee_tier: f64,
+    /// Pool-wide fee APR in percent
+    pub pool_apr: f64,
+    pub token0_decimals: Option<u32>,
+    pub token1_decimals: Option<u32>,
+    /// Capital to deploy in token1 units, used to size the mint amounts
+    pub capital: Option<f64>,
+    /// conservative, balanced (default) or aggressive
+    pub risk_profile: Option<String>,
+    pub horizon_days: Option<u32>,
+    /// Monte Carlo return model: "gbm" (default) or "bootstrap"
+    pub model: Option<String>,
+    pub paths: Option<usize>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OptimalRangeResponse {
+    pub pair: String,
+    pub risk_profile: String,
+    pub current_price: f64,
+    pub horizon_days: u32,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub tick_spacing: i32,
+    pub current_tick: i32,
+    pub price_lower: f64,
+    pub price_upper: f64,
+    pub expected_fee_return_percent: f64,
+    pub expected_il_percent: f64,
+    pub expected_net_return_percent: f64,
+    pub expected_time_in_range_percent: f64,
+    pub mint: Option<MintAmounts>,
+}
+
+/// Token amounts for `LiquidityManager.mintLiquidity(amount0Max, amount1Max, tickLower, tickUpper, ...)`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MintAmounts {
+    pub amount0: f64,
+    pub amount1: f64,
+    /// Raw integer amounts scaled by the token decimals
+    pub amount0_max: String,
+    pub amount1_max: String,
+}
@@ -0,0 +1,173 @@
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Kline interval used when a `klines` topic names none, in minutes
+pub const DEFAULT_KLINE_INTERVAL: u32 = 15;
+
+/// Query parameters of `GET /stream`; WebSocket clients may also subscribe after connecting
+#[derive(Debug, Deserialize)]
+pub struct StreamQuery {
+    /// Comma-separated topics, e.g. `klines:SEI/USDC:15,pool:0x...,ticks:0x...`
+    pub topics: Option<String>,
+}
+
+/// Something clients can subscribe to on `/stream`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// Latest Sailor candle of a pair: `klines:<token0>/<token1>[:<interval minutes>]`
+    Klines {
+        token0: String,
+        token1: String,
+        interval: u32,
+    },
+    /// TVL, volume, APR and price of a pool: `pool:<address>`
+    PoolStats { pool_address: String },
+    /// Ticks whose net liquidity changed in a pool: `ticks:<address>`
+    Ticks { pool_address: String },
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::Klines {
+                token0,
+                token1,
+                interval,
+            } => write!(f, "klines:{}/{}:{}", token0, token1, interval),
+            Topic::PoolStats { pool_address } => write!(f, "pool:{}", pool_address),
+            Topic::Ticks { pool_address } => write!(f, "ticks:{}", pool_address),
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = anyhow::Error;
+
+    /// Symbols are upper-cased and addresses lower-cased, so equal topics share one poller
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (kind, rest) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Topic '{}' must look like kind:target", s))?;
+
+        match kind.to_lowercase().as_str() {
+            "klines" => {
+                let (pair, interval) = match rest.split_once(':') {
+                    Some((pair, interval)) => (
+                        pair,
+                        interval
+                            .parse()
+                            .ok()
+                            .filter(|i| *i > 0)
+                            .ok_or_else(|| anyhow!("Invalid kline interval in '{}'", s))?,
+                    ),
+                    None => (rest, DEFAULT_KLINE_INTERVAL),
+                };
+                match pair.split_once('/') {
+                    Some((token0, token1)) if !token0.is_empty() && !token1.is_empty() => {
+                        Ok(Topic::Klines {
+                            token0: token0.to_uppercase(),
+                            token1: token1.to_uppercase(),
+                            interval,
+                        })
+                    }
+                    _ => Err(anyhow!("Topic '{}' must name a pair as token0/token1", s)),
+                }
+            }
+            "pool" | "ticks" => {
+                if !is_address(rest) {
+                    return Err(anyhow!("Topic '{}' must name a pool address", s));
+                }
+                let pool_address = rest.to_lowercase();
+                if kind.eq_ignore_ascii_case("pool") {
+                    Ok(Topic::PoolStats { pool_address })
+                } else {
+                    Ok(Topic::Ticks { pool_address })
+                }
+            }
+            _ => Err(anyhow!(
+                "Unsupported topic '{}'; expected klines, pool or ticks",
+                s
+            )),
+        }
+    }
+}
+
+fn is_address(value: &str) -> bool {
+    value.len() == 42
+        && value.starts_with("0x")
+        && value[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parse a comma-separated topic list, skipping blanks
+pub fn parse_topics(topics: &str) -> Result<Vec<Topic>> {
+    topics
+        .split(',')
+        .filter(|topic| !topic.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Message a WebSocket client sends to change its subscriptions
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum ClientMessage {
+    Subscribe { topics: Vec<String> },
+    Unsubscribe { topics: Vec<String> },
+}
+
+/// One message pushed to clients; `event` is `update` or `error` for topic messages, and
+/// `subscribed`, `unsubscribed`, `lagged` or `error` for control messages
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamEvent {
+    pub event: String,
+    pub topic: Option<String>,
+    pub chain_id: Option<u64>,
+    pub data: Value,
+    pub sent_at: DateTime<Utc>,
+}
+
+impl StreamEvent {
+    pub fn new(event: &str, topic: Option<String>, chain_id: Option<u64>, data: Value) -> Self {
+        Self {
+            event: event.to_string(),
+            topic,
+            chain_id,
+            data,
+            sent_at: Utc::now(),
+        }
+    }
+}
+
+/// Pool fields pushed on `pool:<address>`
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PoolStatsUpdate {
+    pub address: String,
+    pub protocol: String,
+    pub pair: String,
+    pub tvl: Option<f64>,
+    pub daily_volume: Option<f64>,
+    pub apr: Option<f64>,
+    /// Token1 per token0, from the pool's token USD prices
+    pub price: Option<f64>,
+}
+
+/// Tick changes pushed on `ticks:<address>`; the first update carries every tick
+#[derive(Debug, Serialize)]
+pub struct TickUpdate {
+    pub snapshot: bool,
+    /// Ticks added or whose net liquidity changed
+    pub changed: Vec<TickLiquidity>,
+    /// Ticks that no longer hold liquidity
+    pub removed: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TickLiquidity {
+    pub tick: i32,
+    pub liquidity_net: String,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokenPriceResponse {
+    pub chain_id: u64,
+    pub address: String,
+    pub symbol: String,
+    /// As reported by the first provider listing the token
+    pub decimals: Option<u8>,
+    /// Median of the source quotes
+    pub usd_price: f64,
+    /// 0-1, from how many sources agree with the median and how fresh they are
+    pub confidence: f64,
+    /// Time of the oldest quote behind the price
+    pub as_of: DateTime<Utc>,
+    /// `as_of` is older than PRICE_MAX_AGE_SECS, e.g. when only stored snapshots were available
+    pub stale: bool,
+    pub sources: Vec<SourcePrice>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SourcePrice {
+    /// dragonswap, sailor, binance, onchain_twap (sailor_kline_twap when the pool oracle cannot be
+    /// read), or snapshot:<source> for stored prices
+    pub source: String,
+    pub usd_price: f64,
+    pub observed_at: DateTime<Utc>,
+    pub deviation_percent: f64,
+    /// Further from the median than the agreement band, so it does not add confidence
+    pub outlier: bool,
+}
@@ -0,0 +1,262 @@
+use crate::application::dtos::divergence::{DivergenceObservation, PriceDivergence};
+use crate::application::service::snapshot_service::sql_placeholders;
+use crate::application::use_cases::scan_price_divergences;
+use crate::config::{
+    Chains, divergence_min_tvl, divergence_poll_interval_secs, divergence_retention_days,
+    divergence_threshold_percent,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, FromQueryResult, Statement, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Filters for reading divergence history
+pub struct DivergenceFilter {
+    pub chain_id: u64,
+    /// Uppercase symbols, matched in either order
+    pub pair: Option<(String, String)>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub min_spread: Option<f64>,
+    pub limit: u64,
+}
+
+/// Open a divergence episode for the pair, or update its open one with the latest quotes
+pub async fn record_divergence(
+    db: &DatabaseConnection,
+    divergence: &DivergenceObservation,
+) -> Result<(), DbErr> {
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        r#"INSERT INTO price_divergences
+            (chain_id, pair, base_address, quote_address, cheap_venue, cheap_pool, cheap_price, rich_venue, rich_pool, rich_price, spread_percent, max_spread_percent, venue_count)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11, $12)
+            ON CONFLICT (chain_id, base_address, quote_address) WHERE closed_at IS NULL
+            DO UPDATE SET
+                pair = EXCLUDED.pair,
+                cheap_venue = EXCLUDED.cheap_venue,
+                cheap_pool = EXCLUDED.cheap_pool,
+                cheap_price = EXCLUDED.cheap_price,
+                rich_venue = EXCLUDED.rich_venue,
+                rich_pool = EXCLUDED.rich_pool,
+                rich_price = EXCLUDED.rich_price,
+                spread_percent = EXCLUDED.spread_percent,
+                max_spread_percent = GREATEST(price_divergences.max_spread_percent, EXCLUDED.spread_percent),
+                venue_count = EXCLUDED.venue_count,
+                last_seen_at = NOW()"#,
+        [
+            (divergence.chain_id as i64).into(),
+            divergence.pair.clone().into(),
+            divergence.base_address.clone().into(),
+            divergence.quote_address.clone().into(),
+            divergence.cheap_venue.clone().into(),
+            divergence.cheap_pool.clone().into(),
+            divergence.cheap_price.into(),
+            divergence.rich_venue.clone().into(),
+            divergence.rich_pool.clone().into(),
+            divergence.rich_price.into(),
+            divergence.spread_percent.into(),
+            divergence.venue_count.into(),
+        ],
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Close the chain's open episodes whose pair is not among the latest scan's `divergences`
+pub async fn close_divergences(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    divergences: &[DivergenceObservation],
+) -> Result<u64, DbErr> {
+    let mut values: Vec<Value> = vec![(chain_id as i64).into()];
+    let mut sql =
+        "UPDATE price_divergences SET closed_at = NOW() WHERE chain_id = $1 AND closed_at IS NULL"
+            .to_string();
+    if !divergences.is_empty() {
+        let pairs: Vec<String> = divergences
+            .iter()
+            .map(|divergence| {
+                values.push(divergence.base_address.clone().into());
+                values.push(divergence.quote_address.clone().into());
+                format!("(${}, ${})", values.len() - 1, values.len())
+            })
+            .collect();
+        sql.push_str(&format!(
+            " AND (base_address, quote_address) NOT IN ({})",
+            pairs.join(", ")
+        ));
+    }
+
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            &sql,
+            values,
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Delete episodes that closed before `before`
+pub async fn prune_divergences(
+    db: &DatabaseConnection,
+    before: DateTime<Utc>,
+) -> Result<u64, DbErr> {
+    let result = db
+        .execute(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            "DELETE FROM price_divergences WHERE closed_at < $1",
+            [before.into()],
+        ))
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Stored divergences matching `filter`, newest first
+pub async fn get_divergences(
+    db: &DatabaseConnection,
+    filter: &DivergenceFilter,
+) -> Result<Vec<PriceDivergence>, DbErr> {
+    let mut conditions = vec!["chain_id = $1".to_string()];
+    let mut values: Vec<Value> = vec![(filter.chain_id as i64).into()];
+
+    if let Some((a, b)) = &filter.pair {
+        values.push(format!("{}/{}", a, b).into());
+        values.push(format!("{}/{}", b, a).into());
+        conditions.push(format!(
+            "pair IN (${}, ${})",
+            values.len() - 1,
+            values.len()
+        ));
+    }
+    // Episodes overlapping [from, to]
+    if let Some(from) = filter.from {
+        values.push(from.into());
+        conditions.push(format!(
+            "(closed_at IS NULL OR closed_at >= ${})",
+            values.len()
+        ));
+    }
+    if let Some(to) = filter.to {
+        values.push(to.into());
+        conditions.push(format!("observed_at <= ${}", values.len()));
+    }
+    if let Some(min_spread) = filter.min_spread {
+        values.push(min_spread.into());
+        conditions.push(format!("max_spread_percent >= ${}", values.len()));
+    }
+
+    values.push((filter.limit as i64).into());
+    let sql = format!(
+        "SELECT * FROM price_divergences WHERE {} ORDER BY observed_at DESC LIMIT ${}",
+        conditions.join(" AND "),
+        values.len()
+    );
+
+    PriceDivergence::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        &sql,
+        values,
+    ))
+    .all(db)
+    .await
+}
+
+#[derive(FromQueryResult)]
+struct PoolSpread {
+    cheap_pool: Option<String>,
+    rich_pool: Option<String>,
+    max_spread_percent: f64,
+}
+
+/// Widest divergence open at any point since `from` that last named each of the pools as its cheap or rich venue,
+/// keyed by lowercase address
+pub async fn get_pool_divergences(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    pool_addresses: &[String],
+    from: DateTime<Utc>,
+) -> Result<HashMap<String, f64>, DbErr> {
+    if pool_addresses.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut values: Vec<Value> = vec![(chain_id as i64).into(), from.into()];
+    values.extend(pool_addresses.iter().map(|a| a.to_lowercase().into()));
+    let addresses = sql_placeholders(3, pool_addresses.len());
+    let rows = PoolSpread::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        format!(
+            r#"SELECT cheap_pool, rich_pool, max_spread_percent
+            FROM price_divergences
+            WHERE chain_id = $1 AND (closed_at IS NULL OR closed_at >= $2) AND (LOWER(cheap_pool) IN ({0}) OR LOWER(rich_pool) IN ({0}))"#,
+            addresses
+        ),
+        values,
+    ))
+    .all(db)
+    .await?;
+
+    let mut spreads: HashMap<String, f64> = HashMap::new();
+    for row in rows {
+        for pool in [row.cheap_pool, row.rich_pool].into_iter().flatten() {
+            let widest = spreads.entry(pool.to_lowercase()).or_default();
+            *widest = widest.max(row.max_spread_percent);
+        }
+    }
+
+    Ok(spreads)
+}
+
+/// Scan the venues of every chain on a fixed interval for the lifetime of the server, opening an
+/// episode when a pair starts diverging, updating it while it persists and closing it once the
+/// spread is gone. Closed episodes older than `DIVERGENCE_RETENTION_DAYS` are pruned.
+pub fn spawn_divergence_monitor(db: DatabaseConnection, chains: Chains) {
+    let threshold_percent = divergence_threshold_percent();
+    let min_tvl = divergence_min_tvl();
+    let poll_interval = Duration::from_secs(divergence_poll_interval_secs().max(1));
+    let retention = chrono::Duration::days(divergence_retention_days());
+
+    info!(
+        "🔀 Divergence monitor every {:?}, threshold {}%",
+        poll_interval, threshold_percent
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(poll_interval);
+        loop {
+            ticker.tick().await;
+
+            for chain in chains.iter() {
+                let divergences =
+                    match scan_price_divergences(chain, threshold_percent, min_tvl).await {
+                        Ok(divergences) => divergences,
+                        Err(e) => {
+                            warn!("⚠️ Divergence scan on {} failed: {}", chain.name, e);
+                            continue;
+                        }
+                    };
+                for divergence in &divergences {
+                    if let Err(e) = record_divergence(&db, divergence).await {
+                        error!(
+                            "❌ Failed to store divergence for {}: {}",
+                            divergence.pair, e
+                        );
+                    }
+                }
+                match close_divergences(&db, chain.id, &divergences).await {
+                    Ok(0) => {}
+                    Ok(closed) => info!("🔀 {} divergences on {} closed", closed, chain.name),
+                    Err(e) => error!("❌ Failed to close divergences on {}: {}", chain.name, e),
+                }
+            }
+
+            if let Err(e) = prune_divergences(&db, Utc::now() - retention).await {
+                error!("❌ Failed to prune divergence history: {}", e);
+            }
+        }
+    });
+}
@@ -1,2 +1,6 @@
 pub mod chat_service;
-pub mod position_service;
+pub mod divergence_service;
+pub mod position_indexer;
+pub mod price_oracle;
+pub mod snapshot_service;
+pub mod stream_service;
@@ -0,0 +1,97 @@
+use crate::config::{
+    ChainConfig, Chains, indexer_block_range, indexer_confirmations, indexer_poll_interval_secs,
+};
+use crate::domain::repositories::position_repository::PositionRepository;
+use crate::infrastructure::data::liquidity_manager_logs::LiquidityManagerLogs;
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Apply the logs of the next confirmed block range, starting after the last indexed block or
+/// at the chain's start block. `true` while confirmed blocks are left to catch up on.
+pub async fn index_next_range(
+    chain: &ChainConfig,
+    logs: &LiquidityManagerLogs,
+    positions: &dyn PositionRepository,
+) -> Result<bool> {
+    let contract = logs.contract();
+    let Some(confirmed) = logs
+        .latest_block()
+        .await?
+        .checked_sub(indexer_confirmations())
+    else {
+        return Ok(false);
+    };
+    let from_block = match positions.indexed_block(chain.id, &contract).await? {
+        Some(indexed) => indexed + 1,
+        None => chain.liquidity_manager_start_block,
+    };
+    if from_block > confirmed {
+        return Ok(false);
+    }
+
+    let to_block = confirmed.min(from_block + indexer_block_range() - 1);
+    let events = logs.events(from_block, to_block).await?;
+    positions
+        .apply_liquidity_events(chain.id, &contract, &events, to_block)
+        .await?;
+    if !events.is_empty() {
+        info!(
+            "📒 Indexed {} liquidity events on {} up to block {}",
+            events.len(),
+            chain.name,
+            to_block
+        );
+    }
+
+    Ok(to_block < confirmed)
+}
+
+/// One indexer per chain with a LIQUIDITY_MANAGER_ADDRESS. It only reads blocks
+/// INDEXER_CONFIRMATIONS behind the head, and records how far it got in the same transaction
+/// as the positions it changed, so a restart resumes without applying a log twice.
+pub fn spawn_position_indexer(positions: Arc<dyn PositionRepository>, chains: &Chains) {
+    let poll_interval = Duration::from_secs(indexer_poll_interval_secs());
+
+    for chain in chains.iter() {
+        let logs = match LiquidityManagerLogs::for_chain(chain) {
+            Ok(Some(logs)) => logs,
+            Ok(None) => continue,
+            Err(e) => {
+                error!(
+                    "❌ Position indexer for {} is misconfigured: {}",
+                    chain.name, e
+                );
+                continue;
+            }
+        };
+        info!(
+            "📒 Indexing LiquidityManager {} on {} from block {}, {} confirmations",
+            logs.contract(),
+            chain.name,
+            chain.liquidity_manager_start_block,
+            indexer_confirmations()
+        );
+
+        let chain = chain.clone();
+        let positions = positions.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(poll_interval);
+            loop {
+                ticker.tick().await;
+
+                loop {
+                    match index_next_range(&chain, &logs, positions.as_ref()).await {
+                        Ok(true) => continue,
+                        Ok(false) => break,
+                        Err(e) => {
+                            warn!("⚠️ Position indexing on {} failed: {}", chain.name, e);
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
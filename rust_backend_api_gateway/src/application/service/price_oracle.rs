@@ -0,0 +1,430 @@
+use crate::application::dtos::token_price::{SourcePrice, TokenPriceResponse};
+use crate::application::service::snapshot_service::get_latest_token_prices;
+use crate::application::use_cases::{binance_symbol, is_usd_stablecoin, query_pool_providers};
+use crate::config::{ChainConfig, price_max_age_secs};
+use crate::domain::services::data::{Token, UnifiedPool};
+use crate::infrastructure::data::{get_data_provider, onchain_provider};
+use crate::math::{mean, median, tick_to_price};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::join;
+use sea_orm::DatabaseConnection;
+use tracing::{debug, info, warn};
+
+/// Quotes within this distance of the median, in percent, count as agreeing
+const AGREEMENT_BAND_PERCENT: f64 = 2.0;
+/// Window of the pool TWAP read from the pool's on-chain oracle
+const TWAP_WINDOW_SECS: u32 = 3600;
+/// Five-minute Sailor candles averaged over the same window when the oracle cannot be read
+const TWAP_INTERVAL_MINUTES: u32 = 5;
+const TWAP_CANDLES: u32 = 12;
+
+/// Median USD price of a token across DEX providers, Binance and a DEX pool TWAP, falling back
+/// to stored snapshots when no live source answers
+#[derive(Clone)]
+pub struct PriceOracle {
+    db: DatabaseConnection,
+}
+
+/// One source's USD price for a token
+struct Quote {
+    source: String,
+    usd_price: f64,
+    observed_at: DateTime<Utc>,
+}
+
+/// Pool pairing a token with a USD stablecoin, whose on-chain oracle prices the token
+struct TwapPool {
+    address: String,
+    token_is_token0: bool,
+    token0_decimals: u32,
+    token1_decimals: u32,
+    tvl: f64,
+}
+
+impl TwapPool {
+    /// The pool as a TWAP source for the token, `None` unless its other side is a USD stablecoin
+    fn for_token(pool: &UnifiedPool, address: &str) -> Option<Self> {
+        let token_is_token0 = if pool.token0.address.eq_ignore_ascii_case(address) {
+            true
+        } else if pool.token1.address.eq_ignore_ascii_case(address) {
+            false
+        } else {
+            return None;
+        };
+        let quote = if token_is_token0 {
+            &pool.token1
+        } else {
+            &pool.token0
+        };
+        if !is_usd_stablecoin(&quote.symbol) {
+            return None;
+        }
+
+        Some(Self {
+            address: pool.id.clone(),
+            token_is_token0,
+            token0_decimals: pool.token0.decimals_or_default(),
+            token1_decimals: pool.token1.decimals_or_default(),
+            tvl: pool.tvl_f64().unwrap_or(0.0),
+        })
+    }
+
+    /// USD price of the token at a pool tick, taking the stablecoin at $1
+    fn usd_price(&self, tick: i32) -> f64 {
+        let price = tick_to_price(tick, self.token0_decimals, self.token1_decimals);
+        if self.token_is_token0 {
+            price
+        } else {
+            1.0 / price
+        }
+    }
+}
+
+impl PriceOracle {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// `None` when no provider on the chain lists the token
+    pub async fn token_price(
+        &self,
+        chain: &ChainConfig,
+        address: &str,
+    ) -> Result<Option<TokenPriceResponse>> {
+        info!("💲 Pricing token {} on {}", address, chain.name);
+
+        let now = Utc::now();
+        let mut listed: Option<Token> = None;
+        let mut quotes = Vec::new();
+        let mut twap_pool: Option<TwapPool> = None;
+        for provider_pools in query_pool_providers(chain).await {
+            let Ok(pools) = provider_pools.result else {
+                continue;
+            };
+            // The deepest stablecoin pool of any provider carries the on-chain TWAP
+            for pool in pools
+                .iter()
+                .filter_map(|pool| TwapPool::for_token(pool, address))
+            {
+                if twap_pool.as_ref().is_none_or(|best| pool.tvl > best.tvl) {
+                    twap_pool = Some(pool);
+                }
+            }
+            let Some(token) = pools
+                .iter()
+                .flat_map(|pool| [&pool.token0, &pool.token1])
+                .find(|token| token.address.eq_ignore_ascii_case(address))
+            else {
+                continue;
+            };
+
+            listed.get_or_insert_with(|| token.clone());
+            if let Some(usd_price) = token.usd_price.filter(|p| p.is_finite() && *p > 0.0) {
+                quotes.push(Quote {
+                    source: provider_pools.provider.clone(),
+                    usd_price,
+                    observed_at: now,
+                });
+            }
+        }
+        let Some(Token {
+            symbol, decimals, ..
+        }) = listed
+        else {
+            return Ok(None);
+        };
+
+        let (binance, twap) = join(
+            binance_quote(chain, &symbol),
+            twap_quote(chain, &symbol, twap_pool.as_ref()),
+        )
+        .await;
+        quotes.extend(binance);
+        quotes.extend(twap);
+
+        if quotes.is_empty() {
+            quotes = self.snapshot_quotes(chain.id, address).await?;
+        }
+        if quotes.is_empty() {
+            return Err(anyhow!("No price source has a USD price for {}", symbol));
+        }
+
+        Ok(Some(aggregate_quotes(
+            chain.id, address, &symbol, decimals, quotes, now,
+        )))
+    }
+
+    /// USD price of a token, or an error when it cannot be priced
+    pub async fn usd_price(&self, chain: &ChainConfig, address: &str) -> Result<f64> {
+        self.token_price(chain, address)
+            .await?
+            .map(|price| price.usd_price)
+            .ok_or_else(|| anyhow!("Token {} is not listed by any provider", address))
+    }
+
+    async fn snapshot_quotes(&self, chain_id: u64, address: &str) -> Result<Vec<Quote>> {
+        warn!("⚠️ No live price for {}, using stored snapshots", address);
+
+        Ok(get_latest_token_prices(&self.db, chain_id, address)
+            .await?
+            .into_iter()
+            .map(|snapshot| Quote {
+                source: format!("snapshot:{}", snapshot.source),
+                usd_price: snapshot.usd_price,
+                observed_at: snapshot.captured_at,
+            })
+            .collect())
+    }
+}
+
+/// Last one-minute close against USDT for tokens Binance lists
+async fn binance_quote(chain: &ChainConfig, symbol: &str) -> Option<Quote> {
+    let binance_symbol = binance_symbol(&symbol.to_uppercase())?;
+    if binance_symbol == "USDT" {
+        return None;
+    }
+    let data_provider = get_data_provider("binance", chain)?;
+
+    let last = data_provider
+        .get_price_data(binance_symbol, "USDT", 1, 1)
+        .await
+        .inspect_err(|e| debug!("No Binance price for {}: {}", symbol, e))
+        .ok()?
+        .pop()
+        .filter(|p| p.close > 0.0)?;
+    Some(Quote {
+        source: "binance".to_string(),
+        usd_price: last.close,
+        observed_at: DateTime::from_timestamp(last.tick + 60, 0).unwrap_or_else(Utc::now),
+    })
+}
+
+/// Time-weighted average price over the last hour of the token's deepest stablecoin pool, from
+/// the pool's on-chain oracle, or from Sailor candles when the oracle cannot be read
+async fn twap_quote(chain: &ChainConfig, symbol: &str, pool: Option<&TwapPool>) -> Option<Quote> {
+    if is_usd_stablecoin(symbol) {
+        return None;
+    }
+    match onchain_twap_quote(chain, pool).await {
+        Some(quote) => Some(quote),
+        None => kline_twap_quote(chain, symbol).await,
+    }
+}
+
+async fn onchain_twap_quote(chain: &ChainConfig, pool: Option<&TwapPool>) -> Option<Quote> {
+    let pool = pool?;
+    let reader = onchain_provider(chain)?;
+    let tick = reader
+        .twap_tick(pool.address.parse().ok()?, TWAP_WINDOW_SECS)
+        .await
+        .inspect_err(|e| debug!("No on-chain TWAP for pool {}: {}", pool.address, e))
+        .ok()?;
+
+    Some(Quote {
+        source: "onchain_twap".to_string(),
+        usd_price: Some(pool.usd_price(tick)).filter(|p| p.is_finite() && *p > 0.0)?,
+        observed_at: Utc::now(),
+    })
+}
+
+/// Mean of the last hour of the token's Sailor candles against USDC
+async fn kline_twap_quote(chain: &ChainConfig, symbol: &str) -> Option<Quote> {
+    let data_provider = get_data_provider("sailor", chain)?;
+
+    let closes: Vec<f64> = data_provider
+        .get_price_data(symbol, "USDC", TWAP_INTERVAL_MINUTES, TWAP_CANDLES)
+        .await
+        .inspect_err(|e| debug!("No Sailor kline TWAP for {}: {}", symbol, e))
+        .ok()?
+        .iter()
+        .map(|p| p.close)
+        .filter(|close| *close > 0.0)
+        .collect();
+    if closes.is_empty() {
+        return None;
+    }
+
+    // Candles are evenly spaced, so the plain mean is time-weighted
+    Some(Quote {
+        source: "sailor_kline_twap".to_string(),
+        usd_price: mean(&closes),
+        observed_at: Utc::now(),
+    })
+}
+
+fn aggregate_quotes(
+    chain_id: u64,
+    address: &str,
+    symbol: &str,
+    decimals: Option<u8>,
+    quotes: Vec<Quote>,
+    now: DateTime<Utc>,
+) -> TokenPriceResponse {
+    let prices: Vec<f64> = quotes.iter().map(|q| q.usd_price).collect();
+    let usd_price = median(&prices).unwrap_or_default();
+    let as_of = quotes.iter().map(|q| q.observed_at).min().unwrap_or(now);
+    let stale = now - as_of > Duration::seconds(price_max_age_secs());
+
+    let sources: Vec<SourcePrice> = quotes
+        .into_iter()
+        .map(|quote| {
+            let deviation_percent = (quote.usd_price / usd_price - 1.0) * 100.0;
+            SourcePrice {
+                source: quote.source,
+                usd_price: quote.usd_price,
+                observed_at: quote.observed_at,
+                deviation_percent,
+                outlier: deviation_percent.abs() > AGREEMENT_BAND_PERCENT,
+            }
+        })
+        .collect();
+
+    // Each agreeing source halves the remaining doubt; disagreement and staleness scale it down
+    let agreeing = sources.iter().filter(|s| !s.outlier).count();
+    let source_score = 1.0 - 0.5f64.powi(agreeing as i32);
+    let agreement = agreeing as f64 / sources.len().max(1) as f64;
+    let freshness = if stale { 0.5 } else { 1.0 };
+
+    TokenPriceResponse {
+        chain_id,
+        address: address.to_string(),
+        symbol: symbol.to_string(),
+        decimals,
+        usd_price,
+        confidence: source_score * agreement * freshness,
+        as_of,
+        stale,
+        sources,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn quote(source: &str, usd_price: f64, age: Duration, now: DateTime<Utc>) -> Quote {
+        Quote {
+            source: source.to_string(),
+            usd_price,
+            observed_at: now - age,
+        }
+    }
+
+    fn aggregate(quotes: Vec<Quote>, now: DateTime<Utc>) -> TokenPriceResponse {
+        aggregate_quotes(1329, "0xtoken", "SEI", Some(18), quotes, now)
+    }
+
+    #[test]
+    fn takes_the_median_and_flags_outliers() {
+        let now = Utc::now();
+        let price = aggregate(
+            vec![
+                quote("dragonswap", 0.30, Duration::zero(), now),
+                quote("sailor", 0.302, Duration::zero(), now),
+                quote("binance", 0.301, Duration::seconds(30), now),
+                quote("onchain_twap", 0.40, Duration::zero(), now),
+            ],
+            now,
+        );
+
+        assert!((price.usd_price - 0.3015).abs() < 1e-12);
+        assert!(!price.stale);
+        assert_eq!(price.as_of, now - Duration::seconds(30));
+        let outliers: Vec<&str> = price
+            .sources
+            .iter()
+            .filter(|s| s.outlier)
+            .map(|s| s.source.as_str())
+            .collect();
+        assert_eq!(outliers, ["onchain_twap"]);
+        // Three agreeing sources out of four
+        assert!((price.confidence - 0.875 * 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn halves_confidence_when_the_oldest_quote_is_stale() {
+        let now = Utc::now();
+        let fresh = aggregate(vec![quote("sailor", 2.0, Duration::zero(), now)], now);
+        let stale = aggregate(
+            vec![
+                quote("sailor", 2.0, Duration::zero(), now),
+                quote("snapshot:sailor", 2.0, Duration::days(1), now),
+            ],
+            now,
+        );
+
+        assert!(!fresh.stale);
+        assert_eq!(fresh.confidence, 0.5);
+        assert!(stale.stale);
+        assert_eq!(stale.as_of, now - Duration::days(1));
+        assert_eq!(stale.confidence, 0.75 * 0.5);
+    }
+
+    #[test]
+    fn a_single_quote_sets_the_price() {
+        let now = Utc::now();
+        let price = aggregate(vec![quote("binance", 42.0, Duration::zero(), now)], now);
+
+        assert_eq!(price.usd_price, 42.0);
+        assert_eq!(price.sources.len(), 1);
+        assert_eq!(price.sources[0].deviation_percent, 0.0);
+        assert!(!price.sources[0].outlier);
+    }
+
+    fn pool(token0: (&str, &str, u8), token1: (&str, &str, u8)) -> UnifiedPool {
+        let token = |(address, symbol, decimals): (&str, &str, u8)| Token {
+            chain_id: 1329,
+            address: address.to_string(),
+            symbol: symbol.to_string(),
+            decimals: Some(decimals),
+            usd_price: None,
+        };
+        UnifiedPool {
+            chain_id: 1329,
+            id: "0xpool".to_string(),
+            protocol: "Sailor".to_string(),
+            token0: token(token0),
+            token1: token(token1),
+            tvl: Some(Decimal::from(50_000)),
+            daily_volume: None,
+            apr: None,
+            fee_tier: None,
+            price: None,
+            risk: None,
+        }
+    }
+
+    #[test]
+    fn prices_tokens_from_the_tick_of_a_stablecoin_pool() {
+        let as_token0 =
+            TwapPool::for_token(&pool(("0xSEI", "WSEI", 18), ("0xusdc", "USDC", 6)), "0xsei")
+                .unwrap();
+        let as_token1 =
+            TwapPool::for_token(&pool(("0xusdc", "USDC", 6), ("0xsei", "WSEI", 18)), "0xSEI")
+                .unwrap();
+
+        // 0.3 USDC per WSEI: token1 per token0 is 0.3e-12 one way and 1e12 / 0.3 the other
+        let tick = (0.3e-12f64.ln() / 1.0001f64.ln()).round() as i32;
+        assert!((as_token0.usd_price(tick) - 0.3).abs() < 1e-4);
+        assert!((as_token1.usd_price(-tick) - 0.3).abs() < 1e-4);
+        assert_eq!(as_token0.tvl, 50_000.0);
+
+        let no_stablecoin = pool(("0xsei", "WSEI", 18), ("0xeth", "WETH", 18));
+        assert!(TwapPool::for_token(&no_stablecoin, "0xsei").is_none());
+        assert!(TwapPool::for_token(&no_stablecoin, "0xother").is_none());
+    }
+
+    #[test]
+    fn has_no_confidence_without_quotes() {
+        let now = Utc::now();
+        let price = aggregate(Vec::new(), now);
+
+        assert_eq!(price.usd_price, 0.0);
+        assert_eq!(price.confidence, 0.0);
+        assert_eq!(price.as_of, now);
+        assert!(!price.stale);
+        assert!(price.sources.is_empty());
+    }
+}
@@ -0,0 +1,325 @@
+use crate::application::dtos::pool_history::PoolSnapshot;
+use crate::application::use_cases::query_pool_providers;
+use crate::config::{Chains, snapshot_interval_secs};
+use crate::domain::services::data::UnifiedPool;
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, FromQueryResult, Statement, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// Rows per INSERT, well below Postgres' bind parameter limit
+const SNAPSHOT_BATCH_SIZE: usize = 500;
+
+pub async fn record_pool_snapshots(
+    db: &DatabaseConnection,
+    pools: &[UnifiedPool],
+    captured_at: DateTime<Utc>,
+) -> Result<(), DbErr> {
+    for batch in pools.chunks(SNAPSHOT_BATCH_SIZE) {
+        let rows = batch.iter().map(|pool| -> Vec<Value> {
+            vec![
+                (pool.chain_id as i64).into(),
+                pool.id.to_lowercase().into(),
+                pool.protocol.clone().into(),
+                pool.token0.symbol.clone().into(),
+                pool.token1.symbol.clone().into(),
+                pool.fee_tier.map(|f| f.hundredths_bip() as i32).into(),
+                pool.tvl.into(),
+                pool.daily_volume.into(),
+                pool.apr.into(),
+                pool.price.into(),
+                captured_at.into(),
+            ]
+        });
+        insert_rows(
+            db,
+            "pool_snapshots (chain_id, pool_address, protocol, token0_symbol, token1_symbol, fee_tier, tvl, daily_volume, apr, price, captured_at)",
+            rows,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// USD price of every token listed by the pools, once per token and provider
+pub async fn record_token_snapshots(
+    db: &DatabaseConnection,
+    pools: &[UnifiedPool],
+    captured_at: DateTime<Utc>,
+) -> Result<(), DbErr> {
+    let mut prices = HashMap::new();
+    for pool in pools {
+        for token in [&pool.token0, &pool.token1] {
+            if let Some(usd_price) = token.usd_price {
+                prices.insert(
+                    (
+                        pool.chain_id,
+                        token.address.to_lowercase(),
+                        pool.protocol.clone(),
+                    ),
+                    (token.symbol.clone(), usd_price),
+                );
+            }
+        }
+    }
+
+    let rows: Vec<Vec<Value>> = prices
+        .into_iter()
+        .map(|((chain_id, address, source), (symbol, usd_price))| {
+            vec![
+                (chain_id as i64).into(),
+                address.into(),
+                symbol.into(),
+                source.into(),
+                usd_price.into(),
+                captured_at.into(),
+            ]
+        })
+        .collect();
+    for batch in rows.chunks(SNAPSHOT_BATCH_SIZE) {
+        insert_rows(
+            db,
+            "token_snapshots (chain_id, token_address, symbol, source, usd_price, captured_at)",
+            batch.iter().cloned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Multi-row INSERT of equally long rows into `table_and_columns`
+async fn insert_rows(
+    db: &DatabaseConnection,
+    table_and_columns: &str,
+    rows: impl Iterator<Item = Vec<Value>>,
+) -> Result<(), DbErr> {
+    let mut placeholders = Vec::new();
+    let mut values = Vec::new();
+    for row in rows {
+        let start = values.len();
+        placeholders.push(format!("({})", sql_placeholders(start + 1, row.len())));
+        values.extend(row);
+    }
+    if placeholders.is_empty() {
+        return Ok(());
+    }
+
+    db.execute(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        format!(
+            "INSERT INTO {} VALUES {}",
+            table_and_columns,
+            placeholders.join(", ")
+        ),
+        values,
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Snapshots of one pool between `from` and `to`, oldest first
+pub async fn get_pool_snapshots(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    pool_address: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<PoolSnapshot>, DbErr> {
+    PoolSnapshot::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        r#"SELECT captured_at, fee_tier, tvl::float8 AS tvl, daily_volume::float8 AS daily_volume, apr, price
+            FROM pool_snapshots
+            WHERE chain_id = $1 AND pool_address = $2 AND captured_at >= $3 AND captured_at <= $4
+            ORDER BY captured_at"#,
+        [
+            (chain_id as i64).into(),
+            pool_address.to_lowercase().into(),
+            from.into(),
+            to.into(),
+        ],
+    ))
+    .all(db)
+    .await
+}
+
+#[derive(FromQueryResult)]
+struct AddressedSnapshot {
+    pool_address: String,
+    captured_at: DateTime<Utc>,
+    fee_tier: Option<i32>,
+    tvl: Option<f64>,
+    daily_volume: Option<f64>,
+    apr: Option<f64>,
+    price: Option<f64>,
+}
+
+/// Snapshots since `from` of each of the pools, keyed by lowercase address, oldest first
+pub async fn get_recent_pool_snapshots(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    pool_addresses: &[String],
+    from: DateTime<Utc>,
+) -> Result<HashMap<String, Vec<PoolSnapshot>>, DbErr> {
+    if pool_addresses.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut values: Vec<Value> = vec![(chain_id as i64).into(), from.into()];
+    values.extend(pool_addresses.iter().map(|a| a.to_lowercase().into()));
+    let rows = AddressedSnapshot::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        format!(
+            r#"SELECT pool_address, captured_at, fee_tier, tvl::float8 AS tvl, daily_volume::float8 AS daily_volume, apr, price
+            FROM pool_snapshots
+            WHERE chain_id = $1 AND captured_at >= $2 AND pool_address IN ({})
+            ORDER BY captured_at"#,
+            sql_placeholders(3, pool_addresses.len())
+        ),
+        values,
+    ))
+    .all(db)
+    .await?;
+
+    let mut snapshots: HashMap<String, Vec<PoolSnapshot>> = HashMap::new();
+    for row in rows {
+        snapshots
+            .entry(row.pool_address)
+            .or_default()
+            .push(PoolSnapshot {
+                captured_at: row.captured_at,
+                fee_tier: row.fee_tier,
+                tvl: row.tvl,
+                daily_volume: row.daily_volume,
+                apr: row.apr,
+                price: row.price,
+            });
+    }
+
+    Ok(snapshots)
+}
+
+#[derive(FromQueryResult)]
+struct TokenFirstSeen {
+    token_address: String,
+    first_seen: DateTime<Utc>,
+}
+
+/// When each token's price was first snapshotted, keyed by lowercase address
+pub async fn get_token_first_seen(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    token_addresses: &[String],
+) -> Result<HashMap<String, DateTime<Utc>>, DbErr> {
+    if token_addresses.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut values: Vec<Value> = vec![(chain_id as i64).into()];
+    values.extend(token_addresses.iter().map(|a| a.to_lowercase().into()));
+    let rows = TokenFirstSeen::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        format!(
+            r#"SELECT token_address, MIN(captured_at) AS first_seen
+            FROM token_snapshots
+            WHERE chain_id = $1 AND token_address IN ({})
+            GROUP BY token_address"#,
+            sql_placeholders(2, token_addresses.len())
+        ),
+        values,
+    ))
+    .all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.token_address, row.first_seen))
+        .collect())
+}
+
+#[derive(FromQueryResult)]
+pub struct TokenPriceSnapshot {
+    pub source: String,
+    pub usd_price: f64,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// Most recent snapshotted USD price of a token from each source
+pub async fn get_latest_token_prices(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    token_address: &str,
+) -> Result<Vec<TokenPriceSnapshot>, DbErr> {
+    TokenPriceSnapshot::find_by_statement(Statement::from_sql_and_values(
+        db.get_database_backend(),
+        r#"SELECT DISTINCT ON (source) source, usd_price, captured_at
+            FROM token_snapshots
+            WHERE chain_id = $1 AND token_address = $2
+            ORDER BY source, captured_at DESC"#,
+        [
+            (chain_id as i64).into(),
+            token_address.to_lowercase().into(),
+        ],
+    ))
+    .all(db)
+    .await
+}
+
+/// `$first, $first+1, ...` for `count` bind parameters
+pub fn sql_placeholders(first: usize, count: usize) -> String {
+    (first..first + count)
+        .map(|i| format!("${}", i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Snapshot every pool and token price of every chain on a fixed cadence for the lifetime of the
+/// server
+pub fn spawn_snapshot_scheduler(db: DatabaseConnection, chains: Chains) {
+    let cadence = Duration::from_secs(snapshot_interval_secs().max(60));
+    info!("📸 Pool snapshots every {:?}", cadence);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(cadence);
+        loop {
+            ticker.tick().await;
+
+            let captured_at = Utc::now();
+            for chain in chains.iter() {
+                let pools: Vec<UnifiedPool> = query_pool_providers(chain)
+                    .await
+                    .into_iter()
+                    .filter_map(|provider_pools| provider_pools.result.ok())
+                    .flatten()
+                    .collect();
+                if pools.is_empty() {
+                    warn!("⚠️ No pools to snapshot on {}", chain.name);
+                    continue;
+                }
+
+                if let Err(e) = record_pool_snapshots(&db, &pools, captured_at).await {
+                    error!("❌ Failed to store pool snapshots: {}", e);
+                }
+                if let Err(e) = record_token_snapshots(&db, &pools, captured_at).await {
+                    error!("❌ Failed to store token snapshots: {}", e);
+                }
+                info!("📸 Snapshotted {} pools on {}", pools.len(), chain.name);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_placeholders_from_the_first() {
+        assert_eq!(sql_placeholders(1, 3), "$1, $2, $3");
+        assert_eq!(sql_placeholders(12, 1), "$12");
+        assert_eq!(sql_placeholders(4, 0), "");
+    }
+}
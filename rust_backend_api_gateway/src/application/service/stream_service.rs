@@ -0,0 +1,459 @@
+use crate::application::dtos::stream::{
+    PoolStatsUpdate, StreamEvent, TickLiquidity, TickUpdate, Topic,
+};
+use crate::application::use_cases::find_pool;
+use crate::config::{
+    ChainConfig, stream_client_buffer, stream_max_topics, stream_poll_interval_secs,
+};
+use crate::domain::repositories::dex_provider::DexProvider;
+use crate::infrastructure::data::require_data_provider;
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::MissedTickBehavior;
+use tokio_stream::StreamExt;
+use tokio_stream::StreamMap;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tracing::{debug, info, warn};
+
+/// A topic on one chain; every subscriber to the same key shares one poller
+pub type TopicKey = (u64, Topic);
+
+/// Fans topic updates out to `/stream` clients, running one poller per subscribed topic for
+/// as long as anyone listens
+#[derive(Clone)]
+pub struct StreamHub {
+    topics: Arc<Mutex<HashMap<TopicKey, TopicChannel>>>,
+    poll_interval: Duration,
+    buffer: usize,
+}
+
+struct TopicChannel {
+    sender: broadcast::Sender<Arc<StreamEvent>>,
+    /// Full current state, replayed to new subscribers and to clients that fell behind
+    latest: Option<Arc<StreamEvent>>,
+}
+
+/// What a poller remembers between polls to tell whether anything changed
+enum PollState {
+    Klines(Option<Value>),
+    PoolStats(Option<PoolStatsUpdate>),
+    Ticks {
+        dex_provider: Option<Box<dyn DexProvider>>,
+        ticks: Option<BTreeMap<i32, String>>,
+    },
+}
+
+/// An update to push and the full state that replaces the topic's latest snapshot
+struct Change {
+    update: Value,
+    snapshot: Value,
+}
+
+impl StreamHub {
+    pub fn new() -> Self {
+        Self {
+            topics: Arc::new(Mutex::new(HashMap::new())),
+            poll_interval: Duration::from_secs(stream_poll_interval_secs()),
+            buffer: stream_client_buffer(),
+        }
+    }
+
+    /// Receiver for the topic's updates plus its latest state, starting a poller for topics
+    /// nobody listened to yet
+    pub fn subscribe(
+        &self,
+        chain: &ChainConfig,
+        topic: Topic,
+    ) -> (
+        broadcast::Receiver<Arc<StreamEvent>>,
+        Option<Arc<StreamEvent>>,
+    ) {
+        let key = (chain.id, topic);
+        let mut topics = self.topics.lock().expect("stream topics lock poisoned");
+        if let Some(channel) = topics.get(&key) {
+            return (channel.sender.subscribe(), channel.latest.clone());
+        }
+
+        let (sender, receiver) = broadcast::channel(self.buffer);
+        topics.insert(
+            key.clone(),
+            TopicChannel {
+                sender: sender.clone(),
+                latest: None,
+            },
+        );
+        info!("📡 Starting stream poller for {} on {}", key.1, chain.name);
+        tokio::spawn(poll_topic(self.clone(), chain.clone(), key, sender));
+        (receiver, None)
+    }
+
+    /// Full current state of a topic, for clients that skipped updates
+    pub fn latest(&self, key: &TopicKey) -> Option<Arc<StreamEvent>> {
+        let topics = self.topics.lock().expect("stream topics lock poisoned");
+        topics.get(key).and_then(|channel| channel.latest.clone())
+    }
+
+    fn publish(&self, key: &TopicKey, event: StreamEvent, snapshot: Option<StreamEvent>) {
+        let mut topics = self.topics.lock().expect("stream topics lock poisoned");
+        let Some(channel) = topics.get_mut(key) else {
+            return;
+        };
+        if let Some(snapshot) = snapshot {
+            channel.latest = Some(Arc::new(snapshot));
+        }
+        // Fails only when nobody listens, which the next poll notices
+        let _ = channel.sender.send(Arc::new(event));
+    }
+
+    /// Drop the topic once its last subscriber is gone. Checked under the lock `subscribe`
+    /// takes, so a client subscribing meanwhile keeps the poller alive.
+    fn release_if_unused(&self, key: &TopicKey) -> bool {
+        let mut topics = self.topics.lock().expect("stream topics lock poisoned");
+        match topics.get(key) {
+            Some(channel) if channel.sender.receiver_count() > 0 => false,
+            _ => {
+                topics.remove(key);
+                true
+            }
+        }
+    }
+}
+
+impl Default for StreamHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Topics one `/stream` connection listens to, merged into a single stream of events
+pub struct Subscriptions {
+    hub: StreamHub,
+    chain: ChainConfig,
+    streams: StreamMap<Topic, BroadcastStream<Arc<StreamEvent>>>,
+    max_topics: usize,
+}
+
+impl Subscriptions {
+    pub fn new(hub: StreamHub, chain: ChainConfig) -> Self {
+        Self {
+            hub,
+            chain,
+            streams: StreamMap::new(),
+            max_topics: stream_max_topics(),
+        }
+    }
+
+    /// Subscribe to every topic, returning a `subscribed` notice followed by the latest state
+    /// of topics that already have one
+    pub fn subscribe_all(&mut self, topics: Vec<Topic>) -> Result<Vec<Arc<StreamEvent>>> {
+        let names: Vec<String> = topics.iter().map(|topic| topic.to_string()).collect();
+        let mut events = Vec::new();
+        for topic in topics {
+            if self.streams.contains_key(&topic) {
+                continue;
+            }
+            if self.streams.len() >= self.max_topics {
+                return Err(anyhow!(
+                    "At most {} topics can be subscribed per connection",
+                    self.max_topics
+                ));
+            }
+            let (receiver, latest) = self.hub.subscribe(&self.chain, topic.clone());
+            self.streams.insert(topic, BroadcastStream::new(receiver));
+            events.extend(latest);
+        }
+
+        events.insert(0, self.control("subscribed", names));
+        Ok(events)
+    }
+
+    /// Stop listening to the topics; pollers stop once their last subscriber leaves
+    pub fn unsubscribe_all(&mut self, topics: Vec<Topic>) -> Arc<StreamEvent> {
+        let names: Vec<String> = topics
+            .into_iter()
+            .filter_map(|topic| self.streams.remove(&topic).map(|_| topic.to_string()))
+            .collect();
+        self.control("unsubscribed", names)
+    }
+
+    /// Next events for the client; never resolves while nothing is subscribed. A client that
+    /// fell behind gets a `lagged` notice and the topic's latest state instead of the updates
+    /// it missed.
+    pub async fn next(&mut self) -> Vec<Arc<StreamEvent>> {
+        if self.streams.is_empty() {
+            return std::future::pending().await;
+        }
+
+        match self.streams.next().await {
+            Some((_, Ok(event))) => vec![event],
+            Some((topic, Err(BroadcastStreamRecvError::Lagged(skipped)))) => {
+                debug!("Client lagged {} updates behind on {}", skipped, topic);
+                let mut events = vec![Arc::new(StreamEvent::new(
+                    "lagged",
+                    Some(topic.to_string()),
+                    Some(self.chain.id),
+                    serde_json::json!({ "skipped": skipped }),
+                ))];
+                events.extend(self.hub.latest(&(self.chain.id, topic)));
+                events
+            }
+            None => Vec::new(),
+        }
+    }
+
+    pub fn error(&self, message: String) -> Arc<StreamEvent> {
+        Arc::new(StreamEvent::new(
+            "error",
+            None,
+            Some(self.chain.id),
+            serde_json::json!({ "error": message }),
+        ))
+    }
+
+    fn control(&self, event: &str, topics: Vec<String>) -> Arc<StreamEvent> {
+        Arc::new(StreamEvent::new(
+            event,
+            None,
+            Some(self.chain.id),
+            serde_json::json!({ "topics": topics }),
+        ))
+    }
+}
+
+async fn poll_topic(
+    hub: StreamHub,
+    chain: ChainConfig,
+    key: TopicKey,
+    sender: broadcast::Sender<Arc<StreamEvent>>,
+) {
+    let topic = key.1.clone();
+    let topic_name = topic.to_string();
+    let mut state = match topic {
+        Topic::Klines { .. } => PollState::Klines(None),
+        Topic::PoolStats { .. } => PollState::PoolStats(None),
+        Topic::Ticks { .. } => PollState::Ticks {
+            dex_provider: None,
+            ticks: None,
+        },
+    };
+    let mut last_error: Option<String> = None;
+
+    let mut interval = tokio::time::interval(hub.poll_interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if sender.receiver_count() == 0 && hub.release_if_unused(&key) {
+            info!(
+                "📴 Stopping stream poller for {} on {}",
+                topic_name, chain.name
+            );
+            return;
+        }
+
+        match poll_once(&chain, &topic, &mut state).await {
+            Ok(Some(change)) => {
+                last_error = None;
+                let event = |data| {
+                    StreamEvent::new("update", Some(topic_name.clone()), Some(chain.id), data)
+                };
+                hub.publish(&key, event(change.update), Some(event(change.snapshot)));
+            }
+            Ok(None) => debug!("No change on {}", topic_name),
+            Err(e) => {
+                // Reported once per outage rather than on every poll
+                let message = e.to_string();
+                if last_error.as_ref() != Some(&message) {
+                    warn!("⚠️ Stream poll failed for {}: {}", topic_name, message);
+                    let event = StreamEvent::new(
+                        "error",
+                        Some(topic_name.clone()),
+                        Some(chain.id),
+                        serde_json::json!({ "error": message }),
+                    );
+                    hub.publish(&key, event, None);
+                    last_error = Some(message);
+                }
+            }
+        }
+    }
+}
+
+/// Poll a topic's source once, returning `None` when nothing changed since the last poll
+async fn poll_once(
+    chain: &ChainConfig,
+    topic: &Topic,
+    state: &mut PollState,
+) -> Result<Option<Change>> {
+    match (topic, state) {
+        (
+            Topic::Klines {
+                token0,
+                token1,
+                interval,
+            },
+            PollState::Klines(previous),
+        ) => {
+            let data_provider = require_data_provider("sailor", chain)?;
+            let candle = data_provider
+                .get_price_data(token0, token1, *interval, 1)
+                .await?
+                .pop()
+                .ok_or_else(|| anyhow!("No candles for {}/{}", token0, token1))?;
+
+            let candle = serde_json::to_value(candle)?;
+            Ok(
+                replace_if_changed(previous, candle.clone()).then(|| Change {
+                    update: candle.clone(),
+                    snapshot: candle,
+                }),
+            )
+        }
+        (Topic::PoolStats { pool_address }, PollState::PoolStats(previous)) => {
+            let (_, pool) = find_pool(chain, pool_address).await?;
+            let stats = PoolStatsUpdate {
+                pair: format!("{}/{}", pool.token0.symbol, pool.token1.symbol),
+                tvl: pool.tvl_f64(),
+                daily_volume: pool.daily_volume_f64(),
+                address: pool.id,
+                protocol: pool.protocol,
+                apr: pool.apr,
+                price: pool.price,
+            };
+
+            let data = serde_json::to_value(&stats)?;
+            Ok(replace_if_changed(previous, stats).then(|| Change {
+                update: data.clone(),
+                snapshot: data,
+            }))
+        }
+        (
+            Topic::Ticks { pool_address },
+            PollState::Ticks {
+                dex_provider,
+                ticks,
+            },
+        ) => {
+            // The pool's DEX is looked up once; only its ticks are polled afterwards
+            if dex_provider.is_none() {
+                *dex_provider = Some(find_pool(chain, pool_address).await?.0);
+            }
+            let liquidity = dex_provider
+                .as_ref()
+                .expect("dex provider resolved above")
+                .get_liquidity_data(pool_address)
+                .await?;
+            let current: BTreeMap<i32, String> = liquidity
+                .data
+                .into_iter()
+                .filter_map(|tick| Some((tick.tick_idx.parse().ok()?, tick.liquidity_net)))
+                .filter(|(_, liquidity_net)| liquidity_net != "0")
+                .collect();
+
+            let update = diff_ticks(ticks.as_ref(), &current);
+            if !update.snapshot && update.changed.is_empty() && update.removed.is_empty() {
+                return Ok(None);
+            }
+            let snapshot = serde_json::to_value(diff_ticks(None, &current))?;
+            *ticks = Some(current);
+            Ok(Some(Change {
+                update: serde_json::to_value(update)?,
+                snapshot,
+            }))
+        }
+        _ => Err(anyhow!("Poll state does not match topic {}", topic)),
+    }
+}
+
+/// Store `current` and report whether it differs from what was stored
+fn replace_if_changed<T: PartialEq>(previous: &mut Option<T>, current: T) -> bool {
+    if previous.as_ref() == Some(&current) {
+        return false;
+    }
+    *previous = Some(current);
+    true
+}
+
+/// Ticks added, changed or removed between two polls; a snapshot of every tick without a
+/// previous poll
+pub fn diff_ticks(
+    previous: Option<&BTreeMap<i32, String>>,
+    current: &BTreeMap<i32, String>,
+) -> TickUpdate {
+    let Some(previous) = previous else {
+        return TickUpdate {
+            snapshot: true,
+            changed: current
+                .iter()
+                .map(|(tick, liquidity_net)| TickLiquidity {
+                    tick: *tick,
+                    liquidity_net: liquidity_net.clone(),
+                })
+                .collect(),
+            removed: Vec::new(),
+        };
+    };
+
+    TickUpdate {
+        snapshot: false,
+        changed: current
+            .iter()
+            .filter(|(tick, liquidity_net)| previous.get(tick) != Some(liquidity_net))
+            .map(|(tick, liquidity_net)| TickLiquidity {
+                tick: *tick,
+                liquidity_net: liquidity_net.clone(),
+            })
+            .collect(),
+        removed: previous
+            .keys()
+            .filter(|tick| !current.contains_key(tick))
+            .copied()
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dtos::stream::parse_topics;
+
+    #[test]
+    fn normalizes_topics_so_equal_ones_share_a_poller() {
+        let topics = parse_topics(
+            "klines:sei/usdc, klines:SEI/USDC:15,pool:0x80FE558C54F1F43263E08F0E1FA3E02D8B897F93,",
+        )
+        .unwrap();
+
+        assert_eq!(topics[0], topics[1]);
+        assert_eq!(topics[0].to_string(), "klines:SEI/USDC:15");
+        assert_eq!(
+            topics[2].to_string(),
+            "pool:0x80fe558c54f1f43263e08f0e1fa3e02d8b897f93"
+        );
+        assert!("ticks:0x80fe".parse::<Topic>().is_err());
+        assert!("klines:SEI/USDC:0".parse::<Topic>().is_err());
+        assert!("trades:SEI/USDC".parse::<Topic>().is_err());
+    }
+
+    #[test]
+    fn diffs_ticks_between_polls() {
+        let previous = BTreeMap::from([(-60, "100".to_string()), (0, "50".to_string())]);
+        let current = BTreeMap::from([(0, "75".to_string()), (60, "-175".to_string())]);
+
+        let update = diff_ticks(Some(&previous), &current);
+        assert!(!update.snapshot);
+        assert_eq!(
+            update.changed.iter().map(|t| t.tick).collect::<Vec<_>>(),
+            [0, 60]
+        );
+        assert_eq!(update.removed, [-60]);
+
+        let unchanged = diff_ticks(Some(&current), &current);
+        assert!(unchanged.changed.is_empty() && unchanged.removed.is_empty());
+        assert_eq!(diff_ticks(None, &current).changed.len(), 2);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::application::dtos::correlation::{CorrelationResponse, RollingCorrelation};
+use crate::application::dtos::price_history::{PairRelationshipInfo, PricePoint};
+use crate::config::ChainConfig;
+use crate::domain::repositories::data_provider::DataProvider;
+use crate::infrastructure::data::require_data_provider;
+use crate::math::{PairCorrelation, align_closes, classify_pair};
+use anyhow::{Result, anyhow};
+use futures::future::try_join;
+use tracing::info;
+
+/// Quote token the legs of a pair are priced in
+pub const USD_QUOTE: &str = "USDC";
+pub const DEFAULT_CORRELATION_WINDOW: usize = 30;
+
+/// Correlation between two pairs, e.g. `SEI/USDC` against `WSEI/USDC`
+pub async fn analyze_pair_correlation(
+    chain: &ChainConfig,
+    pair_a: (&str, &str),
+    pair_b: (&str, &str),
+    interval: u32,
+    limit: u32,
+    window: usize,
+) -> Result<CorrelationResponse> {
+    info!(
+        "🔗 Correlating {}/{} with {}/{}",
+        pair_a.0, pair_a.1, pair_b.0, pair_b.1
+    );
+
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let (series_a, series_b) = try_join(
+        data_provider.get_price_data(pair_a.0, pair_a.1, interval, limit),
+        data_provider.get_price_data(pair_b.0, pair_b.1, interval, limit),
+    )
+    .await?;
+
+    let (ticks, closes_a, closes_b) = align_closes(&series_a, &series_b);
+    if ticks.len() < 3 {
+        return Err(anyhow!("Not enough overlapping price data for both pairs"));
+    }
+
+    let ratio: Vec<f64> = closes_a
+        .iter()
+        .zip(&closes_b)
+        .map(|(a, b)| if *b > 0.0 { a / b } else { 0.0 })
+        .collect();
+    let correlation = classify_pair(&ratio, Some((&closes_a, &closes_b)), interval, window);
+
+    Ok(correlation_response(
+        format!("{}/{}", pair_a.0, pair_a.1),
+        format!("{}/{}", pair_b.0, pair_b.1),
+        interval,
+        window,
+        &ticks,
+        &correlation,
+        None,
+    ))
+}
+
+/// Correlation between the two legs of `token0/token1`, each priced in USD
+pub async fn analyze_pair_legs(
+    chain: &ChainConfig,
+    token0: &str,
+    token1: &str,
+    interval: u32,
+    limit: u32,
+    window: usize,
+) -> Result<CorrelationResponse> {
+    info!("🔗 Correlating the USD legs of {}/{}", token0, token1);
+
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let (pair_data, legs) = try_join(
+        data_provider.get_price_data(token0, token1, interval, limit),
+        fetch_usd_legs(data_provider.as_ref(), token0, token1, interval, limit),
+    )
+    .await?;
+
+    if pair_data.len() < 3 {
+        return Err(anyhow!(
+            "No price data available for the specified token pair"
+        ));
+    }
+
+    let correlation = classify_with_legs(&pair_data, Some(&legs), interval, window);
+    let (ticks, _, _) = align_closes(&legs.0, &legs.1);
+
+    Ok(correlation_response(
+        format!("{}/{}", token0, USD_QUOTE),
+        format!("{}/{}", token1, USD_QUOTE),
+        interval,
+        window,
+        &ticks,
+        &correlation,
+        Some(USD_QUOTE),
+    ))
+}
+
+/// USD klines for both tokens of a pair; a stablecoin leg is a constant 1.0 series
+pub async fn fetch_usd_legs(
+    data_provider: &dyn DataProvider,
+    token0: &str,
+    token1: &str,
+    interval: u32,
+    limit: u32,
+) -> Result<(Vec<PricePoint>, Vec<PricePoint>)> {
+    match (is_usd_stablecoin(token0), is_usd_stablecoin(token1)) {
+        (true, true) => Err(anyhow!(
+            "Both {} and {} are USD stablecoins",
+            token0,
+            token1
+        )),
+        (true, false) => {
+            let leg1 = data_provider
+                .get_price_data(token1, USD_QUOTE, interval, limit)
+                .await?;
+            Ok((constant_series(&leg1), leg1))
+        }
+        (false, true) => {
+            let leg0 = data_provider
+                .get_price_data(token0, USD_QUOTE, interval, limit)
+                .await?;
+            let leg1 = constant_series(&leg0);
+            Ok((leg0, leg1))
+        }
+        (false, false) => {
+            try_join(
+                data_provider.get_price_data(token0, USD_QUOTE, interval, limit),
+                data_provider.get_price_data(token1, USD_QUOTE, interval, limit),
+            )
+            .await
+        }
+    }
+}
+
+/// Classify a pair from its own klines, using the USD legs for correlation when available
+pub fn classify_with_legs(
+    pair_data: &[PricePoint],
+    legs: Option<&(Vec<PricePoint>, Vec<PricePoint>)>,
+    interval: u32,
+    window: usize,
+) -> PairCorrelation {
+    let ratio: Vec<f64> = pair_data.iter().map(|p| p.close).collect();
+    let aligned = legs.map(|(leg0, leg1)| align_closes(leg0, leg1));
+
+    classify_pair(
+        &ratio,
+        aligned
+            .as_ref()
+            .map(|(_, leg0, leg1)| (leg0.as_slice(), leg1.as_slice())),
+        interval,
+        window,
+    )
+}
+
+pub fn relationship_info(
+    correlation: &PairCorrelation,
+    interval: u32,
+    window: usize,
+    legs_quote: Option<&str>,
+) -> PairRelationshipInfo {
+    PairRelationshipInfo {
+        classification: correlation.relationship.as_str().to_string(),
+        correlation: correlation.correlation,
+        latest_rolling_correlation: correlation.rolling_correlation.last().copied().flatten(),
+        correlation_window: window,
+        half_life_hours: correlation
+            .half_life_periods
+            .map(|periods| periods * interval as f64 / 60.0),
+        ratio_daily_volatility_percent: correlation.ratio_daily_volatility * 100.0,
+        max_deviation_percent: correlation.max_deviation * 100.0,
+        legs_quote: legs_quote.map(str::to_string),
+    }
+}
+
+pub fn is_usd_stablecoin(symbol: &str) -> bool {
+    let symbol = symbol.to_uppercase();
+    symbol.starts_with("USD") || symbol == "DAI"
+}
+
+fn constant_series(like: &[PricePoint]) -> Vec<PricePoint> {
+    like.iter()
+        .map(|p| PricePoint {
+            tick: p.tick,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: None,
+        })
+        .collect()
+}
+
+fn correlation_response(
+    pair_a: String,
+    pair_b: String,
+    interval: u32,
+    window: usize,
+    ticks: &[i64],
+    correlation: &PairCorrelation,
+    legs_quote: Option<&str>,
+) -> CorrelationResponse {
+    CorrelationResponse {
+        pair_a,
+        pair_b,
+        interval_minutes: interval,
+        data_points: ticks.len(),
+        relationship: relationship_info(correlation, interval, window, legs_quote),
+        rolling_correlation: correlation
+            .rolling_correlation
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| {
+                Some(RollingCorrelation {
+                    timestamp: *ticks.get(i + window)?,
+                    value: *value,
+                })
+            })
+            .collect(),
+    }
+}
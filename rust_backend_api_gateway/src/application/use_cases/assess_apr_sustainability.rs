@@ -0,0 +1,262 @@
+use crate::application::dtos::pool_history::{
+    AprSustainabilityRequest, AprSustainabilityResponse, PoolSnapshot,
+};
+use crate::application::service::snapshot_service::get_pool_snapshots;
+use crate::math::{linear_regression_xy, mean, std_dev};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use sea_orm::DatabaseConnection;
+use tracing::info;
+
+pub const DEFAULT_SUSTAINABILITY_DAYS: u32 = 30;
+const MIN_SNAPSHOTS: usize = 3;
+/// APR standard deviation over mean above which the APR is too erratic to rely on
+const MAX_APR_VARIATION: f64 = 0.5;
+/// Fitted APR drop over the window, relative to the mean, treated as a decline
+const MAX_APR_DECLINE: f64 = 0.25;
+/// Share of the APR not backed by swap fees above which it depends on incentives
+const MAX_INCENTIVE_SHARE: f64 = 0.5;
+/// Volume drop over the window that signals fee income drying up
+const MAX_VOLUME_DECLINE_PERCENT: f64 = -30.0;
+
+/// Judge whether a pool's current APR is likely to hold, from its stored snapshots
+pub async fn assess_apr_sustainability(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    request: &AprSustainabilityRequest,
+) -> Result<AprSustainabilityResponse> {
+    let days = request.days.unwrap_or(DEFAULT_SUSTAINABILITY_DAYS);
+    info!(
+        "🌱 Assessing APR sustainability of pool {} over {} days",
+        request.pool_address, days
+    );
+
+    let to = Utc::now();
+    let from = to - Duration::days(days as i64);
+    let snapshots = get_pool_snapshots(db, chain_id, &request.pool_address, from, to).await?;
+
+    Ok(assess_snapshots(&request.pool_address, days, &snapshots))
+}
+
+fn assess_snapshots(
+    pool_address: &str,
+    days: u32,
+    snapshots: &[PoolSnapshot],
+) -> AprSustainabilityResponse {
+    let aprs: Vec<(f64, f64)> = snapshots
+        .iter()
+        .filter_map(|s| {
+            let age_days =
+                (s.captured_at - snapshots[0].captured_at).num_seconds() as f64 / 86_400.0;
+            Some((age_days, s.apr?))
+        })
+        .collect();
+    let fee_aprs: Vec<f64> = snapshots.iter().filter_map(fee_apr).collect();
+
+    let mut response = AprSustainabilityResponse {
+        pool_address: pool_address.to_lowercase(),
+        days,
+        snapshots: snapshots.len(),
+        verdict: "INSUFFICIENT_DATA".to_string(),
+        sustainable: false,
+        current_apr: aprs.last().map(|(_, apr)| *apr),
+        mean_apr: None,
+        apr_variation: None,
+        apr_trend: None,
+        mean_fee_apr: (!fee_aprs.is_empty()).then(|| mean(&fee_aprs)),
+        incentive_share: None,
+        tvl_change_percent: change_percent(snapshots.iter().filter_map(|s| s.tvl)),
+        volume_change_percent: change_percent(snapshots.iter().filter_map(|s| s.daily_volume)),
+        reasons: Vec::new(),
+    };
+
+    if aprs.len() < MIN_SNAPSHOTS {
+        response.reasons.push(format!(
+            "Only {} APR snapshots in the last {} days; at least {} are needed",
+            aprs.len(),
+            days,
+            MIN_SNAPSHOTS
+        ));
+        return response;
+    }
+
+    let (ages, values): (Vec<f64>, Vec<f64>) = aprs.into_iter().unzip();
+    let mean_apr = mean(&values);
+    let apr_variation = (mean_apr > 0.0).then(|| std_dev(&values) / mean_apr);
+    let apr_trend = linear_regression_xy(&ages, &values)
+        .map(|fit| fit.slope * (ages.last().copied().unwrap_or(0.0) - ages[0]));
+    let incentive_share = response
+        .mean_fee_apr
+        .filter(|_| mean_apr > 0.0)
+        .map(|fee_apr| (1.0 - fee_apr / mean_apr).clamp(0.0, 1.0));
+
+    response.mean_apr = Some(mean_apr);
+    response.apr_variation = apr_variation;
+    response.apr_trend = apr_trend;
+    response.incentive_share = incentive_share;
+
+    let mut verdict = "SUSTAINABLE";
+    if let Some(share) = incentive_share.filter(|s| *s > MAX_INCENTIVE_SHARE) {
+        verdict = "INCENTIVE_DRIVEN";
+        response.reasons.push(format!(
+            "{:.0}% of the APR is not covered by swap fees",
+            share * 100.0
+        ));
+    }
+    if let Some(volume_change) = response
+        .volume_change_percent
+        .filter(|c| *c < MAX_VOLUME_DECLINE_PERCENT)
+    {
+        verdict = "DECLINING";
+        response.reasons.push(format!(
+            "Daily volume fell {:.0}% over the window",
+            -volume_change
+        ));
+    }
+    if let Some(trend) = apr_trend.filter(|t| mean_apr > 0.0 && *t < -MAX_APR_DECLINE * mean_apr) {
+        verdict = "DECLINING";
+        response.reasons.push(format!(
+            "APR trended down {:.1} points against a {:.1}% average",
+            -trend, mean_apr
+        ));
+    }
+    if let Some(variation) = apr_variation.filter(|v| *v > MAX_APR_VARIATION) {
+        verdict = "VOLATILE";
+        response.reasons.push(format!(
+            "APR varies by {:.0}% of its average between snapshots",
+            variation * 100.0
+        ));
+    }
+    if verdict == "SUSTAINABLE" {
+        response.reasons.push(format!(
+            "APR held near {:.1}% with steady volume across {} snapshots",
+            mean_apr,
+            values.len()
+        ));
+    }
+
+    response.sustainable = verdict == "SUSTAINABLE";
+    response.verdict = verdict.to_string();
+    response
+}
+
+/// APR paid by swap fees alone at one snapshot
+fn fee_apr(snapshot: &PoolSnapshot) -> Option<f64> {
+    let tvl = snapshot.tvl.filter(|tvl| *tvl > 0.0)?;
+    let fee_percent = snapshot.fee_tier? as f64 / 10_000.0;
+    Some(snapshot.daily_volume? * fee_percent / tvl * 365.0)
+}
+
+fn change_percent(mut values: impl DoubleEndedIterator<Item = f64>) -> Option<f64> {
+    let first = values.next()?;
+    let last = values.next_back()?;
+    (first > 0.0).then(|| (last / first - 1.0) * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn snapshot(day: i64, apr: f64, daily_volume: f64) -> PoolSnapshot {
+        PoolSnapshot {
+            captured_at: DateTime::<Utc>::from_timestamp(1_760_000_000, 0).unwrap()
+                + Duration::days(day),
+            fee_tier: Some(3000),
+            tvl: Some(1_000_000.0),
+            daily_volume: Some(daily_volume),
+            apr: Some(apr),
+            price: Some(1.0),
+        }
+    }
+
+    fn series(aprs: &[f64], daily_volume: f64) -> Vec<PoolSnapshot> {
+        aprs.iter()
+            .enumerate()
+            .map(|(day, apr)| snapshot(day as i64, *apr, daily_volume))
+            .collect()
+    }
+
+    #[test]
+    fn needs_three_apr_snapshots() {
+        let empty = assess_snapshots("0xPOOL", 30, &[]);
+        assert_eq!(empty.pool_address, "0xpool");
+        assert_eq!(empty.verdict, "INSUFFICIENT_DATA");
+        assert_eq!(empty.snapshots, 0);
+        assert_eq!(empty.current_apr, None);
+        assert_eq!(empty.mean_fee_apr, None);
+        assert_eq!(empty.tvl_change_percent, None);
+
+        let single = assess_snapshots("0xpool", 30, &series(&[20.0], 200_000.0));
+        assert_eq!(single.verdict, "INSUFFICIENT_DATA");
+        assert!(!single.sustainable);
+        assert_eq!(single.current_apr, Some(20.0));
+        assert_eq!(single.volume_change_percent, None);
+
+        // Snapshots without an APR do not count
+        let mut gaps = series(&[20.0, 20.0, 20.0], 200_000.0);
+        gaps[1].apr = None;
+        assert_eq!(
+            assess_snapshots("0xpool", 30, &gaps).verdict,
+            "INSUFFICIENT_DATA"
+        );
+    }
+
+    #[test]
+    fn steady_fee_backed_apr_is_sustainable() {
+        let response =
+            assess_snapshots("0xpool", 30, &series(&[20.0, 21.0, 19.0, 20.0], 200_000.0));
+
+        assert_eq!(response.verdict, "SUSTAINABLE");
+        assert!(response.sustainable);
+        assert_eq!(response.mean_apr, Some(20.0));
+        // 200k volume at 0.3% on 1M TVL pays 21.9% a year
+        assert!((response.mean_fee_apr.unwrap() - 21.9).abs() < 1e-9);
+        assert_eq!(response.incentive_share, Some(0.0));
+        assert_eq!(response.volume_change_percent, Some(0.0));
+    }
+
+    #[test]
+    fn flags_apr_not_covered_by_fees() {
+        let response = assess_snapshots("0xpool", 30, &series(&[20.0, 20.0, 20.0], 50_000.0));
+
+        assert_eq!(response.verdict, "INCENTIVE_DRIVEN");
+        assert!((response.incentive_share.unwrap() - (1.0 - 5.475 / 20.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flags_declining_and_volatile_apr() {
+        let declining = assess_snapshots(
+            "0xpool",
+            30,
+            &series(&[30.0, 25.0, 20.0, 15.0], 1_000_000.0),
+        );
+        assert_eq!(declining.verdict, "DECLINING");
+        assert!((declining.apr_trend.unwrap() + 15.0).abs() < 1e-9);
+
+        let volatile =
+            assess_snapshots("0xpool", 30, &series(&[5.0, 40.0, 5.0, 40.0], 1_000_000.0));
+        assert_eq!(volatile.verdict, "VOLATILE");
+        assert!(!volatile.sustainable);
+
+        let mut drying = series(&[20.0, 20.0, 20.0], 200_000.0);
+        drying[2].daily_volume = Some(100_000.0);
+        let drying = assess_snapshots("0xpool", 30, &drying);
+        assert_eq!(drying.verdict, "DECLINING");
+        assert_eq!(drying.volume_change_percent, Some(-50.0));
+    }
+
+    #[test]
+    fn ignores_fees_and_changes_of_zero_tvl() {
+        let mut snapshots = series(&[0.0, 0.0, 0.0], 0.0);
+        for snapshot in &mut snapshots {
+            snapshot.tvl = Some(0.0);
+        }
+        let response = assess_snapshots("0xpool", 30, &snapshots);
+
+        assert_eq!(response.mean_fee_apr, None);
+        assert_eq!(response.incentive_share, None);
+        assert_eq!(response.tvl_change_percent, None);
+        assert_eq!(response.apr_variation, None);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::application::dtos::onchain_pool::{OnChainPoolResponse, ProviderCrossCheck};
+use crate::application::use_cases::find_pool;
+use crate::config::ChainConfig;
+use crate::infrastructure::data::onchain_dex_provider::OnChainDexProvider;
+use anyhow::{Result, anyhow};
+use tracing::{info, warn};
+
+/// Read a pool from the chain and compare it with the REST provider that lists it, if any
+pub async fn cross_check_pool(
+    chain: &ChainConfig,
+    pool_address: &str,
+) -> Result<OnChainPoolResponse> {
+    info!(
+        "⛓️ Cross-checking pool {} on-chain on {}",
+        pool_address, chain.name
+    );
+
+    let onchain = OnChainDexProvider::for_chain(chain)?;
+    let state = onchain
+        .pool_states(&[pool_address.parse()?])
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow!("Pool {} could not be read on-chain", pool_address))?;
+    let price = state.price();
+
+    let cross_check = match find_pool(chain, pool_address).await {
+        Ok((_, pool)) => Some(ProviderCrossCheck {
+            provider: pool.protocol,
+            provider_price: pool.price,
+            price_deviation_percent: pool
+                .price
+                .filter(|_| price > 0.0)
+                .map(|provider_price| (provider_price / price - 1.0) * 100.0),
+            provider_fee_tier: pool.fee_tier.map(|f| f.percent()),
+            fee_tier_matches: pool.fee_tier == Some(state.fee_tier),
+        }),
+        Err(e) => {
+            warn!(
+                "⚠️ No provider listing to cross-check {}: {}",
+                pool_address, e
+            );
+            None
+        }
+    };
+
+    Ok(OnChainPoolResponse {
+        chain_id: chain.id,
+        address: state.address.to_string(),
+        fee_tier: state.fee_tier.percent(),
+        tick_spacing: state.tick_spacing,
+        sqrt_price_x96: state.sqrt_price_x96.to_string(),
+        tick: state.tick,
+        liquidity: state.liquidity.to_string(),
+        fee_growth_global0_x128: state.fee_growth_global0_x128.to_string(),
+        fee_growth_global1_x128: state.fee_growth_global1_x128.to_string(),
+        token0: state.token0,
+        token1: state.token1,
+        price,
+        cross_check,
+    })
+}
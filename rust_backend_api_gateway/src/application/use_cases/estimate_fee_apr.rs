@@ -0,0 +1,187 @@
+use crate::application::dtos::fee_apr::{FeeAprRequest, FeeAprResponse};
+use crate::application::dtos::pool_detail::{PoolDetailResponse, RangeFeeApr};
+use crate::application::service::price_oracle::PriceOracle;
+use crate::application::use_cases::{find_pool, is_usd_stablecoin};
+use crate::config::ChainConfig;
+use crate::domain::services::data::Token;
+use crate::infrastructure::data::require_data_provider;
+use crate::math::{
+    FeeAprParams, active_liquidity_at_tick, estimate_position_fee_apr, price_to_tick,
+};
+use anyhow::{Result, anyhow};
+use tracing::{info, warn};
+
+const DEFAULT_CAPITAL_USD: f64 = 1000.0;
+/// Range the pool detail estimate is made for: ±10% around the current price
+const DEFAULT_RANGE_HALF_WIDTH: f64 = 0.1;
+
+pub async fn estimate_fee_apr(
+    chain: &ChainConfig,
+    request: &FeeAprRequest,
+    price_oracle: &PriceOracle,
+) -> Result<FeeAprResponse> {
+    info!(
+        "💰 Estimating fee APR for pool {} in range [{}, {}]",
+        request.pool_address, request.price_lower, request.price_upper
+    );
+
+    let (dex_provider, pool) = find_pool(chain, &request.pool_address).await?;
+
+    let fee_tier = pool
+        .fee_tier_percent()
+        .ok_or_else(|| anyhow!("Pool {} has no usable fee tier", pool.id))?;
+    let token0_decimals = pool.token0.decimals_or_default();
+    let token1_decimals = pool.token1.decimals_or_default();
+
+    let current_price = match request.current_price {
+        Some(price) => price,
+        None => latest_close(chain, &pool.token0.symbol, &pool.token1.symbol).await?,
+    };
+    let token1_usd_price = match request.token1_usd_price {
+        Some(price) => price,
+        None => {
+            infer_token1_usd_price(
+                chain,
+                &pool.token0,
+                &pool.token1,
+                current_price,
+                price_oracle,
+            )
+            .await?
+        }
+    };
+
+    let liquidity = dex_provider.get_liquidity_data(&pool.id).await?;
+    let current_tick =
+        price_to_tick(current_price, token0_decimals, token1_decimals).floor() as i32;
+    let active_liquidity = active_liquidity_at_tick(&liquidity, current_tick)
+        .ok_or_else(|| anyhow!("No tick liquidity data available for pool {}", pool.id))?;
+
+    let capital = request.capital.unwrap_or(DEFAULT_CAPITAL_USD);
+    let daily_volume = pool.daily_volume_f64().unwrap_or(0.0);
+
+    let estimate = estimate_position_fee_apr(&FeeAprParams {
+        capital,
+        current_price,
+        price_lower: request.price_lower,
+        price_upper: request.price_upper,
+        token0_decimals,
+        token1_decimals,
+        token1_usd_price,
+        daily_volume,
+        fee_tier_percent: fee_tier,
+        active_liquidity,
+    })?;
+
+    let pool_apr_percent = pool
+        .tvl_f64()
+        .filter(|tvl| *tvl > 0.0)
+        .map(|tvl| daily_volume * fee_tier / tvl * 365.0);
+
+    Ok(FeeAprResponse {
+        pool_address: pool.id.clone(),
+        protocol: pool.protocol.clone(),
+        pair: format!("{}/{}", pool.token0.symbol, pool.token1.symbol),
+        fee_tier,
+        current_price,
+        price_lower: request.price_lower,
+        price_upper: request.price_upper,
+        in_range: estimate.in_range,
+        capital,
+        daily_volume,
+        active_liquidity,
+        position_liquidity: estimate.position_liquidity,
+        liquidity_share_percent: estimate.liquidity_share * 100.0,
+        estimated_daily_fees: estimate.daily_fees,
+        estimated_apr_percent: estimate.apr_percent,
+        pool_apr_percent,
+    })
+}
+
+pub async fn latest_close(chain: &ChainConfig, token0: &str, token1: &str) -> Result<f64> {
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    data_provider
+        .get_price_data(token0, token1, 60, 1)
+        .await?
+        .last()
+        .map(|p| p.close)
+        .ok_or_else(|| anyhow!("No current price available for {}/{}", token0, token1))
+}
+
+/// USD price of token1, read off the pair when one side is a dollar stablecoin and from the
+/// price oracle otherwise
+async fn infer_token1_usd_price(
+    chain: &ChainConfig,
+    token0: &Token,
+    token1: &Token,
+    current_price: f64,
+    price_oracle: &PriceOracle,
+) -> Result<f64> {
+    if is_usd_stablecoin(&token1.symbol) {
+        Ok(1.0)
+    } else if is_usd_stablecoin(&token0.symbol) {
+        Ok(1.0 / current_price)
+    } else {
+        price_oracle
+            .usd_price(chain, &token1.address)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "token1_usd_price is required for {}/{}: {}",
+                    token0.symbol,
+                    token1.symbol,
+                    e
+                )
+            })
+    }
+}
+
+/// Fee APR of a `DEFAULT_CAPITAL_USD` position within `DEFAULT_RANGE_HALF_WIDTH` of the current
+/// price, or `None` when the detail lacks a price, fee tier, volume or tick liquidity
+pub async fn default_range_fee_apr(
+    chain: &ChainConfig,
+    detail: &PoolDetailResponse,
+    price_oracle: &PriceOracle,
+) -> Option<RangeFeeApr> {
+    let current_price = detail.current_price?;
+    let active_liquidity = detail.liquidity.as_ref()?.active_liquidity?;
+    let (daily_volume, fee_tier) = (detail.daily_volume?, detail.fee_tier?);
+    let token1_usd_price = infer_token1_usd_price(
+        chain,
+        &detail.token0,
+        &detail.token1,
+        current_price,
+        price_oracle,
+    )
+    .await
+    .inspect_err(|e| warn!("⚠️ No fee APR for pool {}: {}", detail.address, e))
+    .ok()?;
+
+    let (price_lower, price_upper) = (
+        current_price * (1.0 - DEFAULT_RANGE_HALF_WIDTH),
+        current_price * (1.0 + DEFAULT_RANGE_HALF_WIDTH),
+    );
+    let estimate = estimate_position_fee_apr(&FeeAprParams {
+        capital: DEFAULT_CAPITAL_USD,
+        current_price,
+        price_lower,
+        price_upper,
+        token0_decimals: detail.token0.decimals_or_default(),
+        token1_decimals: detail.token1.decimals_or_default(),
+        token1_usd_price,
+        daily_volume,
+        fee_tier_percent: fee_tier,
+        active_liquidity,
+    })
+    .ok()?;
+
+    Some(RangeFeeApr {
+        price_lower,
+        price_upper,
+        capital: DEFAULT_CAPITAL_USD,
+        liquidity_share_percent: estimate.liquidity_share * 100.0,
+        estimated_daily_fees: estimate.daily_fees,
+        estimated_apr_percent: estimate.apr_percent,
+    })
+}
@@ -1,16 +1,30 @@
+use crate::config::ChainConfig;
 use crate::domain::repositories::dex_provider::DexProvider;
 use crate::domain::services::data::ActiveLiquidityResponse;
 use crate::infrastructure::data::dragonswap_data_provider::DragonSwapDataProvider;
 use crate::infrastructure::data::sailor_data_provider::SailorDataProvider;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use std::error::Error;
 use tracing::{error, info};
 
-pub async fn get_graph_data(pool_address: &str) -> Result<ActiveLiquidityResponse, Box<dyn Error>> {
-    let data_provider = DragonSwapDataProvider::new();
+pub async fn get_graph_data(
+    chain: &ChainConfig,
+    pool_address: &str,
+) -> Result<ActiveLiquidityResponse, Box<dyn Error>> {
+    let dragonswap_provider = match DragonSwapDataProvider::for_chain(chain) {
+        Some(data_provider) => match data_provider.is_dragonswap_pool(pool_address).await {
+            Ok(true) => Some(data_provider),
+            Ok(false) => None,
+            Err(e) => {
+                error!("Failed to check pool type for {}: {}", pool_address, e);
+                return Err(e.into());
+            }
+        },
+        None => None,
+    };
 
-    match data_provider.is_dragonswap_pool(pool_address).await {
-        Ok(true) => {
+    match dragonswap_provider {
+        Some(data_provider) => {
             info!(
                 "Pool {} identified as a DragonSwap pool. Fetching data ONLY from DragonSwap.",
                 pool_address
@@ -18,8 +32,9 @@ pub async fn get_graph_data(pool_address: &str) -> Result<ActiveLiquidityRespons
             let liquidity_data = data_provider.get_liquidity_data(pool_address).await?;
             Ok(liquidity_data)
         }
-        Ok(false) => {
-            let sailor_data_provider = SailorDataProvider::new();
+        None => {
+            let sailor_data_provider = SailorDataProvider::for_chain(chain)
+                .ok_or_else(|| anyhow!("No liquidity provider is configured on {}", chain.name))?;
             info!(
                 "Pool {} is not a DragonSwap pool. Using sailor service.",
                 pool_address
@@ -29,9 +44,5 @@ pub async fn get_graph_data(pool_address: &str) -> Result<ActiveLiquidityRespons
                 .await?;
             Ok(liquidity_data)
         }
-        Err(e) => {
-            error!("Failed to check pool type for {}: {}", pool_address, e);
-            Err(e.into())
-        }
     }
 }
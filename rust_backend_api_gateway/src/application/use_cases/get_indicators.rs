@@ -0,0 +1,112 @@
+use crate::application::dtos::indicators::{IndicatorResponse, IndicatorSeries};
+use crate::config::ChainConfig;
+use crate::infrastructure::data::require_data_provider;
+use crate::math::{BollingerBands, Ema, IndicatorSpec, Macd, Rsi, Sma, indicator_series};
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+use tracing::{info, warn};
+
+pub async fn get_indicators(
+    chain: &ChainConfig,
+    token0: &str,
+    token1: &str,
+    interval: u32,
+    limit: u32,
+    specs: &[IndicatorSpec],
+) -> Result<IndicatorResponse> {
+    info!(
+        "📈 Computing {} indicators for {}/{}",
+        specs.len(),
+        token0,
+        token1
+    );
+
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let price_data = data_provider
+        .get_price_data(token0, token1, interval, limit)
+        .await?;
+
+    if price_data.is_empty() {
+        warn!("⚠️ No price data found for {}/{}", token0, token1);
+        return Err(anyhow!(
+            "No price data available for the specified token pair"
+        ));
+    }
+
+    let timestamps: Vec<i64> = price_data.iter().map(|p| p.tick).collect();
+    let closes: Vec<f64> = price_data.iter().map(|p| p.close).collect();
+
+    let indicators = specs
+        .iter()
+        .map(|spec| IndicatorSeries {
+            name: spec.name(),
+            lines: compute_lines(spec, &closes),
+        })
+        .collect();
+
+    Ok(IndicatorResponse {
+        pair: format!("{}/{}", token0, token1),
+        interval_minutes: interval,
+        timestamps,
+        close: closes,
+        indicators,
+    })
+}
+
+fn compute_lines(spec: &IndicatorSpec, closes: &[f64]) -> BTreeMap<String, Vec<Option<f64>>> {
+    let mut lines = BTreeMap::new();
+
+    match spec {
+        IndicatorSpec::Sma(period) => {
+            lines.insert(
+                "value".to_string(),
+                indicator_series(&mut Sma::new(*period), closes),
+            );
+        }
+        IndicatorSpec::Ema(period) => {
+            lines.insert(
+                "value".to_string(),
+                indicator_series(&mut Ema::new(*period), closes),
+            );
+        }
+        IndicatorSpec::Rsi(period) => {
+            lines.insert(
+                "value".to_string(),
+                indicator_series(&mut Rsi::new(*period), closes),
+            );
+        }
+        IndicatorSpec::Bollinger(period) => {
+            let series = indicator_series(&mut BollingerBands::new(*period, 2.0), closes);
+            lines.insert(
+                "upper".to_string(),
+                series.iter().map(|o| o.map(|b| b.upper)).collect(),
+            );
+            lines.insert(
+                "middle".to_string(),
+                series.iter().map(|o| o.map(|b| b.middle)).collect(),
+            );
+            lines.insert(
+                "lower".to_string(),
+                series.iter().map(|o| o.map(|b| b.lower)).collect(),
+            );
+        }
+        IndicatorSpec::Macd(fast, slow, signal) => {
+            let series = indicator_series(&mut Macd::new(*fast, *slow, *signal), closes);
+            lines.insert(
+                "macd".to_string(),
+                series.iter().map(|o| o.map(|m| m.macd)).collect(),
+            );
+            lines.insert(
+                "signal".to_string(),
+                series.iter().map(|o| o.and_then(|m| m.signal)).collect(),
+            );
+            lines.insert(
+                "histogram".to_string(),
+                series.iter().map(|o| o.and_then(|m| m.histogram)).collect(),
+            );
+        }
+    }
+
+    lines
+}
@@ -1,16 +1,19 @@
 use crate::application::dtos::price_history::PricePoint;
+use crate::config::ChainConfig;
 use crate::domain::repositories::data_provider::DataProvider;
 use crate::infrastructure::data::sailor_data_provider::SailorDataProvider;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use std::error::Error;
 
 pub async fn get_kline_data(
+    chain: &ChainConfig,
     token0_symbol: &str,
     token1_symbol: &str,
     interval_minutes: u32,
     limit: u32,
 ) -> Result<Vec<PricePoint>, Box<dyn Error>> {
-    let data_provider = SailorDataProvider::new();
+    let data_provider = SailorDataProvider::for_chain(chain)
+        .ok_or_else(|| anyhow!("Sailor is not configured on {}", chain.name))?;
     let response = data_provider
         .get_price_data(token0_symbol, token1_symbol, interval_minutes, limit)
         .await?;
@@ -0,0 +1,196 @@
+use crate::application::dtos::range_optimizer::{
+    MintAmounts, OptimalRangeRequest, OptimalRangeResponse,
+};
+use crate::config::ChainConfig;
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::require_data_provider;
+use crate::math::{
+    DEFAULT_MAX_HALF_WIDTH, DEFAULT_MIN_HALF_WIDTH, RangeOptimizerParams, ReturnModel, RiskProfile,
+    candles_per_step, horizon_candles, liquidity_for_capital, log_returns, optimize_range,
+    periods_per_year, position_amounts,
+};
+use alloy::primitives::U256;
+use anyhow::{Result, anyhow};
+use tracing::{info, warn};
+
+/// Longest horizon the optimizer accepts
+pub const MAX_HORIZON_DAYS: u32 = 365;
+const DEFAULT_HORIZON_DAYS: u32 = 7;
+const DEFAULT_OPTIMIZER_PATHS: usize = 300;
+const MAX_OPTIMIZER_PATHS: usize = 2000;
+
+pub async fn get_optimal_range(
+    chain: &ChainConfig,
+    request: &OptimalRangeRequest,
+) -> Result<OptimalRangeResponse> {
+    let interval = request.interval.unwrap_or(60);
+    let limit = request.limit.unwrap_or(500);
+    let horizon_days = request.horizon_days.unwrap_or(DEFAULT_HORIZON_DAYS);
+    let risk_profile: RiskProfile = request
+        .risk_profile
+        .as_deref()
+        .unwrap_or("balanced")
+        .parse()?;
+
+    info!(
+        "🎯 Optimizing range for {}/{} ({} profile, {}d horizon)",
+        request.token0,
+        request.token1,
+        risk_profile.as_str(),
+        horizon_days
+    );
+
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let price_data = data_provider
+        .get_price_data(&request.token0, &request.token1, interval, limit)
+        .await?;
+
+    let Some(last) = price_data.last() else {
+        warn!(
+            "⚠️ No price data found for {}/{}",
+            request.token0, request.token1
+        );
+        return Err(anyhow!(
+            "No price data available for the specified token pair"
+        ));
+    };
+    let current_price = last.close;
+
+    let closes: Vec<f64> = price_data.iter().map(|p| p.close).collect();
+    let returns = log_returns(&closes);
+    if returns.len() < 2 {
+        return Err(anyhow!(
+            "Not enough price data to fit a return distribution"
+        ));
+    }
+    let model = ReturnModel::calibrate(request.model.as_deref().unwrap_or("gbm"), &returns)?;
+
+    let token0_decimals = request.token0_decimals.unwrap_or(18);
+    let token1_decimals = request.token1_decimals.unwrap_or(18);
+
+    // Long horizons at short intervals are simulated several candles per step
+    let candles = horizon_candles(horizon_days, interval).ok_or_else(|| {
+        anyhow!(
+            "Cannot simulate {} days at a {}min interval",
+            horizon_days,
+            interval
+        )
+    })?;
+    let candles_per_step = candles_per_step(candles);
+    let params = RangeOptimizerParams {
+        current_price,
+        token0_decimals,
+        token1_decimals,
+        fee_tier: FeeTier::from_percent(request.fee_tier)
+            .ok_or_else(|| anyhow!("Invalid fee tier: {}%", request.fee_tier))?,
+        pool_apr: request.pool_apr,
+        horizon_steps: candles.div_ceil(candles_per_step),
+        periods_per_year: periods_per_year(interval) / candles_per_step as f64,
+        risk_profile,
+        min_half_width: DEFAULT_MIN_HALF_WIDTH,
+        max_half_width: DEFAULT_MAX_HALF_WIDTH,
+        paths: request
+            .paths
+            .unwrap_or(DEFAULT_OPTIMIZER_PATHS)
+            .clamp(1, MAX_OPTIMIZER_PATHS),
+        seed: request.seed,
+    };
+    let model = model.over_candles(candles_per_step);
+
+    // Simulating and scoring every candidate is CPU-bound, so keep it off the async workers
+    let optimized = tokio::task::spawn_blocking(move || optimize_range(&model, &params)).await??;
+
+    let mint = request
+        .capital
+        .map(|capital| {
+            let liquidity = liquidity_for_capital(
+                capital,
+                current_price,
+                optimized.price_lower,
+                optimized.price_upper,
+            );
+            let (amount0, amount1) = position_amounts(
+                liquidity,
+                current_price,
+                optimized.price_lower,
+                optimized.price_upper,
+            );
+            Ok::<_, anyhow::Error>(MintAmounts {
+                amount0,
+                amount1,
+                amount0_max: to_raw_amount(amount0, token0_decimals)?,
+                amount1_max: to_raw_amount(amount1, token1_decimals)?,
+            })
+        })
+        .transpose()?;
+
+    info!(
+        "✅ Optimal range for {}/{}: ticks [{}, {}]",
+        request.token0, request.token1, optimized.tick_lower, optimized.tick_upper
+    );
+
+    Ok(OptimalRangeResponse {
+        pair: format!("{}/{}", request.token0, request.token1),
+        risk_profile: risk_profile.as_str().to_string(),
+        current_price,
+        horizon_days,
+        tick_lower: optimized.tick_lower,
+        tick_upper: optimized.tick_upper,
+        tick_spacing: optimized.tick_spacing,
+        current_tick: optimized.current_tick,
+        price_lower: optimized.price_lower,
+        price_upper: optimized.price_upper,
+        expected_fee_return_percent: optimized.expected_fees * 100.0,
+        expected_il_percent: optimized.expected_il * 100.0,
+        expected_net_return_percent: (optimized.expected_fees - optimized.expected_il) * 100.0,
+        expected_time_in_range_percent: optimized.expected_time_in_range * 100.0,
+        mint,
+    })
+}
+
+/// Scale a human-readable amount to its integer on-chain representation.
+///
+/// Shifts the decimal point of the amount's shortest decimal form instead of multiplying by
+/// 10^decimals in floating point, which invents digits for 18-decimal tokens.
+fn to_raw_amount(amount: f64, decimals: u32) -> Result<String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Ok("0".to_string());
+    }
+
+    // Display never switches to exponent notation for f64
+    let decimal = amount.to_string();
+    let (whole, fraction) = decimal.split_once('.').unwrap_or((&decimal, ""));
+    let fraction: String = fraction
+        .chars()
+        .chain(std::iter::repeat('0'))
+        .take(decimals as usize)
+        .collect();
+
+    U256::from_str_radix(&format!("{}{}", whole, fraction), 10)
+        .map(|raw| raw.to_string())
+        .map_err(|_| {
+            anyhow!(
+                "{} does not fit in a uint256 at {} decimals",
+                amount,
+                decimals
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_amounts_without_float_error() {
+        assert_eq!(to_raw_amount(1.1, 18).unwrap(), "1100000000000000000");
+        assert_eq!(to_raw_amount(0.000123, 6).unwrap(), "123");
+        // Digits beyond the token's decimals are truncated
+        assert_eq!(to_raw_amount(1.23456789, 6).unwrap(), "1234567");
+        assert_eq!(to_raw_amount(42.0, 0).unwrap(), "42");
+        assert_eq!(to_raw_amount(0.0, 18).unwrap(), "0");
+        assert_eq!(to_raw_amount(-1.0, 18).unwrap(), "0");
+        assert!(to_raw_amount(1e60, 18).is_err());
+    }
+}
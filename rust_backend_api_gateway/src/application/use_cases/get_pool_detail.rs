@@ -0,0 +1,246 @@
+use crate::application::dtos::pool_detail::{LiquiditySummary, PoolDetailResponse};
+use crate::application::service::price_oracle::PriceOracle;
+use crate::application::use_cases::{
+    ProviderPools, count_token_listings, default_range_fee_apr, load_risk_context,
+    query_pool_providers, risk_inputs,
+};
+use crate::config::ChainConfig;
+use crate::domain::repositories::dex_provider::DexProvider;
+use crate::domain::services::data::{ActiveLiquidityResponse, Token, UnifiedPool};
+use crate::infrastructure::data::{get_dex_provider, require_data_provider};
+use crate::math::{
+    RiskInputs, active_liquidity_at_tick, liquidity_concentration, liquidity_distribution,
+    price_to_tick, score_pool_risk,
+};
+use anyhow::{Result, anyhow};
+use futures::future::join;
+use sea_orm::DatabaseConnection;
+use std::slice;
+use tracing::{info, warn};
+
+/// Hourly candles covering the 7d change plus the current one
+const PRICE_CHANGE_INTERVAL: u32 = 60;
+const PRICE_CHANGE_LIMIT: u32 = 169;
+/// Half-width of the window used for the liquidity concentration summary
+const CONCENTRATION_WINDOW: f64 = 0.02;
+
+pub async fn get_pool_detail(
+    db: &DatabaseConnection,
+    chain: &ChainConfig,
+    pool_address: &str,
+    price_oracle: &PriceOracle,
+) -> Result<PoolDetailResponse> {
+    info!(
+        "🏊 Fetching pool detail for {} on {}",
+        pool_address, chain.name
+    );
+
+    let provider_pools = query_pool_providers(chain).await;
+    let token_listings = count_token_listings(&provider_pools);
+    let (dex_provider, pool) = find_pool_in(chain, provider_pools, pool_address)?;
+
+    let context = load_risk_context(db, chain.id, slice::from_ref(&pool), token_listings).await;
+    let risk = risk_inputs(&pool, &context);
+    let mut detail = pool_detail_for(chain, dex_provider.as_ref(), pool, Some(risk)).await?;
+    detail.fee_apr = default_range_fee_apr(chain, &detail, price_oracle).await;
+    Ok(detail)
+}
+
+/// Locate a pool by address across the DEXes on the chain, returning the provider that lists it
+pub async fn find_pool(
+    chain: &ChainConfig,
+    pool_address: &str,
+) -> Result<(Box<dyn DexProvider>, UnifiedPool)> {
+    find_pool_in(chain, query_pool_providers(chain).await, pool_address)
+}
+
+fn find_pool_in(
+    chain: &ChainConfig,
+    provider_pools: Vec<ProviderPools>,
+    pool_address: &str,
+) -> Result<(Box<dyn DexProvider>, UnifiedPool)> {
+    let mut failed = Vec::new();
+    for provider_pools in provider_pools {
+        let Ok(pools) = provider_pools.result else {
+            failed.push(provider_pools.provider);
+            continue;
+        };
+        if let Some(pool) = pools
+            .into_iter()
+            .find(|pool| pool.id.eq_ignore_ascii_case(pool_address))
+        {
+            return Ok((dex_provider(&provider_pools.provider, chain)?, pool));
+        }
+    }
+
+    if failed.is_empty() {
+        Err(anyhow!("Pool {} not found", pool_address))
+    } else {
+        Err(anyhow!(
+            "Pool {} not found; unavailable providers: {}",
+            pool_address,
+            failed.join(", ")
+        ))
+    }
+}
+
+/// Deepest pool (by TVL) trading `token0`/`token1` in either order, matched by symbol or address
+pub async fn find_pool_for_pair(
+    chain: &ChainConfig,
+    token0: &str,
+    token1: &str,
+) -> Result<Option<(Box<dyn DexProvider>, UnifiedPool)>> {
+    let deepest = query_pool_providers(chain)
+        .await
+        .into_iter()
+        .filter_map(|provider_pools| {
+            let provider = provider_pools.provider;
+            Some(
+                provider_pools
+                    .result
+                    .ok()?
+                    .into_iter()
+                    .map(move |pool| (provider.clone(), pool)),
+            )
+        })
+        .flatten()
+        .filter(|(_, pool)| pool_trades_pair(pool, token0, token1))
+        .max_by_key(|(_, pool)| pool.tvl.unwrap_or_default());
+
+    match deepest {
+        Some((provider, pool)) => Ok(Some((dex_provider(&provider, chain)?, pool))),
+        None => Ok(None),
+    }
+}
+
+/// Whether `token` is `wanted`, given as a symbol or an address
+pub fn token_matches(token: &Token, wanted: &str) -> bool {
+    token.symbol.eq_ignore_ascii_case(wanted) || token.address.eq_ignore_ascii_case(wanted)
+}
+
+/// Whether the pool trades `token_a` against `token_b`, in either order
+pub fn pool_trades_pair(pool: &UnifiedPool, token_a: &str, token_b: &str) -> bool {
+    (token_matches(&pool.token0, token_a) && token_matches(&pool.token1, token_b))
+        || (token_matches(&pool.token0, token_b) && token_matches(&pool.token1, token_a))
+}
+
+pub fn dex_provider(name: &str, chain: &ChainConfig) -> Result<Box<dyn DexProvider>> {
+    get_dex_provider(name, chain)
+        .ok_or_else(|| anyhow!("Dex provider {} is not available on {}", name, chain.name))
+}
+
+/// Pool metadata combined with liquidity and recent prices, fetched concurrently.
+/// `risk` is completed with the hourly closes and tick liquidity and scored when given.
+pub async fn pool_detail_for(
+    chain: &ChainConfig,
+    dex_provider: &dyn DexProvider,
+    pool: UnifiedPool,
+    risk: Option<RiskInputs>,
+) -> Result<PoolDetailResponse> {
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let (liquidity, prices) = join(
+        dex_provider.get_liquidity_data(&pool.id),
+        data_provider.get_price_data(
+            &pool.token0.symbol,
+            &pool.token1.symbol,
+            PRICE_CHANGE_INTERVAL,
+            PRICE_CHANGE_LIMIT,
+        ),
+    )
+    .await;
+
+    let liquidity = match liquidity {
+        Ok(liquidity) => Some(liquidity),
+        Err(e) => {
+            warn!(
+                "⚠️ No liquidity data for pool {}, reading ticks on-chain: {}",
+                pool.id, e
+            );
+            onchain_liquidity(chain, &pool.id).await
+        }
+    };
+    let closes: Vec<f64> = prices
+        .inspect_err(|e| warn!("⚠️ No price data for pool {}: {}", pool.id, e))
+        .map(|points| points.iter().map(|p| p.close).collect())
+        .unwrap_or_default();
+
+    let fee_tier = pool.fee_tier;
+    let current_price = closes.last().copied().filter(|p| *p > 0.0);
+    let current_tick = current_price.map(|price| {
+        price_to_tick(
+            price,
+            pool.token0.decimals_or_default(),
+            pool.token1.decimals_or_default(),
+        )
+        .floor() as i32
+    });
+
+    let liquidity = liquidity.map(|l| summarize_liquidity(&l, current_tick));
+    let risk = risk.map(|mut inputs| {
+        if closes.len() >= 3 {
+            inputs.prices = closes.clone();
+            inputs.price_interval_minutes = PRICE_CHANGE_INTERVAL;
+        }
+        inputs.concentration_2_percent = liquidity.as_ref().and_then(|l| l.concentration_2_percent);
+        score_pool_risk(&inputs)
+    });
+
+    let (tvl, daily_volume) = (pool.tvl_f64(), pool.daily_volume_f64());
+    Ok(PoolDetailResponse {
+        chain_id: pool.chain_id,
+        address: pool.id,
+        protocol: pool.protocol,
+        token0: pool.token0,
+        token1: pool.token1,
+        fee_tier: fee_tier.map(|f| f.percent()),
+        tick_spacing: fee_tier.map(|f| f.tick_spacing()),
+        current_price,
+        current_tick,
+        tvl,
+        daily_volume,
+        apr: pool.apr,
+        liquidity,
+        price_change_24h_percent: price_change_percent(&closes, 24),
+        price_change_7d_percent: price_change_percent(&closes, 168),
+        risk,
+        fee_apr: None,
+    })
+}
+
+/// Tick liquidity straight from the pool contract, for when the REST provider has none
+async fn onchain_liquidity(
+    chain: &ChainConfig,
+    pool_address: &str,
+) -> Option<ActiveLiquidityResponse> {
+    dex_provider("onchain", chain)
+        .ok()?
+        .get_liquidity_data(pool_address)
+        .await
+        .inspect_err(|e| warn!("⚠️ No on-chain liquidity for pool {}: {}", pool_address, e))
+        .ok()
+}
+
+fn summarize_liquidity(
+    liquidity: &ActiveLiquidityResponse,
+    current_tick: Option<i32>,
+) -> LiquiditySummary {
+    let distribution = liquidity_distribution(liquidity);
+    let tick_radius = ((1.0 + CONCENTRATION_WINDOW).ln() / 1.0001f64.ln()).round() as i32;
+
+    LiquiditySummary {
+        initialized_ticks: distribution.len(),
+        lowest_tick: distribution.first().map(|(tick, _)| *tick),
+        highest_tick: distribution.last().map(|(tick, _)| *tick),
+        active_liquidity: current_tick.and_then(|t| active_liquidity_at_tick(liquidity, t)),
+        concentration_2_percent: current_tick
+            .and_then(|t| liquidity_concentration(&distribution, t, tick_radius)),
+    }
+}
+
+/// Percent change between the last close and the one `candles_ago` before it
+fn price_change_percent(closes: &[f64], candles_ago: usize) -> Option<f64> {
+    let last = *closes.last()?;
+    let past = *closes.get(closes.len().checked_sub(candles_ago + 1)?)?;
+    (past > 0.0).then(|| (last / past - 1.0) * 100.0)
+}
@@ -0,0 +1,52 @@
+use crate::application::dtos::pool_history::{MetricPoint, PoolHistoryResponse, PoolSnapshot};
+use crate::application::service::snapshot_service::get_pool_snapshots;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use sea_orm::DatabaseConnection;
+use tracing::info;
+
+/// Snapshot fields served by the history endpoint
+pub const SNAPSHOT_METRICS: [&str; 4] = ["tvl", "daily_volume", "apr", "price"];
+
+pub async fn get_pool_history(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    pool_address: &str,
+    metric: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<PoolHistoryResponse> {
+    info!("📈 Fetching {} history for pool {}", metric, pool_address);
+
+    if !SNAPSHOT_METRICS.contains(&metric) {
+        return Err(anyhow!("Unsupported metric: {}", metric));
+    }
+    let snapshots = get_pool_snapshots(db, chain_id, pool_address, from, to).await?;
+
+    Ok(PoolHistoryResponse {
+        chain_id,
+        pool_address: pool_address.to_lowercase(),
+        metric: metric.to_string(),
+        from,
+        to,
+        points: snapshots
+            .iter()
+            .filter_map(|snapshot| {
+                Some(MetricPoint {
+                    timestamp: snapshot.captured_at,
+                    value: snapshot_metric(snapshot, metric)?,
+                })
+            })
+            .collect(),
+    })
+}
+
+pub fn snapshot_metric(snapshot: &PoolSnapshot, metric: &str) -> Option<f64> {
+    match metric {
+        "tvl" => snapshot.tvl,
+        "daily_volume" => snapshot.daily_volume,
+        "apr" => snapshot.apr,
+        "price" => snapshot.price,
+        _ => None,
+    }
+}
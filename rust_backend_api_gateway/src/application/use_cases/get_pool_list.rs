@@ -1,22 +1,212 @@
-use crate::domain::repositories::dex_provider::DexProvider;
-use crate::{
-    domain::services::data::UnifiedPool,
-    infrastructure::data::{
-        dragonswap_data_provider::DragonSwapDataProvider, sailor_data_provider::SailorDataProvider,
-    },
-};
+use crate::application::dtos::pool_list::{PoolListQuery, PoolListResponse, SourceStatus};
+use crate::application::use_cases::{count_token_listings, load_risk_context, score_pools};
+use crate::config::ChainConfig;
+use crate::domain::services::data::UnifiedPool;
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::{dex_provider_names, get_dex_provider};
+use futures::future::join_all;
+use sea_orm::DatabaseConnection;
+use std::cmp::Ordering;
 use std::error::Error;
+use std::time::Instant;
+use tracing::warn;
 
-pub async fn get_pool_list() -> Result<Vec<UnifiedPool>, Box<dyn Error>> {
-    let dragonswap_data_provider = DragonSwapDataProvider::new();
-    let sailor_data_provider = SailorDataProvider::new();
+/// Numeric fields pools can be sorted on
+pub const POOL_SORT_FIELDS: [&str; 4] = ["tvl", "daily_volume", "apr", "fee_tier"];
+pub const DEFAULT_MIN_POOL_TVL: f64 = 1000.0;
+pub const DEFAULT_MIN_POOL_VOLUME: f64 = 1000.0;
+pub const DEFAULT_POOL_PAGE_SIZE: usize = 100;
+pub const MAX_POOL_PAGE_SIZE: usize = 500;
 
-    let dragonswap_pools = dragonswap_data_provider.get_pool_list().await?;
-    let sailor_pools = sailor_data_provider.get_pool_list().await?;
+/// Pools returned by one provider, with how long the query took
+pub struct ProviderPools {
+    pub provider: String,
+    pub result: anyhow::Result<Vec<UnifiedPool>>,
+    pub latency_ms: u64,
+}
+
+/// Query every DEX provider configured on the chain concurrently; one failing provider does not
+/// affect the others
+pub async fn query_pool_providers(chain: &ChainConfig) -> Vec<ProviderPools> {
+    let dex_providers = dex_provider_names(chain)
+        .into_iter()
+        .filter_map(|provider| {
+            let dex_provider = get_dex_provider(&provider, chain)?;
+            Some((provider, dex_provider))
+        });
 
+    join_all(dex_providers.map(|(provider, dex_provider)| async move {
+        let started = Instant::now();
+        let result = dex_provider.get_pool_list().await;
+        if let Err(e) = &result {
+            warn!("⚠️ Pool list from {} failed: {}", provider, e);
+        }
+
+        ProviderPools {
+            provider,
+            result,
+            latency_ms: started.elapsed().as_millis() as u64,
+        }
+    }))
+    .await
+}
+
+/// Filtered, sorted page of the chain's pools from every provider, each with a risk score
+pub async fn get_pool_list(
+    db: &DatabaseConnection,
+    chain: &ChainConfig,
+    query: &PoolListQuery,
+) -> Result<PoolListResponse, Box<dyn Error>> {
     let mut all_pools = Vec::new();
-    all_pools.extend(dragonswap_pools);
-    all_pools.extend(sailor_pools);
+    let mut sources = Vec::new();
+
+    let provider_pools = query_pool_providers(chain).await;
+    let token_listings = count_token_listings(&provider_pools);
+    for provider_pools in provider_pools {
+        sources.push(source_status(&provider_pools));
+        if let Ok(pools) = provider_pools.result {
+            all_pools.extend(pools);
+        }
+    }
+
+    let sort_by = query.sort_by.as_deref().unwrap_or("tvl");
+    if !POOL_SORT_FIELDS.contains(&sort_by) {
+        return Err(format!("Unsupported sort field: {}", sort_by).into());
+    }
+    let descending = !matches!(query.order.as_deref(), Some("asc"));
+    let cursor = query.cursor.as_deref().map(parse_pool_cursor).transpose()?;
+
+    let mut pools: Vec<UnifiedPool> = all_pools
+        .into_iter()
+        .filter(|pool| matches_query(pool, query))
+        .collect();
+    pools.sort_by(|a, b| compare_pools(&sort_key(a, sort_by), &sort_key(b, sort_by), descending));
+    let total = pools.len();
+
+    // Legacy v1 callers that ask for neither a limit nor a cursor still get every pool
+    let unpaged_v1 =
+        query.schema.as_deref() != Some("v2") && query.limit.is_none() && query.cursor.is_none();
+    let page_size = if unpaged_v1 {
+        total
+    } else {
+        query
+            .limit
+            .unwrap_or(DEFAULT_POOL_PAGE_SIZE)
+            .clamp(1, MAX_POOL_PAGE_SIZE)
+    };
+    let mut page: Vec<UnifiedPool> = pools
+        .into_iter()
+        .filter(|pool| {
+            cursor.as_ref().is_none_or(|cursor| {
+                compare_pools(&sort_key(pool, sort_by), cursor, descending) == Ordering::Greater
+            })
+        })
+        .take(page_size + 1)
+        .collect();
+
+    let next_cursor = if page.len() > page_size {
+        page.truncate(page_size);
+        page.last()
+            .map(|pool| encode_cursor(&sort_key(pool, sort_by)))
+    } else {
+        None
+    };
+
+    // Scored after paging so the history queries only cover the pools returned
+    let context = load_risk_context(db, chain.id, &page, token_listings).await;
+    score_pools(&mut page, &context);
+
+    Ok(PoolListResponse {
+        pools: page,
+        total,
+        next_cursor,
+        sources,
+    })
+}
+
+pub fn source_status(provider_pools: &ProviderPools) -> SourceStatus {
+    let (status, pool_count, error) = match &provider_pools.result {
+        Ok(pools) => ("ok", pools.len(), None),
+        Err(e) => ("error", 0, Some(e.to_string())),
+    };
+
+    SourceStatus {
+        provider: provider_pools.provider.clone(),
+        status: status.to_string(),
+        latency_ms: provider_pools.latency_ms,
+        pool_count,
+        error,
+    }
+}
+
+fn matches_query(pool: &UnifiedPool, query: &PoolListQuery) -> bool {
+    let token_matches = query.token.as_deref().is_none_or(|token| {
+        [&pool.token0, &pool.token1]
+            .iter()
+            .any(|t| t.symbol.eq_ignore_ascii_case(token) || t.address.eq_ignore_ascii_case(token))
+    });
+    let protocol_matches = query
+        .protocol
+        .as_deref()
+        .is_none_or(|protocol| pool.protocol.eq_ignore_ascii_case(protocol));
+    let fee_tier_matches = query.fee_tier.is_none_or(|fee_tier| {
+        pool.fee_tier.is_some() && pool.fee_tier == FeeTier::from_percent(fee_tier)
+    });
+
+    token_matches
+        && protocol_matches
+        && fee_tier_matches
+        && pool.tvl_f64().unwrap_or(0.0) >= query.min_tvl.unwrap_or(DEFAULT_MIN_POOL_TVL)
+        && pool.daily_volume_f64().unwrap_or(0.0)
+            >= query.min_volume.unwrap_or(DEFAULT_MIN_POOL_VOLUME)
+        && query
+            .min_apr
+            .is_none_or(|min_apr| pool.apr.unwrap_or(0.0) >= min_apr)
+}
+
+/// Sort value of a pool plus its id, which breaks ties so cursors are stable
+type SortKey = (Option<f64>, String);
+
+fn sort_key(pool: &UnifiedPool, field: &str) -> SortKey {
+    let value = match field {
+        "tvl" => pool.tvl_f64(),
+        "daily_volume" => pool.daily_volume_f64(),
+        "apr" => pool.apr,
+        "fee_tier" => pool.fee_tier_percent(),
+        _ => None,
+    };
+    (value.filter(|v| v.is_finite()), pool.id.to_lowercase())
+}
+
+/// Order by value (pools without one last), then by id
+fn compare_pools(a: &SortKey, b: &SortKey, descending: bool) -> Ordering {
+    let by_value = match (a.0, b.0) {
+        (Some(x), Some(y)) if descending => y.total_cmp(&x),
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    by_value.then_with(|| a.1.cmp(&b.1))
+}
+
+fn encode_cursor((value, id): &SortKey) -> String {
+    match value {
+        Some(value) => format!("{}~{}", value, id),
+        None => format!("null~{}", id),
+    }
+}
 
-    Ok(all_pools)
+pub fn parse_pool_cursor(cursor: &str) -> Result<SortKey, Box<dyn Error>> {
+    let (value, id) = cursor
+        .split_once('~')
+        .ok_or_else(|| format!("Invalid cursor: {}", cursor))?;
+    let value = match value {
+        "null" => None,
+        v => Some(
+            v.parse::<f64>()
+                .map_err(|_| format!("Invalid cursor: {}", cursor))?,
+        ),
+    };
+    Ok((value, id.to_string()))
 }
@@ -1,28 +1,54 @@
+use crate::application::dtos::pool_detail::PoolDetailResponse;
 use crate::application::dtos::price_history::{
-    PoolInfo, PriceHistoryResponse, PricePoint, PriceRange, RecentPrice, RecommendationContext,
-    VolatilityInfo,
+    PairRelationshipInfo, PoolInfo, PriceHistoryResponse, PricePoint, PriceRange, RangeSurvival,
+    RecentPrice, RecommendationContext, RecommendationOptions, RollingVolatility, SuggestedRange,
+    TrendInfo, VolatilityInfo,
 };
-use crate::infrastructure::data::get_data_provider;
+use crate::application::use_cases::{
+    DEFAULT_CORRELATION_WINDOW, USD_QUOTE, classify_with_legs, fetch_usd_legs, find_pool,
+    find_pool_for_pair, pool_detail_for, relationship_info,
+};
+use crate::config::ChainConfig;
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::require_data_provider;
 use crate::math::{
-    calculate_suggested_range_width, calculate_volatility, determine_trend,
-    determine_volatility_level,
+    PairCorrelation, PairRelationship, RangeOptimizerParams, ReturnModel, RiskProfile,
+    analyze_trend, average_true_range, calculate_volatility, candles_per_step,
+    determine_volatility_level, estimate_range_survival, garman_klass_volatility, horizon_candles,
+    log_returns, optimize_range, parkinson_volatility, periods_per_year, realized_volatility,
+    rolling_volatility,
 };
 use anyhow::{Result, anyhow};
+use futures::future::join;
 use tracing::{debug, info, warn};
 
+const CANDIDATE_RANGE_WIDTHS_PERCENT: [f64; 4] = [5.0, 10.0, 20.0, 30.0];
+const SURVIVAL_HORIZONS_DAYS: [u32; 3] = [1, 7, 30];
+const DEFAULT_SIMULATION_PATHS: usize = 1000;
+const MAX_SIMULATION_PATHS: usize = 10_000;
+const ATR_PERIOD: usize = 14;
+const ROLLING_VOLATILITY_WINDOW: usize = 20;
+const OPTIMIZER_HORIZON_DAYS: u32 = 7;
+const OPTIMIZER_PATHS: usize = 200;
+/// Pool-wide fee APR assumed by the optimizer when the caller does not know the pool
+const DEFAULT_POOL_APR: f64 = 20.0;
+/// Width used when there is too little data to run the optimizer
+const FALLBACK_RANGE_WIDTH_PERCENT: f64 = 10.0;
+
 pub async fn get_price_history_analysis(
+    chain: &ChainConfig,
     token0: &str,
     token1: &str,
     interval: u32,
     limit: u32,
+    options: &RecommendationOptions,
 ) -> Result<PriceHistoryResponse> {
     info!(
         "🔍 Starting price history analysis for {}/{}",
         token0, token1
     );
 
-    let data_provider =
-        get_data_provider("sailor").ok_or_else(|| anyhow!("Unsupported wallet type"))?;
+    let data_provider = require_data_provider("sailor", chain)?;
 
     let price_data = data_provider
         .get_price_data(token0, token1, interval, limit)
@@ -37,13 +63,57 @@ pub async fn get_price_history_analysis(
 
     debug!("📊 Processing {} price points", price_data.len());
 
-    let analysis = analyze_price_data(PriceAnalysisData {
+    let (legs, pool) = join(
+        fetch_usd_legs(data_provider.as_ref(), token0, token1, interval, limit),
+        resolve_pool(chain, token0, token1, options.pool_address.as_deref()),
+    )
+    .await;
+
+    let pool = pool
+        .inspect_err(|e| {
+            warn!(
+                "⚠️ Could not resolve a pool for {}/{}: {}",
+                token0, token1, e
+            )
+        })
+        .ok()
+        .flatten();
+    let legs = match legs {
+        Ok(legs) => Some(legs),
+        Err(e) => {
+            warn!(
+                "⚠️ Could not price the legs of {}/{} in {}: {}",
+                token0, token1, USD_QUOTE, e
+            );
+            None
+        }
+    };
+    let pair_correlation = classify_with_legs(
+        &price_data,
+        legs.as_ref(),
+        interval,
+        DEFAULT_CORRELATION_WINDOW,
+    );
+    let pair_relationship = relationship_info(
+        &pair_correlation,
+        interval,
+        DEFAULT_CORRELATION_WINDOW,
+        legs.as_ref().map(|_| USD_QUOTE),
+    );
+
+    // The Monte Carlo runs are CPU-bound, so keep them off the async workers
+    let analysis_data = PriceAnalysisData {
         token0: token0.to_string(),
         token1: token1.to_string(),
         interval,
         limit,
         data: price_data,
-    })?;
+        options: options.clone(),
+        pair_correlation,
+        pair_relationship,
+        pool,
+    };
+    let analysis = tokio::task::spawn_blocking(move || analyze_price_data(analysis_data)).await??;
 
     info!(
         "✅ Price history analysis completed for {}/{}",
@@ -52,12 +122,39 @@ pub async fn get_price_history_analysis(
     Ok(analysis)
 }
 
+/// Pool detail for the requested address, or for the deepest pool trading the pair
+async fn resolve_pool(
+    chain: &ChainConfig,
+    token0: &str,
+    token1: &str,
+    pool_address: Option<&str>,
+) -> Result<Option<PoolDetailResponse>> {
+    let found = match pool_address {
+        Some(address) => Some(find_pool(chain, address).await?),
+        None => find_pool_for_pair(chain, token0, token1).await?,
+    };
+
+    match found {
+        Some((dex_provider, pool)) => Ok(Some(
+            pool_detail_for(chain, dex_provider.as_ref(), pool, None).await?,
+        )),
+        None => Ok(None),
+    }
+}
+
 fn analyze_price_data(analysis_data: PriceAnalysisData) -> Result<PriceHistoryResponse> {
     let data = &analysis_data.data;
 
     if data.is_empty() {
         return Err(anyhow!("Cannot analyze empty price data"));
     }
+    if data.len() < analysis_data.limit as usize {
+        debug!(
+            "📉 Only {} of {} requested candles are available",
+            data.len(),
+            analysis_data.limit
+        );
+    }
 
     let prices: Vec<f64> = data.iter().map(|p| p.close).collect();
     let highs: Vec<f64> = data.iter().map(|p| p.high).collect();
@@ -67,8 +164,26 @@ fn analyze_price_data(analysis_data: PriceAnalysisData) -> Result<PriceHistoryRe
     let max_price = highs.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
     let avg_price = prices.iter().sum::<f64>() / prices.len() as f64;
 
-    let volatility = calculate_volatility(&prices);
+    let interval = analysis_data.interval;
+    let volatility = calculate_volatility(&prices, interval);
     let volatility_level = determine_volatility_level(volatility);
+    let trend = analyze_trend(&prices);
+
+    let atr = average_true_range(data, ATR_PERIOD);
+    let last_close = prices.last().copied().unwrap_or(0.0);
+    let atr_percent = atr
+        .filter(|_| last_close > 0.0)
+        .map(|atr| atr / last_close * 100.0);
+
+    let rolling_annualized: Vec<RollingVolatility> =
+        rolling_volatility(&prices, ROLLING_VOLATILITY_WINDOW, interval)
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| RollingVolatility {
+                timestamp: data[i + ROLLING_VOLATILITY_WINDOW].tick,
+                value,
+            })
+            .collect();
 
     let recent_prices: Vec<RecentPrice> = data
         .iter()
@@ -82,14 +197,49 @@ fn analyze_price_data(analysis_data: PriceAnalysisData) -> Result<PriceHistoryRe
         })
         .collect();
 
+    let options = &analysis_data.options;
+    let returns = log_returns(&prices);
+    let model = if returns.len() >= 2 && interval > 0 {
+        Some(ReturnModel::calibrate(
+            options.model.as_deref().unwrap_or("gbm"),
+            &returns,
+        )?)
+    } else {
+        None
+    };
+
+    let suggested_range = match &model {
+        Some(model) => Some(suggest_range(
+            model,
+            last_close,
+            interval,
+            &analysis_data.pair_correlation,
+            analysis_data.pool.as_ref(),
+            options,
+        )?),
+        None => None,
+    };
+    let suggested_range_width_percent = suggested_range
+        .as_ref()
+        .map(|r| (r.upper_price - r.lower_price) / 2.0 / last_close * 100.0)
+        .unwrap_or(FALLBACK_RANGE_WIDTH_PERCENT);
+
+    // Ranges and their survival odds start from where the price is now, not the window average
     let recommendation_context = RecommendationContext {
-        center_price: avg_price,
-        suggested_range_width_percent: calculate_suggested_range_width(volatility),
-        trend: determine_trend(&prices),
+        center_price: last_close,
+        suggested_range_width_percent,
+        trend: trend.direction.clone(),
+        pair_relationship: analysis_data.pair_relationship.classification.clone(),
+        suggested_range,
+        range_survival: match &model {
+            Some(model) => simulate_range_survival(model, last_close, interval, options),
+            None => Vec::new(),
+        },
     };
 
+    let pair = format!("{}/{}", analysis_data.token0, analysis_data.token1);
     let response = PriceHistoryResponse {
-        pair: format!("{}/{}", analysis_data.token0, analysis_data.token1),
+        pair,
         price_range: PriceRange {
             min: min_price,
             max: max_price,
@@ -99,13 +249,32 @@ fn analyze_price_data(analysis_data: PriceAnalysisData) -> Result<PriceHistoryRe
             value: volatility,
             percentage: volatility * 100.0,
             level: volatility_level,
+            realized_annualized: realized_volatility(&prices, interval),
+            parkinson_annualized: parkinson_volatility(data, interval),
+            garman_klass_annualized: garman_klass_volatility(data, interval),
+            atr,
+            atr_percent,
+            rolling_window: ROLLING_VOLATILITY_WINDOW,
+            rolling_annualized,
+        },
+        trend: TrendInfo {
+            direction: trend.direction,
+            slope: trend.slope,
+            r_squared: trend.r_squared,
+            change_percent: trend.change * 100.0,
         },
+        pair_relationship: analysis_data.pair_relationship,
         data_points: data.len(),
-        interval_minutes: analysis_data.interval,
-        pool_info: Some(PoolInfo {
-            pool_id: None,
-            tvl: None,
-            fee_tier: None,
+        interval_minutes: interval,
+        pool_info: analysis_data.pool.map(|pool| PoolInfo {
+            pool_id: Some(pool.address),
+            protocol: Some(pool.protocol),
+            tvl: pool.tvl,
+            fee_tier: pool.fee_tier,
+            tick_spacing: pool.tick_spacing,
+            current_tick: pool.current_tick,
+            daily_volume: pool.daily_volume,
+            apr: pool.apr,
         }),
         recent_prices,
         recommendation_context,
@@ -114,10 +283,138 @@ fn analyze_price_data(analysis_data: PriceAnalysisData) -> Result<PriceHistoryRe
     Ok(response)
 }
 
+/// Optimizer range around the centre for the requested risk profile.
+///
+/// The search grid follows the pair classification, so pegged pairs get tight ranges that still
+/// cover the deviations seen in the sample. Fee tier and APR come from the pool when known.
+fn suggest_range(
+    model: &ReturnModel,
+    center_price: f64,
+    interval: u32,
+    pair_correlation: &PairCorrelation,
+    pool: Option<&PoolDetailResponse>,
+    options: &RecommendationOptions,
+) -> Result<SuggestedRange> {
+    let risk_profile: RiskProfile = options
+        .risk_profile
+        .as_deref()
+        .unwrap_or("balanced")
+        .parse()?;
+
+    let relationship = pair_correlation.relationship;
+    let (min_half_width, max_half_width) = relationship.range_half_width_bounds();
+    let min_half_width = match relationship {
+        PairRelationship::Pegged => pair_correlation
+            .max_deviation
+            .clamp(min_half_width, max_half_width),
+        _ => min_half_width,
+    };
+
+    let candles = horizon_candles(OPTIMIZER_HORIZON_DAYS, interval)
+        .ok_or_else(|| anyhow!("Cannot simulate a horizon at a {}min interval", interval))?;
+    let candles_per_step = candles_per_step(candles);
+
+    // Ticks are not exposed here, so token decimals only need to be consistent
+    let optimized = optimize_range(
+        &model.over_candles(candles_per_step),
+        &RangeOptimizerParams {
+            current_price: center_price,
+            token0_decimals: 18,
+            token1_decimals: 18,
+            fee_tier: pool
+                .and_then(|p| p.fee_tier)
+                .and_then(FeeTier::from_percent)
+                .unwrap_or_else(|| relationship.typical_fee_tier()),
+            pool_apr: options
+                .pool_apr
+                .or_else(|| pool.and_then(|p| p.apr))
+                .unwrap_or(DEFAULT_POOL_APR),
+            horizon_steps: candles.div_ceil(candles_per_step),
+            periods_per_year: periods_per_year(interval) / candles_per_step as f64,
+            risk_profile,
+            min_half_width,
+            max_half_width,
+            paths: OPTIMIZER_PATHS,
+            seed: options.seed,
+        },
+    )?;
+
+    Ok(SuggestedRange {
+        risk_profile: risk_profile.as_str().to_string(),
+        lower_price: optimized.price_lower,
+        upper_price: optimized.price_upper,
+        expected_fee_return_percent: optimized.expected_fees * 100.0,
+        expected_il_percent: optimized.expected_il * 100.0,
+        expected_time_in_range_percent: optimized.expected_time_in_range * 100.0,
+        horizon_days: OPTIMIZER_HORIZON_DAYS,
+    })
+}
+
+/// Monte Carlo survival of the candidate ranges, calibrated on the close-to-close log returns
+fn simulate_range_survival(
+    model: &ReturnModel,
+    center_price: f64,
+    interval: u32,
+    options: &RecommendationOptions,
+) -> Vec<RangeSurvival> {
+    let ranges: Vec<(f64, f64)> = CANDIDATE_RANGE_WIDTHS_PERCENT
+        .iter()
+        .map(|w| {
+            (
+                center_price * (1.0 - w / 100.0),
+                center_price * (1.0 + w / 100.0),
+            )
+        })
+        .collect();
+
+    // Long horizons at short intervals are simulated several candles per step
+    let horizons_candles: Vec<usize> = SURVIVAL_HORIZONS_DAYS
+        .iter()
+        .map(|days| horizon_candles(*days, interval).unwrap_or(1))
+        .collect();
+    let candles_per_step = candles_per_step(horizons_candles.iter().copied().max().unwrap_or(1));
+    let horizons_steps: Vec<usize> = horizons_candles
+        .iter()
+        .map(|candles| candles.div_ceil(candles_per_step))
+        .collect();
+
+    let paths = options
+        .paths
+        .unwrap_or(DEFAULT_SIMULATION_PATHS)
+        .clamp(1, MAX_SIMULATION_PATHS);
+
+    let probabilities = estimate_range_survival(
+        &model.over_candles(candles_per_step),
+        center_price,
+        &ranges,
+        &horizons_steps,
+        paths,
+        options.seed,
+    );
+
+    CANDIDATE_RANGE_WIDTHS_PERCENT
+        .iter()
+        .zip(ranges)
+        .zip(probabilities)
+        .map(|((width, (lower, upper)), p)| RangeSurvival {
+            range_width_percent: *width,
+            lower_price: lower,
+            upper_price: upper,
+            probability_1d: p[0],
+            probability_7d: p[1],
+            probability_30d: p[2],
+        })
+        .collect()
+}
+
 struct PriceAnalysisData {
     token0: String,
     token1: String,
     interval: u32,
     limit: u32,
     data: Vec<PricePoint>,
+    options: RecommendationOptions,
+    pair_correlation: PairCorrelation,
+    pair_relationship: PairRelationshipInfo,
+    pool: Option<PoolDetailResponse>,
 }
@@ -1,11 +1,13 @@
 use crate::{
-    domain::services::data::Token,
+    config::ChainConfig, domain::services::data::Token,
     infrastructure::data::dragonswap_data_provider::DragonSwapDataProvider,
 };
+use anyhow::anyhow;
 use std::error::Error;
 
-pub async fn get_token_symbol(address: &str) -> Result<Token, Box<dyn Error>> {
-    let dragonswap_data_provider = DragonSwapDataProvider::new();
+pub async fn get_token_symbol(chain: &ChainConfig, address: &str) -> Result<Token, Box<dyn Error>> {
+    let dragonswap_data_provider = DragonSwapDataProvider::for_chain(chain)
+        .ok_or_else(|| anyhow!("DragonSwap is not configured on {}", chain.name))?;
     let token_symbol = dragonswap_data_provider
         .transform_token_address_to_symbol(address)
         .await?;
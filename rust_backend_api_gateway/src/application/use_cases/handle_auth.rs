@@ -1,16 +1,25 @@
 use crate::application::dtos::auth::AuthRequest;
+use crate::config::Chains;
 use crate::domain::repositories::jwt::JwtEncoder;
 use crate::infrastructure::jwt::Hs256Jwt;
 use crate::infrastructure::wallet::get_verifier;
 use actix_web::HttpResponse;
 
-pub fn handle_auth(data: AuthRequest) -> Result<String, HttpResponse> {
+#[allow(clippy::result_large_err)]
+pub fn handle_auth(chains: &Chains, data: AuthRequest) -> Result<String, HttpResponse> {
     let verifier = get_verifier(&data.wallet_type)
         .ok_or_else(|| HttpResponse::BadRequest().body("Unsupported wallet type"))?;
 
+    let chain = match data.chain_id {
+        Some(chain_id) => chains
+            .find_by_id(chain_id)
+            .ok_or_else(|| HttpResponse::BadRequest().body("Unsupported chain"))?,
+        None => chains.default_chain(),
+    };
+
     verifier.verify(&data)?;
 
     Hs256Jwt
-        .encode(&data.address)
+        .encode(&data.address, chain.id)
         .map_err(|_| HttpResponse::InternalServerError().body("Token generation failed"))
 }
@@ -1,14 +1,42 @@
 pub mod handle_auth;
 pub use handle_auth::*;
+pub mod analyze_pair_correlation;
+pub use analyze_pair_correlation::*;
+pub mod assess_apr_sustainability;
+pub use assess_apr_sustainability::*;
+pub mod cross_check_pool;
+pub use cross_check_pool::*;
+pub mod estimate_fee_apr;
+pub use estimate_fee_apr::*;
 pub mod get_graph_data;
 pub use get_graph_data::*;
+pub mod get_indicators;
+pub use get_indicators::*;
 pub mod get_kline_data;
 pub use get_kline_data::*;
+pub mod get_optimal_range;
+pub use get_optimal_range::*;
 pub mod get_token_symbol;
 pub use get_token_symbol::*;
+pub mod get_pool_detail;
+pub use get_pool_detail::*;
+pub mod get_pool_history;
+pub use get_pool_history::*;
 pub mod get_pool_list;
 pub use get_pool_list::*;
 pub mod get_price_history_analysis;
 pub use get_price_history_analysis::*;
+pub mod rank_pair_pools;
+pub use rank_pair_pools::*;
+pub mod run_backtest;
+pub use run_backtest::*;
+pub mod scan_price_divergences;
+pub use scan_price_divergences::*;
+pub mod score_pool_risk;
+pub use score_pool_risk::*;
+pub mod simulate_strategies;
+pub use simulate_strategies::*;
+pub mod value_positions;
+pub use value_positions::*;
 pub mod forward_prompt_to_backend;
 pub use forward_prompt_to_backend::*;
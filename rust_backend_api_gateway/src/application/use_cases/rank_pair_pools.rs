@@ -0,0 +1,230 @@
+use crate::application::dtos::pair_pools::{PairPoolRanking, PairPoolsResponse};
+use crate::application::use_cases::{
+    dex_provider, latest_close, pool_trades_pair, query_pool_providers, source_status,
+    token_matches,
+};
+use crate::config::ChainConfig;
+use crate::domain::services::data::{ActiveLiquidityResponse, UnifiedPool};
+use crate::math::{
+    liquidity_distribution, price_to_tick, tick_after_token0_in, tick_after_token1_in,
+    token0_depth, token1_depth,
+};
+use anyhow::Result;
+use futures::future::join_all;
+use std::cmp::Ordering;
+use tracing::{info, warn};
+
+/// Fields pair venues can be ranked on
+pub const PAIR_SORT_FIELDS: [&str; 4] = ["score", "depth", "fee_apr", "price_impact"];
+pub const DEFAULT_PAIR_TRADE_SIZE: f64 = 1000.0;
+/// Half-width of the price window depth is measured over
+const DEPTH_WINDOW: f64 = 0.02;
+
+/// Every pool trading `token_a`/`token_b` (in either order) across the chain's DEXes, best venue
+/// first
+pub async fn rank_pair_pools(
+    chain: &ChainConfig,
+    token_a: &str,
+    token_b: &str,
+    trade_size: f64,
+    sort_by: &str,
+) -> Result<PairPoolsResponse> {
+    info!(
+        "⚖️ Ranking pools for {}/{} on {}",
+        token_a, token_b, chain.name
+    );
+
+    let mut sources = Vec::new();
+    let mut venues = Vec::new();
+    for provider_pools in query_pool_providers(chain).await {
+        sources.push(source_status(&provider_pools));
+        let provider = provider_pools.provider;
+        if let Ok(pools) = provider_pools.result {
+            venues.extend(
+                pools
+                    .into_iter()
+                    .filter(|pool| pool_trades_pair(pool, token_a, token_b))
+                    .map(|pool| (provider.clone(), pool)),
+            );
+        }
+    }
+
+    // Path tokens may be addresses; price lookups need the symbols
+    let (symbol_a, symbol_b) = match venues.first() {
+        Some((_, pool)) if token_matches(&pool.token0, token_a) => {
+            (pool.token0.symbol.clone(), pool.token1.symbol.clone())
+        }
+        Some((_, pool)) => (pool.token1.symbol.clone(), pool.token0.symbol.clone()),
+        None => (token_a.to_string(), token_b.to_string()),
+    };
+
+    let current_price = if venues.is_empty() {
+        None
+    } else {
+        latest_close(chain, &symbol_a, &symbol_b)
+            .await
+            .inspect_err(|e| warn!("⚠️ No price for {}/{}: {}", symbol_a, symbol_b, e))
+            .ok()
+    };
+
+    let liquidity = join_all(venues.iter().map(|(provider, pool)| async move {
+        dex_provider(provider, chain)?
+            .get_liquidity_data(&pool.id)
+            .await
+    }))
+    .await;
+
+    let mut pools: Vec<PairPoolRanking> = venues
+        .iter()
+        .zip(liquidity)
+        .map(|((_, pool), liquidity)| {
+            let liquidity = liquidity
+                .inspect_err(|e| warn!("⚠️ No liquidity data for pool {}: {}", pool.id, e))
+                .ok();
+            venue_ranking(pool, liquidity.as_ref(), current_price, token_a, trade_size)
+        })
+        .collect();
+
+    score_venues(&mut pools);
+    pools.sort_by(|a, b| {
+        let ordering = match sort_by {
+            "depth" => by_metric(a.depth_2_percent, b.depth_2_percent, true),
+            "fee_apr" => by_metric(a.fee_apr_percent, b.fee_apr_percent, true),
+            "price_impact" => by_metric(a.price_impact_percent, b.price_impact_percent, false),
+            _ => b.score.total_cmp(&a.score),
+        };
+        ordering.then_with(|| a.address.cmp(&b.address))
+    });
+    for (i, pool) in pools.iter_mut().enumerate() {
+        pool.rank = i + 1;
+    }
+
+    Ok(PairPoolsResponse {
+        token_a: symbol_a,
+        token_b: symbol_b,
+        current_price,
+        trade_size,
+        sort_by: sort_by.to_string(),
+        pools,
+        sources,
+    })
+}
+
+/// Depth and price impact of one pool, oriented so token B is the quote
+fn venue_ranking(
+    pool: &UnifiedPool,
+    liquidity: Option<&ActiveLiquidityResponse>,
+    price_ab: Option<f64>,
+    token_a: &str,
+    trade_size: f64,
+) -> PairPoolRanking {
+    let fee_tier = pool.fee_tier_percent();
+    let fee_apr_percent = pool.apr.or_else(|| {
+        let tvl = pool.tvl_f64().filter(|tvl| *tvl > 0.0)?;
+        Some(pool.daily_volume_f64()? * fee_tier? / tvl * 365.0)
+    });
+
+    let mut ranking = PairPoolRanking {
+        rank: 0,
+        address: pool.id.clone(),
+        protocol: pool.protocol.clone(),
+        fee_tier,
+        tvl: pool.tvl_f64(),
+        daily_volume: pool.daily_volume_f64(),
+        fee_apr_percent,
+        depth_2_percent: None,
+        price_impact_percent: None,
+        score: 0.0,
+    };
+
+    let (Some(liquidity), Some(price_ab)) = (liquidity, price_ab.filter(|p| *p > 0.0)) else {
+        return ranking;
+    };
+    let distribution = liquidity_distribution(liquidity);
+    if distribution.len() < 2 {
+        return ranking;
+    }
+
+    // Token A is token0 when the pool quotes token B, otherwise the pool price is inverted
+    let b_is_token1 = token_matches(&pool.token0, token_a);
+    let pool_price = if b_is_token1 {
+        price_ab
+    } else {
+        1.0 / price_ab
+    };
+    let token0_decimals = pool.token0.decimals_or_default();
+    let token1_decimals = pool.token1.decimals_or_default();
+    let token0_scale = 10f64.powi(token0_decimals as i32);
+    let token1_scale = 10f64.powi(token1_decimals as i32);
+    let current_tick = price_to_tick(pool_price, token0_decimals, token1_decimals);
+    let tick_radius = (1.0 + DEPTH_WINDOW).ln() / 1.0001f64.ln();
+
+    let depth_token1 = token1_depth(&distribution, current_tick, current_tick + tick_radius)
+        / token1_scale
+        + token0_depth(&distribution, current_tick, current_tick - tick_radius) / token0_scale
+            * pool_price;
+    ranking.depth_2_percent = Some(if b_is_token1 {
+        depth_token1
+    } else {
+        depth_token1 / pool_price
+    });
+
+    let amount_in = trade_size * (1.0 - fee_tier.unwrap_or(0.0) / 100.0);
+    let end_tick = if b_is_token1 {
+        tick_after_token1_in(&distribution, current_tick, amount_in * token1_scale)
+    } else {
+        tick_after_token0_in(&distribution, current_tick, amount_in * token0_scale)
+    };
+    ranking.price_impact_percent =
+        end_tick.map(|tick| (1.0001f64.powf(tick - current_tick) - 1.0).abs() * 100.0);
+
+    ranking
+}
+
+/// Score each venue 0-100 against the best value of each metric; missing metrics count as zero
+fn score_venues(pools: &mut [PairPoolRanking]) {
+    let best = |metric: fn(&PairPoolRanking) -> Option<f64>, higher_is_better: bool| {
+        let values = pools.iter().filter_map(metric).filter(|v| v.is_finite());
+        if higher_is_better {
+            values.fold(None, |best: Option<f64>, v| {
+                Some(best.map_or(v, |b| b.max(v)))
+            })
+        } else {
+            values.fold(None, |best: Option<f64>, v| {
+                Some(best.map_or(v, |b| b.min(v)))
+            })
+        }
+    };
+    let best_depth = best(|p| p.depth_2_percent, true);
+    let best_apr = best(|p| p.fee_apr_percent, true);
+    let best_impact = best(|p| p.price_impact_percent, false);
+
+    for pool in pools.iter_mut() {
+        let depth = ratio(pool.depth_2_percent, best_depth);
+        let apr = ratio(pool.fee_apr_percent, best_apr);
+        let impact = match (pool.price_impact_percent, best_impact) {
+            (Some(impact), Some(best)) if impact > 0.0 => best / impact,
+            (Some(_), Some(_)) => 1.0,
+            _ => 0.0,
+        };
+        pool.score = (depth + apr + impact) / 3.0 * 100.0;
+    }
+}
+
+fn ratio(value: Option<f64>, best: Option<f64>) -> f64 {
+    match (value, best) {
+        (Some(value), Some(best)) if best > 0.0 => (value / best).clamp(0.0, 1.0),
+        _ => 0.0,
+    }
+}
+
+/// Order by a metric, venues without one last
+fn by_metric(a: Option<f64>, b: Option<f64>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(x), Some(y)) if descending => y.total_cmp(&x),
+        (Some(x), Some(y)) => x.total_cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
@@ -0,0 +1,96 @@
+use crate::application::dtos::backtest::{BacktestRequest, BacktestResponse, RangeBacktestResult};
+use crate::config::ChainConfig;
+use crate::infrastructure::data::require_data_provider;
+use crate::math::{BacktestParams, backtest_range};
+use anyhow::{Result, anyhow};
+use tracing::{info, warn};
+
+const DEFAULT_RANGE_WIDTHS_PERCENT: [f64; 4] = [5.0, 10.0, 20.0, 30.0];
+
+pub async fn run_backtest(
+    chain: &ChainConfig,
+    request: &BacktestRequest,
+) -> Result<BacktestResponse> {
+    let interval = request.interval.unwrap_or(60);
+    let limit = request.limit.unwrap_or(200);
+
+    info!(
+        "🧪 Starting range backtest for {}/{} (interval: {}min, limit: {})",
+        request.token0, request.token1, interval, limit
+    );
+
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let price_data = data_provider
+        .get_price_data(&request.token0, &request.token1, interval, limit)
+        .await?;
+
+    let (Some(first), Some(last)) = (price_data.first(), price_data.last()) else {
+        warn!(
+            "⚠️ No price data found for {}/{}",
+            request.token0, request.token1
+        );
+        return Err(anyhow!(
+            "No price data available for the specified token pair"
+        ));
+    };
+    let start_price = first.close;
+    let end_price = last.close;
+
+    let widths = request
+        .range_widths_percent
+        .clone()
+        .unwrap_or_else(|| DEFAULT_RANGE_WIDTHS_PERCENT.to_vec());
+
+    let results: Vec<RangeBacktestResult> = widths
+        .into_iter()
+        .map(|width| {
+            let params = BacktestParams {
+                lower_price: start_price * (1.0 - width / 100.0),
+                upper_price: start_price * (1.0 + width / 100.0),
+                capital: request.capital,
+                fee_rate: request.fee_tier / 100.0,
+                pool_tvl: request.pool_tvl,
+            };
+            let result = backtest_range(&price_data, &params);
+
+            RangeBacktestResult {
+                range_width_percent: width,
+                lower_price: result.lower_price,
+                upper_price: result.upper_price,
+                liquidity: result.liquidity,
+                fees_earned: result.fees_earned,
+                fee_apr_percent: result.fee_apr,
+                final_position_value: result.final_position_value,
+                hold_value: result.hold_value,
+                impermanent_loss_percent: result.impermanent_loss * 100.0,
+                time_in_range_percent: result.time_in_range * 100.0,
+                pnl: result.pnl,
+                pnl_percent: result.pnl_percent,
+            }
+        })
+        .collect();
+
+    let best_range_width_percent = results
+        .iter()
+        .max_by(|a, b| a.pnl.total_cmp(&b.pnl))
+        .map(|r| r.range_width_percent);
+
+    info!(
+        "✅ Range backtest completed for {}/{} over {} candles",
+        request.token0,
+        request.token1,
+        price_data.len()
+    );
+
+    Ok(BacktestResponse {
+        pair: format!("{}/{}", request.token0, request.token1),
+        interval_minutes: interval,
+        data_points: price_data.len(),
+        start_price,
+        end_price,
+        capital: request.capital,
+        results,
+        best_range_width_percent,
+    })
+}
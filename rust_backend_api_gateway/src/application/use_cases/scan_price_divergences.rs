@@ -0,0 +1,305 @@
+use crate::application::dtos::divergence::DivergenceObservation;
+use crate::application::use_cases::query_pool_providers;
+use crate::config::ChainConfig;
+use crate::domain::services::data::UnifiedPool;
+use crate::infrastructure::data::get_data_provider;
+use anyhow::{Result, anyhow};
+use futures::future::join_all;
+use std::collections::BTreeMap;
+use tracing::{debug, info};
+
+/// One venue's current price for a pair, quote per base
+#[derive(Debug, Clone)]
+struct VenueQuote {
+    venue: String,
+    pool: Option<String>,
+    price: f64,
+}
+
+/// Every venue's quote for one pair, base and quote ordered by token address
+#[derive(Debug)]
+struct PairQuotes {
+    base_symbol: String,
+    quote_symbol: String,
+    venues: Vec<VenueQuote>,
+}
+
+/// Compare current prices for every pair listed on the chain's DEXes (and Binance where it
+/// trades) and return the pairs whose cheapest and richest venues are `threshold_percent` or more
+/// apart
+pub async fn scan_price_divergences(
+    chain: &ChainConfig,
+    threshold_percent: f64,
+    min_tvl: f64,
+) -> Result<Vec<DivergenceObservation>> {
+    let mut any_source = false;
+    let mut pairs: BTreeMap<(String, String), PairQuotes> = BTreeMap::new();
+
+    for provider_pools in query_pool_providers(chain).await {
+        let Ok(pools) = provider_pools.result else {
+            continue;
+        };
+        any_source = true;
+
+        add_pool_quotes(&mut pairs, pools, min_tvl);
+    }
+
+    if !any_source {
+        return Err(anyhow!("No pool provider returned data"));
+    }
+
+    let binance_quotes = join_all(
+        pairs
+            .values()
+            .map(|pair| binance_price(chain, &pair.base_symbol, &pair.quote_symbol)),
+    )
+    .await;
+    for (pair, binance) in pairs.values_mut().zip(binance_quotes) {
+        if let Some(price) = binance {
+            pair.venues.push(VenueQuote {
+                venue: "Binance".to_string(),
+                pool: None,
+                price,
+            });
+        }
+    }
+
+    let divergences: Vec<DivergenceObservation> = pairs
+        .into_iter()
+        .filter_map(|((base_address, quote_address), pair)| {
+            let observation = widest_spread(chain.id, base_address, quote_address, &pair)?;
+            (observation.spread_percent >= threshold_percent).then_some(observation)
+        })
+        .collect();
+
+    info!(
+        "🔀 Divergence scan on {} found {} pairs above {}%",
+        chain.name,
+        divergences.len(),
+        threshold_percent
+    );
+    Ok(divergences)
+}
+
+/// Group the pools' prices by pair. Pairs are keyed by sorted token addresses so both pool
+/// orientations land together and tokens sharing a symbol stay apart.
+fn add_pool_quotes(
+    pairs: &mut BTreeMap<(String, String), PairQuotes>,
+    pools: Vec<UnifiedPool>,
+    min_tvl: f64,
+) {
+    for pool in pools {
+        let Some(price) = pool.price.filter(|p| p.is_finite() && *p > 0.0) else {
+            continue;
+        };
+        if pool.tvl_f64().unwrap_or(0.0) < min_tvl {
+            continue;
+        }
+
+        let (token0, token1) = (&pool.token0, &pool.token1);
+        let (address0, address1) = (token0.address.to_lowercase(), token1.address.to_lowercase());
+        let (key, symbols, price) = if address0 <= address1 {
+            (
+                (address0, address1),
+                (&token0.symbol, &token1.symbol),
+                price,
+            )
+        } else {
+            (
+                (address1, address0),
+                (&token1.symbol, &token0.symbol),
+                1.0 / price,
+            )
+        };
+        pairs
+            .entry(key)
+            .or_insert_with(|| PairQuotes {
+                base_symbol: symbols.0.to_uppercase(),
+                quote_symbol: symbols.1.to_uppercase(),
+                venues: Vec::new(),
+            })
+            .venues
+            .push(VenueQuote {
+                venue: pool.protocol,
+                pool: Some(pool.id),
+                price,
+            });
+    }
+}
+
+fn widest_spread(
+    chain_id: u64,
+    base_address: String,
+    quote_address: String,
+    pair: &PairQuotes,
+) -> Option<DivergenceObservation> {
+    let venues = &pair.venues;
+    if venues.len() < 2 {
+        return None;
+    }
+    let cheap = venues.iter().min_by(|a, b| a.price.total_cmp(&b.price))?;
+    let rich = venues.iter().max_by(|a, b| a.price.total_cmp(&b.price))?;
+
+    Some(DivergenceObservation {
+        chain_id,
+        pair: format!("{}/{}", pair.base_symbol, pair.quote_symbol),
+        base_address,
+        quote_address,
+        cheap_venue: cheap.venue.clone(),
+        cheap_pool: cheap.pool.clone(),
+        cheap_price: cheap.price,
+        rich_venue: rich.venue.clone(),
+        rich_pool: rich.pool.clone(),
+        rich_price: rich.price,
+        spread_percent: (rich.price / cheap.price - 1.0) * 100.0,
+        venue_count: venues.len() as i32,
+    })
+}
+
+/// Latest one-minute close on Binance, trying the inverse market when the direct one is not listed
+async fn binance_price(chain: &ChainConfig, base: &str, quote: &str) -> Option<f64> {
+    let (base, quote) = (binance_symbol(base)?, binance_symbol(quote)?);
+    if base == quote {
+        return None;
+    }
+
+    match binance_close(chain, base, quote).await {
+        Some(price) => Some(price),
+        None => binance_close(chain, quote, base)
+            .await
+            .map(|price| 1.0 / price),
+    }
+}
+
+async fn binance_close(chain: &ChainConfig, token0: &str, token1: &str) -> Option<f64> {
+    let data_provider = get_data_provider("binance", chain)?;
+
+    data_provider
+        .get_price_data(token0, token1, 1, 1)
+        .await
+        .inspect_err(|e| debug!("No Binance market {}{}: {}", token0, token1, e))
+        .ok()?
+        .last()
+        .map(|p| p.close)
+        .filter(|p| *p > 0.0)
+}
+
+/// Binance symbol for tokens it lists; wrapped tokens map to the native asset
+pub fn binance_symbol(symbol: &str) -> Option<&'static str> {
+    match symbol {
+        "WSEI" | "SEI" => Some("SEI"),
+        "WETH" | "ETH" => Some("ETH"),
+        "WBTC" | "BTC" => Some("BTC"),
+        "USDC" => Some("USDC"),
+        "USDT" => Some("USDT"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::data::Token;
+    use rust_decimal::Decimal;
+
+    fn token(address: &str, symbol: &str) -> Token {
+        Token {
+            chain_id: 1329,
+            address: address.to_string(),
+            symbol: symbol.to_string(),
+            decimals: Some(18),
+            usd_price: None,
+        }
+    }
+
+    fn pool(id: &str, token0: Token, token1: Token, price: f64, tvl: i64) -> UnifiedPool {
+        UnifiedPool {
+            chain_id: 1329,
+            id: id.to_string(),
+            protocol: id.to_string(),
+            token0,
+            token1,
+            tvl: Some(Decimal::from(tvl)),
+            daily_volume: None,
+            apr: None,
+            fee_tier: None,
+            price: Some(price),
+            risk: None,
+        }
+    }
+
+    #[test]
+    fn groups_both_orientations_by_address() {
+        let (sei, usdc) = (token("0xB", "wsei"), token("0xa", "USDC"));
+        let mut pairs = BTreeMap::new();
+        add_pool_quotes(
+            &mut pairs,
+            vec![
+                pool("DragonSwap", sei.clone(), usdc.clone(), 0.25, 50_000),
+                pool("Sailor", usdc, sei, 5.0, 50_000),
+            ],
+            10_000.0,
+        );
+
+        let pair = &pairs[&("0xa".to_string(), "0xb".to_string())];
+        assert_eq!(
+            (pair.base_symbol.as_str(), pair.quote_symbol.as_str()),
+            ("USDC", "WSEI")
+        );
+        let prices: Vec<f64> = pair.venues.iter().map(|v| v.price).collect();
+        assert_eq!(prices, vec![4.0, 5.0]);
+
+        let spread = widest_spread(1329, "0xa".into(), "0xb".into(), pair).unwrap();
+        assert_eq!(spread.pair, "USDC/WSEI");
+        assert_eq!(spread.cheap_venue, "DragonSwap");
+        assert_eq!(spread.rich_venue, "Sailor");
+        assert!((spread.spread_percent - 25.0).abs() < 1e-9);
+        assert_eq!(spread.venue_count, 2);
+    }
+
+    #[test]
+    fn keeps_tokens_sharing_a_symbol_apart() {
+        let usdc = token("0xa", "USDC");
+        let mut pairs = BTreeMap::new();
+        add_pool_quotes(
+            &mut pairs,
+            vec![
+                pool(
+                    "DragonSwap",
+                    token("0xb", "USDT"),
+                    usdc.clone(),
+                    1.0,
+                    50_000,
+                ),
+                // A bridged USDT with the same symbol is a different market
+                pool("Sailor", token("0xc", "USDT"), usdc, 0.9, 50_000),
+            ],
+            10_000.0,
+        );
+
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.values().all(|pair| pair.venues.len() == 1));
+        assert!(
+            pairs
+                .values()
+                .all(|pair| widest_spread(1329, String::new(), String::new(), pair).is_none())
+        );
+    }
+
+    #[test]
+    fn skips_thin_and_unpriced_pools() {
+        let (a, b) = (token("0xa", "A"), token("0xb", "B"));
+        let mut pairs = BTreeMap::new();
+        add_pool_quotes(
+            &mut pairs,
+            vec![
+                pool("thin", a.clone(), b.clone(), 1.0, 500),
+                pool("zero", a.clone(), b.clone(), 0.0, 50_000),
+                pool("nan", a, b, f64::NAN, 50_000),
+            ],
+            10_000.0,
+        );
+
+        assert!(pairs.is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::application::dtos::pool_history::PoolSnapshot;
+use crate::application::service::divergence_service::get_pool_divergences;
+use crate::application::service::snapshot_service::{
+    get_recent_pool_snapshots, get_token_first_seen,
+};
+use crate::application::use_cases::{ProviderPools, binance_symbol};
+use crate::config::{divergence_min_tvl, divergence_threshold_percent, snapshot_interval_secs};
+use crate::domain::services::data::{Token, UnifiedPool};
+use crate::math::{RiskInputs, TokenListing, TokenMaturity, score_pool_risk};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::DatabaseConnection;
+use std::collections::{HashMap, HashSet};
+use tracing::warn;
+
+/// Snapshot window behind the TVL stability and (in lists) volatility factors
+const RISK_HISTORY_DAYS: i64 = 7;
+/// Divergences older than this no longer count against a pool
+const RISK_DIVERGENCE_HOURS: i64 = 24;
+
+/// Stored history shared by every pool scored in one request
+#[derive(Default)]
+pub struct RiskContext {
+    snapshots: HashMap<String, Vec<PoolSnapshot>>,
+    token_first_seen: HashMap<String, DateTime<Utc>>,
+    divergences: HashMap<String, f64>,
+    /// Number of providers listing each token, by lowercase address
+    token_listings: HashMap<String, usize>,
+}
+
+/// Providers listing each token, counted over every provider that answered
+pub fn count_token_listings(provider_pools: &[ProviderPools]) -> HashMap<String, usize> {
+    let mut providers: HashMap<String, HashSet<&str>> = HashMap::new();
+    for provider_pools in provider_pools {
+        let Ok(pools) = &provider_pools.result else {
+            continue;
+        };
+        for token in pools.iter().flat_map(|pool| [&pool.token0, &pool.token1]) {
+            providers
+                .entry(token.address.to_lowercase())
+                .or_default()
+                .insert(provider_pools.provider.as_str());
+        }
+    }
+
+    providers
+        .into_iter()
+        .map(|(address, providers)| (address, providers.len()))
+        .collect()
+}
+
+/// Load the history the pools are scored against; a failed query only drops its factors
+pub async fn load_risk_context(
+    db: &DatabaseConnection,
+    chain_id: u64,
+    pools: &[UnifiedPool],
+    token_listings: HashMap<String, usize>,
+) -> RiskContext {
+    let now = Utc::now();
+    let pool_addresses: Vec<String> = pools.iter().map(|p| p.id.to_lowercase()).collect();
+    let token_addresses: Vec<String> = pools
+        .iter()
+        .flat_map(|pool| [&pool.token0, &pool.token1])
+        .map(|token| token.address.to_lowercase())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let (snapshots, token_first_seen, divergences) = futures::join!(
+        get_recent_pool_snapshots(
+            db,
+            chain_id,
+            &pool_addresses,
+            now - Duration::days(RISK_HISTORY_DAYS)
+        ),
+        get_token_first_seen(db, chain_id, &token_addresses),
+        get_pool_divergences(
+            db,
+            chain_id,
+            &pool_addresses,
+            now - Duration::hours(RISK_DIVERGENCE_HOURS)
+        ),
+    );
+
+    RiskContext {
+        snapshots: snapshots
+            .inspect_err(|e| warn!("⚠️ No pool snapshots for risk scoring: {}", e))
+            .unwrap_or_default(),
+        token_first_seen: token_first_seen
+            .inspect_err(|e| warn!("⚠️ No token history for risk scoring: {}", e))
+            .unwrap_or_default(),
+        divergences: divergences
+            .inspect_err(|e| warn!("⚠️ No divergence history for risk scoring: {}", e))
+            .unwrap_or_default(),
+        token_listings,
+    }
+}
+
+/// Inputs for one pool from the shared context; prices come from snapshots
+pub fn risk_inputs(pool: &UnifiedPool, context: &RiskContext) -> RiskInputs {
+    let snapshots = context
+        .snapshots
+        .get(&pool.id.to_lowercase())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let (divergence_percent, divergence_note) =
+        match context.divergences.get(&pool.id.to_lowercase()) {
+            Some(spread) => (
+                Some(*spread),
+                Some(format!(
+                    "Spread of {:.2}% against other venues in the last {}h",
+                    spread, RISK_DIVERGENCE_HOURS
+                )),
+            ),
+            None if pool.tvl_f64().unwrap_or(0.0) >= divergence_min_tvl() => (
+                Some(0.0),
+                Some(format!(
+                    "No spread above {}% recorded in the last {}h",
+                    divergence_threshold_percent(),
+                    RISK_DIVERGENCE_HOURS
+                )),
+            ),
+            None => (
+                None,
+                Some(format!(
+                    "Below the ${:.0} TVL the divergence monitor watches",
+                    divergence_min_tvl()
+                )),
+            ),
+        };
+
+    RiskInputs {
+        tvl: pool.tvl_f64(),
+        daily_volume: pool.daily_volume_f64(),
+        tvl_history: snapshots.iter().filter_map(|s| s.tvl).collect(),
+        prices: snapshots.iter().filter_map(|s| s.price).collect(),
+        price_interval_minutes: (snapshot_interval_secs() / 60) as u32,
+        concentration_2_percent: None,
+        tokens: [&pool.token0, &pool.token1]
+            .into_iter()
+            .map(|token| token_maturity(token, context))
+            .collect(),
+        divergence_percent,
+        divergence_note,
+    }
+}
+
+/// Attach a risk score to every pool
+pub fn score_pools(pools: &mut [UnifiedPool], context: &RiskContext) {
+    for pool in pools {
+        pool.risk = Some(score_pool_risk(&risk_inputs(pool, context)));
+    }
+}
+
+fn token_maturity(token: &Token, context: &RiskContext) -> TokenMaturity {
+    let address = token.address.to_lowercase();
+    let listing = if binance_symbol(&token.symbol.to_uppercase()).is_some() {
+        TokenListing::Exchange
+    } else if context.token_listings.get(&address).copied().unwrap_or(0) > 1 {
+        TokenListing::MultiDex
+    } else {
+        TokenListing::SingleDex
+    };
+
+    TokenMaturity {
+        symbol: token.symbol.clone(),
+        listing,
+        age_days: context
+            .token_first_seen
+            .get(&address)
+            .map(|first_seen| (Utc::now() - *first_seen).num_hours() as f64 / 24.0),
+    }
+}
@@ -0,0 +1,163 @@
+use crate::application::dtos::backtest::{
+    DEFAULT_INNER_FRACTION, DEFAULT_MAX_WIDTH_PERCENT, DEFAULT_MIN_WIDTH_PERCENT,
+    DEFAULT_VOLATILITY_MULTIPLIER, DEFAULT_VOLATILITY_WINDOW, EquityCurvePoint, StrategyConfig,
+    StrategyResult, StrategySimulationRequest, StrategySimulationResponse, StrategySummary,
+};
+use crate::config::ChainConfig;
+use crate::infrastructure::data::require_data_provider;
+use crate::math::{
+    FixedRange, RebalanceStrategy, RecenterOnExit, SimulationParams, TrailingRange,
+    VolatilityScaledRange, simulate_strategy,
+};
+use anyhow::{Result, anyhow};
+use tracing::{info, warn};
+
+pub async fn simulate_strategies(
+    chain: &ChainConfig,
+    request: &StrategySimulationRequest,
+) -> Result<StrategySimulationResponse> {
+    let interval = request.interval.unwrap_or(60);
+    let limit = request.limit.unwrap_or(200);
+
+    info!(
+        "🧪 Starting strategy simulation for {}/{} (interval: {}min, limit: {})",
+        request.token0, request.token1, interval, limit
+    );
+
+    let data_provider = require_data_provider("sailor", chain)?;
+
+    let price_data = data_provider
+        .get_price_data(&request.token0, &request.token1, interval, limit)
+        .await?;
+
+    if price_data.is_empty() {
+        warn!(
+            "⚠️ No price data found for {}/{}",
+            request.token0, request.token1
+        );
+        return Err(anyhow!(
+            "No price data available for the specified token pair"
+        ));
+    }
+
+    let params = SimulationParams {
+        capital: request.capital,
+        fee_rate: request.fee_tier / 100.0,
+        pool_tvl: request.pool_tvl,
+        gas_cost: request.gas_cost.unwrap_or(0.0),
+        swap_cost_rate: request.swap_cost_percent.unwrap_or(request.fee_tier) / 100.0,
+    };
+
+    let configs = request
+        .strategies
+        .clone()
+        .unwrap_or_else(default_strategies);
+
+    let results: Vec<StrategyResult> = configs
+        .iter()
+        .map(|config| {
+            let strategy = build_strategy(config);
+            let result = simulate_strategy(&price_data, strategy.as_ref(), &params);
+
+            StrategyResult {
+                strategy: result.strategy,
+                summary: StrategySummary {
+                    final_equity: result.final_equity,
+                    hold_value: result.hold_value,
+                    fees_earned: result.fees_earned,
+                    rebalance_costs: result.rebalance_costs,
+                    rebalance_count: result.rebalance_count,
+                    time_in_range_percent: result.time_in_range * 100.0,
+                    pnl: result.pnl,
+                    pnl_percent: result.pnl_percent,
+                    max_drawdown_percent: result.max_drawdown * 100.0,
+                },
+                equity_curve: result
+                    .equity_curve
+                    .into_iter()
+                    .map(|p| EquityCurvePoint {
+                        timestamp: p.timestamp,
+                        price: p.price,
+                        equity: p.equity,
+                        hold_value: p.hold_value,
+                        lower_price: p.lower_price,
+                        upper_price: p.upper_price,
+                        in_range: p.in_range,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let best_strategy = results
+        .iter()
+        .max_by(|a, b| a.summary.pnl.total_cmp(&b.summary.pnl))
+        .map(|r| r.strategy.clone());
+
+    info!(
+        "✅ Simulated {} strategies for {}/{} over {} candles",
+        results.len(),
+        request.token0,
+        request.token1,
+        price_data.len()
+    );
+
+    Ok(StrategySimulationResponse {
+        pair: format!("{}/{}", request.token0, request.token1),
+        interval_minutes: interval,
+        data_points: price_data.len(),
+        capital: request.capital,
+        results,
+        best_strategy,
+    })
+}
+
+pub fn build_strategy(config: &StrategyConfig) -> Box<dyn RebalanceStrategy> {
+    match config {
+        StrategyConfig::Fixed { width_percent } => Box::new(FixedRange {
+            width_percent: *width_percent,
+        }),
+        StrategyConfig::RecenterOnExit { width_percent } => Box::new(RecenterOnExit {
+            width_percent: *width_percent,
+        }),
+        StrategyConfig::Trailing {
+            width_percent,
+            inner_fraction,
+        } => Box::new(TrailingRange {
+            width_percent: *width_percent,
+            inner_fraction: inner_fraction.unwrap_or(DEFAULT_INNER_FRACTION),
+        }),
+        StrategyConfig::VolatilityScaled {
+            window,
+            multiplier,
+            min_width_percent,
+            max_width_percent,
+        } => Box::new(VolatilityScaledRange {
+            window: window.unwrap_or(DEFAULT_VOLATILITY_WINDOW),
+            multiplier: multiplier.unwrap_or(DEFAULT_VOLATILITY_MULTIPLIER),
+            min_width_percent: min_width_percent.unwrap_or(DEFAULT_MIN_WIDTH_PERCENT),
+            max_width_percent: max_width_percent.unwrap_or(DEFAULT_MAX_WIDTH_PERCENT),
+        }),
+    }
+}
+
+fn default_strategies() -> Vec<StrategyConfig> {
+    vec![
+        StrategyConfig::Fixed {
+            width_percent: 10.0,
+        },
+        StrategyConfig::RecenterOnExit {
+            width_percent: 10.0,
+        },
+        StrategyConfig::Trailing {
+            width_percent: 10.0,
+            inner_fraction: None,
+        },
+        StrategyConfig::VolatilityScaled {
+            window: None,
+            multiplier: None,
+            min_width_percent: None,
+            max_width_percent: None,
+        },
+    ]
+}
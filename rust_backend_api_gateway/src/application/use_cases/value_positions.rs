@@ -0,0 +1,152 @@
+use crate::application::dtos::position::PositionResponse;
+use crate::application::dtos::token_price::TokenPriceResponse;
+use crate::application::service::price_oracle::PriceOracle;
+use crate::config::Chains;
+use crate::domain::services::position::Position;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Positions with their deposited amounts valued at the oracle's current USD prices, pricing
+/// each token once
+pub async fn value_positions(
+    oracle: &PriceOracle,
+    chains: &Chains,
+    positions: Vec<Position>,
+) -> Vec<PositionResponse> {
+    let mut prices: HashMap<(i64, String), Option<TokenPriceResponse>> = HashMap::new();
+    for position in &positions {
+        for address in [&position.token0_address, &position.token1_address]
+            .into_iter()
+            .flatten()
+        {
+            let key = (position.chain_id, address.to_lowercase());
+            if prices.contains_key(&key) {
+                continue;
+            }
+            let price = token_price(oracle, chains, position.chain_id, address).await;
+            prices.insert(key, price);
+        }
+    }
+
+    positions
+        .into_iter()
+        .map(|position| {
+            let price = |address: &Option<String>| {
+                let key = (position.chain_id, address.as_deref()?.to_lowercase());
+                prices.get(&key)?.as_ref()
+            };
+            let deposit_value_usd = deposit_value_usd(
+                &position,
+                price(&position.token0_address),
+                price(&position.token1_address),
+            );
+            PositionResponse {
+                position,
+                deposit_value_usd,
+            }
+        })
+        .collect()
+}
+
+async fn token_price(
+    oracle: &PriceOracle,
+    chains: &Chains,
+    chain_id: i64,
+    address: &str,
+) -> Option<TokenPriceResponse> {
+    let chain = chains.find_by_id(chain_id as u64)?;
+    oracle
+        .token_price(chain, address)
+        .await
+        .inspect_err(|e| warn!("⚠️ Could not price position token {}: {}", address, e))
+        .ok()
+        .flatten()
+}
+
+/// USD value of both deposited raw amounts, `None` unless both tokens have a price and decimals
+fn deposit_value_usd(
+    position: &Position,
+    price0: Option<&TokenPriceResponse>,
+    price1: Option<&TokenPriceResponse>,
+) -> Option<f64> {
+    let value = |amount: &Option<String>, price: Option<&TokenPriceResponse>| {
+        let price = price?;
+        let amount: f64 = amount.as_deref()?.parse().ok()?;
+        Some(amount / 10f64.powi(price.decimals? as i32) * price.usd_price)
+    };
+
+    Some(value(&position.amount0, price0)? + value(&position.amount1, price1)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn position(amount0: &str, amount1: &str) -> Position {
+        Position {
+            id: 1,
+            chain_id: 1329,
+            pb_key: "0xabc".to_string(),
+            pool_address: Some("0xpool".to_string()),
+            protocol: None,
+            token0_address: Some("0xsei".to_string()),
+            token1_address: Some("0xusdc".to_string()),
+            tick_lower: Some(-600),
+            tick_upper: Some(600),
+            liquidity: Some("1000".to_string()),
+            amount0: Some(amount0.to_string()),
+            amount1: Some(amount1.to_string()),
+            nft_token_id: None,
+            tx_hash: None,
+            price_lower: None,
+            price_upper: None,
+            value_usd: None,
+            legacy_trans_id: None,
+            status: "open".to_string(),
+            created_at: Utc::now(),
+            closed_at: None,
+            block_number: None,
+            log_index: None,
+        }
+    }
+
+    fn price(usd_price: f64, decimals: Option<u8>) -> TokenPriceResponse {
+        TokenPriceResponse {
+            chain_id: 1329,
+            address: "0xtoken".to_string(),
+            symbol: "TOKEN".to_string(),
+            decimals,
+            usd_price,
+            confidence: 1.0,
+            as_of: Utc::now(),
+            stale: false,
+            sources: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn values_both_deposited_amounts() {
+        let value = deposit_value_usd(
+            &position("2000000000000000000", "250000"),
+            Some(&price(0.3, Some(18))),
+            Some(&price(1.0, Some(6))),
+        )
+        .unwrap();
+
+        assert!((value - 0.85).abs() < 1e-12);
+    }
+
+    #[test]
+    fn leaves_positions_it_cannot_fully_price_unvalued() {
+        let position = position("2000000000000000000", "250000");
+        let sei = price(0.3, Some(18));
+
+        assert!(deposit_value_usd(&position, Some(&sei), None).is_none());
+        assert!(deposit_value_usd(&position, Some(&sei), Some(&price(1.0, None))).is_none());
+
+        let mut legacy = position;
+        legacy.amount1 = None;
+        assert!(deposit_value_usd(&legacy, Some(&sei), Some(&sei)).is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use std::env;
+use tracing::warn;
 
 pub fn jwt_secret() -> String {
     env::var("JWT_SECRET").unwrap_or_else(|_| "dev-secret".to_string())
@@ -22,3 +23,344 @@ pub fn sailor_api_base_url() -> String {
     env::var("SAILOR_API_BASE_URL")
         .unwrap_or_else(|_| "https://asia-southeast1-ktx-finance-2.cloudfunctions.net".to_string())
 }
+
+/// Spread between venues, in percent, above which a divergence is recorded
+pub fn divergence_threshold_percent() -> f64 {
+    env::var("DIVERGENCE_THRESHOLD_PERCENT")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0.5)
+}
+
+pub fn divergence_poll_interval_secs() -> u64 {
+    env::var("DIVERGENCE_POLL_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60)
+}
+
+/// Pools below this TVL are too thin to arbitrage and are left out of the monitor
+pub fn divergence_min_tvl() -> f64 {
+    env::var("DIVERGENCE_MIN_TVL")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10_000.0)
+}
+
+/// Days closed divergence episodes are kept before the monitor prunes them
+pub fn divergence_retention_days() -> i64 {
+    env::var("DIVERGENCE_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30)
+}
+
+/// How often every pool and token price is snapshotted into Postgres
+pub fn snapshot_interval_secs() -> u64 {
+    env::var("SNAPSHOT_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3600)
+}
+
+/// Age after which a token price quote is reported as stale
+pub fn price_max_age_secs() -> i64 {
+    env::var("PRICE_MAX_AGE_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(300)
+}
+
+/// How often each subscribed `/stream` topic is polled upstream
+pub fn stream_poll_interval_secs() -> u64 {
+    env::var("STREAM_POLL_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10)
+        .max(1)
+}
+
+/// How often idle `/stream` connections are pinged; WebSocket clients silent for two
+/// heartbeats are disconnected
+pub fn stream_heartbeat_secs() -> u64 {
+    env::var("STREAM_HEARTBEAT_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(15)
+        .max(1)
+}
+
+/// Updates buffered per topic for each client; slower clients skip ahead to the latest
+pub fn stream_client_buffer() -> usize {
+    env::var("STREAM_CLIENT_BUFFER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(16)
+        .max(1)
+}
+
+/// Topics a single `/stream` connection may subscribe to
+pub fn stream_max_topics() -> usize {
+    env::var("STREAM_MAX_TOPICS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(20)
+}
+
+/// How providers reach their upstream HTTP APIs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamMode {
+    /// Call the upstream APIs
+    Live,
+    /// Call the upstream APIs and save every response as a fixture
+    Record,
+    /// Serve saved fixtures and never touch the network
+    Replay,
+}
+
+/// From UPSTREAM_MODE: `live` (default), `record` or `replay`
+pub fn upstream_mode() -> UpstreamMode {
+    match env::var("UPSTREAM_MODE")
+        .unwrap_or_default()
+        .to_lowercase()
+        .as_str()
+    {
+        "" | "live" => UpstreamMode::Live,
+        "record" => UpstreamMode::Record,
+        "replay" => UpstreamMode::Replay,
+        other => {
+            warn!(
+                "⚠️ Unknown UPSTREAM_MODE {}, calling upstream APIs live",
+                other
+            );
+            UpstreamMode::Live
+        }
+    }
+}
+
+/// Where upstream responses are recorded to and replayed from
+pub fn upstream_fixtures_dir() -> String {
+    env::var("UPSTREAM_FIXTURES_DIR").unwrap_or_else(|_| "tests/fixtures/upstream".to_string())
+}
+
+/// Sei EVM chain ids
+pub const SEI_CHAIN_ID: u64 = 1329;
+pub const SEI_TESTNET_CHAIN_ID: u64 = 1328;
+
+/// Endpoints the providers use on one EVM chain
+#[derive(Debug, Clone)]
+pub struct ChainConfig {
+    pub id: u64,
+    /// Name clients pass as `chain`, e.g. `sei`
+    pub name: String,
+    /// `None` when DragonSwap is not configured on the chain
+    pub dragonswap_api_base_url: Option<String>,
+    pub sailor_api_base_url: Option<String>,
+    /// Value of the `chain` field Sailor tags the chain's pools with
+    pub sailor_chain: String,
+    /// JSON-RPC endpoint of the EVM node the on-chain provider reads pools from
+    pub onchain_rpc_url: String,
+    /// Multicall3 deployment calls are batched through
+    pub multicall3_address: String,
+    /// V3 pools the on-chain provider lists
+    pub onchain_pool_addresses: Vec<String>,
+    /// DEXes read from a standard Uniswap V3 subgraph
+    pub subgraph_dexes: Vec<SubgraphDexConfig>,
+    /// LiquidityManager deployment whose events are indexed into positions, `None` to not index
+    /// the chain
+    pub liquidity_manager_address: Option<String>,
+    /// Block the indexer starts from on its first run, usually the deployment block
+    pub liquidity_manager_start_block: u64,
+}
+
+/// A DEX served by a Uniswap V3 subgraph, added through config alone
+#[derive(Debug, Clone)]
+pub struct SubgraphDexConfig {
+    /// Provider name, e.g. `oku`
+    pub name: String,
+    /// Protocol reported on the DEX's pools
+    pub protocol: String,
+    /// GraphQL endpoint of the subgraph
+    pub url: String,
+}
+
+/// Chains the gateway serves, read from the environment once at startup and shared through
+/// `AppState`; never empty, and the first one is the default chain
+#[derive(Debug, Clone)]
+pub struct Chains(Vec<ChainConfig>);
+
+impl Chains {
+    /// Chains from the comma-separated CHAINS (default `sei`). Each chain reads its settings from
+    /// variables prefixed with its upper-cased name, e.g. `SEI_TESTNET_RPC_URL`, and the default
+    /// chain falls back to the unprefixed ones.
+    pub fn from_env() -> Self {
+        Self(chains_from_env())
+    }
+
+    pub fn default_chain(&self) -> &ChainConfig {
+        &self.0[0]
+    }
+
+    /// Look a configured chain up by name or numeric chain id
+    pub fn find(&self, chain: &str) -> Option<&ChainConfig> {
+        let chain = chain.trim();
+        self.0.iter().find(|config| {
+            config.name.eq_ignore_ascii_case(chain) || config.id.to_string() == chain
+        })
+    }
+
+    pub fn find_by_id(&self, chain_id: u64) -> Option<&ChainConfig> {
+        self.0.iter().find(|config| config.id == chain_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ChainConfig> {
+        self.0.iter()
+    }
+}
+
+fn chains_from_env() -> Vec<ChainConfig> {
+    let names: Vec<String> = env::var("CHAINS")
+        .unwrap_or_else(|_| "sei".to_string())
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let chains: Vec<ChainConfig> = names
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| chain_from_env(name, i == 0))
+        .collect();
+    if chains.is_empty() {
+        return vec![chain_from_env("sei", true).expect("sei is a built-in chain")];
+    }
+    chains
+}
+
+fn chain_from_env(name: &str, is_default: bool) -> Option<ChainConfig> {
+    let prefix = name.to_uppercase().replace('-', "_");
+    let prefixed = |key: &str| {
+        env::var(format!("{}_{}", prefix, key))
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+    };
+    let var = |key: &str| {
+        prefixed(key).or_else(|| {
+            is_default
+                .then(|| env::var(key).ok())
+                .flatten()
+                .filter(|value| !value.trim().is_empty())
+        })
+    };
+
+    // Mainnet keeps the provider defaults; other chains only get providers they configure
+    let (builtin_id, builtin_rpc, dragonswap, sailor) = match name {
+        "sei" => (
+            Some(SEI_CHAIN_ID),
+            Some("https://evm-rpc.sei-apis.com"),
+            Some(dragonswap_api_base_url()),
+            Some(sailor_api_base_url()),
+        ),
+        "sei-testnet" => (
+            Some(SEI_TESTNET_CHAIN_ID),
+            Some("https://evm-rpc-testnet.sei-apis.com"),
+            None,
+            None,
+        ),
+        _ => (None, None, None, None),
+    };
+
+    let Some(id) = var("CHAIN_ID")
+        .and_then(|id| id.parse().ok())
+        .or(builtin_id)
+    else {
+        warn!(
+            "⚠️ Chain {} has no {}_CHAIN_ID and is ignored",
+            name, prefix
+        );
+        return None;
+    };
+    let Some(onchain_rpc_url) = prefixed("RPC_URL")
+        .or_else(|| var("ONCHAIN_RPC_URL"))
+        .or(builtin_rpc.map(str::to_string))
+    else {
+        warn!("⚠️ Chain {} has no {}_RPC_URL and is ignored", name, prefix);
+        return None;
+    };
+
+    Some(ChainConfig {
+        id,
+        name: name.to_string(),
+        dragonswap_api_base_url: var("DRAGONSWAP_API_BASE_URL").or(dragonswap),
+        sailor_api_base_url: var("SAILOR_API_BASE_URL").or(sailor),
+        sailor_chain: var("SAILOR_CHAIN").unwrap_or_else(|| name.to_string()),
+        onchain_rpc_url,
+        multicall3_address: var("MULTICALL3_ADDRESS")
+            .unwrap_or_else(|| "0xcA11bde05977b3631167028862bE2a173976CA11".to_string()),
+        onchain_pool_addresses: var("ONCHAIN_POOL_ADDRESSES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .map(str::to_string)
+            .collect(),
+        subgraph_dexes: var("SUBGRAPH_DEXES")
+            .unwrap_or_default()
+            .split(',')
+            .map(|dex| dex.trim().to_lowercase())
+            .filter(|dex| !dex.is_empty())
+            .filter_map(|dex| {
+                let key = format!("SUBGRAPH_{}", dex.to_uppercase().replace('-', "_"));
+                let Some(url) = var(&format!("{}_URL", key)) else {
+                    warn!(
+                        "⚠️ Subgraph DEX {} on {} has no {}_URL and is ignored",
+                        dex, name, key
+                    );
+                    return None;
+                };
+                Some(SubgraphDexConfig {
+                    protocol: var(&format!("{}_PROTOCOL", key)).unwrap_or_else(|| dex.clone()),
+                    name: dex,
+                    url,
+                })
+            })
+            .collect(),
+        liquidity_manager_address: var("LIQUIDITY_MANAGER_ADDRESS"),
+        liquidity_manager_start_block: var("LIQUIDITY_MANAGER_START_BLOCK")
+            .and_then(|block| block.parse().ok())
+            .unwrap_or(0),
+    })
+}
+
+/// Tick bitmap words read on each side of the current tick when loading tick liquidity
+pub fn onchain_tick_word_radius() -> i16 {
+    env::var("ONCHAIN_TICK_WORD_RADIUS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(20)
+}
+
+/// Blocks the position indexer stays behind the head, so logs it applies are not reorged away
+pub fn indexer_confirmations() -> u64 {
+    env::var("INDEXER_CONFIRMATIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5)
+}
+
+pub fn indexer_poll_interval_secs() -> u64 {
+    env::var("INDEXER_POLL_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5)
+        .max(1)
+}
+
+/// Blocks per `eth_getLogs` request, within the range limits of public RPCs
+pub fn indexer_block_range() -> u64 {
+    env::var("INDEXER_BLOCK_RANGE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(2000)
+        .max(1)
+}
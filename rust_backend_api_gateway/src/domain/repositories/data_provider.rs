@@ -3,7 +3,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 
 #[async_trait]
-pub trait DataProvider {
+pub trait DataProvider: Send + Sync {
     async fn get_price_data(
         &self,
         token0: &str,
@@ -1,3 +1,3 @@
 pub trait JwtEncoder {
-    fn encode(&self, subject: &str) -> Result<String, String>;
+    fn encode(&self, subject: &str, chain_id: u64) -> Result<String, String>;
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod data_provider;
 pub mod dex_provider;
 pub mod jwt;
+pub mod position_repository;
 pub mod wallet;
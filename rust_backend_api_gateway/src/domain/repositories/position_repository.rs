@@ -0,0 +1,31 @@
+use crate::domain::services::position::{LiquidityEvent, NewPosition, Position};
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+#[async_trait]
+pub trait PositionRepository: Send + Sync {
+    /// Store a position, registering its wallet on first use
+    async fn add(&self, position: NewPosition) -> Result<Position, DbErr>;
+
+    /// `DbErr::RecordNotFound` when the wallet has no position with that id
+    async fn delete(&self, pb_key: &str, id: i64) -> Result<(), DbErr>;
+
+    /// Newest first
+    async fn list_for_wallet(&self, pb_key: &str) -> Result<Vec<Position>, DbErr>;
+
+    /// Last block of `contract` the indexer applied, `None` before its first run
+    async fn indexed_block(&self, chain_id: u64, contract: &str) -> Result<Option<u64>, DbErr>;
+
+    /// Apply the events in order and record `to_block` as indexed, all or nothing. A mint
+    /// completes the position recorded for the same transaction and range, or inserts one for
+    /// the recipient; a burn takes its liquidity from the open positions in the pool and range
+    /// that are not NFPM NFTs, the burner's own first and then oldest first, closing those it
+    /// empties.
+    async fn apply_liquidity_events(
+        &self,
+        chain_id: u64,
+        contract: &str,
+        events: &[LiquidityEvent],
+        to_block: u64,
+    ) -> Result<(), DbErr>;
+}
@@ -2,5 +2,6 @@ use crate::application::dtos::auth::AuthRequest;
 use actix_web::HttpResponse;
 
 pub trait WalletVerifier {
+    #[allow(clippy::result_large_err)]
     fn verify(&self, data: &AuthRequest) -> Result<(), HttpResponse>;
 }
@@ -1,4 +1,9 @@
-use serde::{Deserialize, Serialize};
+use crate::domain::services::fee_tier::FeeTier;
+use crate::domain::services::pool_risk::PoolRiskScore;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 
 #[derive(Deserialize, Debug)]
 pub struct DragonSwapTick {
@@ -38,16 +43,84 @@ pub struct KlineResponse {
     pub data: Vec<KlineData>,
 }
 
+/// Pool as reported by any DEX provider (v2: typed fee tier, integer decimals, decimal USD amounts)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UnifiedPool {
+    /// EVM chain id of the chain the pool is deployed on
+    pub chain_id: u64,
     pub id: String,
     pub protocol: String,
     pub token0: Token,
     pub token1: Token,
-    pub tvl: Option<f64>,
-    pub daily_volume: Option<f64>,
+    /// USD
+    pub tvl: Option<Decimal>,
+    /// USD
+    pub daily_volume: Option<Decimal>,
     pub apr: Option<f64>,
-    pub fee_tier: String,
+    /// `None` when the provider does not report a usable fee
+    pub fee_tier: Option<FeeTier>,
+    /// Token1 per token0, from the USD prices the provider reports for both tokens
+    pub price: Option<f64>,
+    /// Filled in by the gateway, never by providers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<PoolRiskScore>,
+}
+
+impl UnifiedPool {
+    pub fn tvl_f64(&self) -> Option<f64> {
+        self.tvl.and_then(|tvl| tvl.to_f64())
+    }
+
+    pub fn daily_volume_f64(&self) -> Option<f64> {
+        self.daily_volume.and_then(|volume| volume.to_f64())
+    }
+
+    pub fn fee_tier_percent(&self) -> Option<f64> {
+        self.fee_tier.map(|fee_tier| fee_tier.percent())
+    }
+}
+
+/// Serializes a pool in the pre-v2 shape (fee tier as a percent string, decimals as strings,
+/// float amounts) for clients that have not moved to v2 yet
+pub struct LegacyUnifiedPool<'a>(pub &'a UnifiedPool);
+
+impl Serialize for LegacyUnifiedPool<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let pool = self.0;
+        let mut state = serializer.serialize_struct("UnifiedPool", 8)?;
+        state.serialize_field("id", &pool.id)?;
+        state.serialize_field("protocol", &pool.protocol)?;
+        state.serialize_field("token0", &LegacyToken(&pool.token0))?;
+        state.serialize_field("token1", &LegacyToken(&pool.token1))?;
+        state.serialize_field("tvl", &pool.tvl_f64())?;
+        state.serialize_field("daily_volume", &pool.daily_volume_f64())?;
+        state.serialize_field("apr", &pool.apr)?;
+        state.serialize_field(
+            "fee_tier",
+            &pool
+                .fee_tier_percent()
+                .map_or_else(|| "N/A".to_string(), |percent| percent.to_string()),
+        )?;
+        state.end()
+    }
+}
+
+struct LegacyToken<'a>(&'a Token);
+
+impl Serialize for LegacyToken<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let token = self.0;
+        let mut state = serializer.serialize_struct("Token", 3)?;
+        state.serialize_field("address", &token.address)?;
+        state.serialize_field("symbol", &token.symbol)?;
+        state.serialize_field(
+            "decimals",
+            &token
+                .decimals
+                .map_or_else(|| "0".to_string(), |d| d.to_string()),
+        )?;
+        state.end()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -142,6 +215,116 @@ pub struct SailorPoolListResponse {
     pub pool_stats: Vec<SailorPoolStats>,
 }
 
+/// Envelope of a GraphQL response; `errors` may accompany partial `data`
+#[derive(Debug, Deserialize)]
+pub struct GraphQlResponse<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphQlError {
+    pub message: String,
+}
+
+/// Uniswap V3 subgraph token; BigDecimal and BigInt fields arrive as strings
+#[derive(Debug, Deserialize)]
+pub struct SubgraphToken {
+    pub id: String,
+    pub symbol: String,
+    pub decimals: String,
+    #[serde(rename = "derivedETH")]
+    pub derived_eth: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphPoolDay {
+    #[serde(rename = "volumeUSD")]
+    pub volume_usd: String,
+    #[serde(rename = "feesUSD")]
+    pub fees_usd: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubgraphPool {
+    pub id: String,
+    pub fee_tier: String,
+    pub token0: SubgraphToken,
+    pub token1: SubgraphToken,
+    /// Token1 per token0
+    pub token1_price: String,
+    #[serde(rename = "totalValueLockedUSD")]
+    pub total_value_locked_usd: String,
+    /// Most recent day first
+    #[serde(default)]
+    pub pool_day_data: Vec<SubgraphPoolDay>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphPoolsData {
+    pub pools: Vec<SubgraphPool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphBundle {
+    #[serde(rename = "ethPriceUSD")]
+    pub eth_price_usd: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphBundleData {
+    pub bundle: Option<SubgraphBundle>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubgraphTick {
+    pub id: String,
+    pub tick_idx: String,
+    pub liquidity_net: String,
+    pub price0: String,
+    pub price1: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphTicksData {
+    pub ticks: Vec<SubgraphTick>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphPoolRef {
+    pub id: String,
+    #[serde(rename = "totalValueLockedUSD")]
+    pub total_value_locked_usd: String,
+}
+
+/// Deepest pool of a pair in each token order
+#[derive(Debug, Deserialize)]
+pub struct SubgraphPairPoolsData {
+    pub direct: Vec<SubgraphPoolRef>,
+    pub inverse: Vec<SubgraphPoolRef>,
+}
+
+/// A `poolHourData` or `poolDayData` entity, aliased to a common shape by the query. Prices are
+/// token0 per token1.
+#[derive(Debug, Deserialize)]
+pub struct SubgraphCandle {
+    pub start: i64,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    #[serde(rename = "volumeUSD")]
+    pub volume_usd: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphCandlesData {
+    pub candles: Vec<SubgraphCandle>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ActiveLiquidity {
     pub tick: String,
@@ -149,6 +332,17 @@ pub struct ActiveLiquidity {
     pub liquidity: String,
 }
 
+/// What the `liquidity_net` of a provider's ticks holds
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TickDataShape {
+    /// Uniswap `liquidityNet` deltas, accumulated from the lowest tick
+    #[default]
+    LiquidityNet,
+    /// Liquidity active from each tick up to the next one
+    ActiveLiquidity,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct ActiveLiquidityResponse {
     #[serde(default)]
@@ -157,11 +351,79 @@ pub struct ActiveLiquidityResponse {
     pub active_liquidity: Vec<ActiveLiquidity>,
     #[serde(default)]
     pub data: Vec<LiquidityTick>,
+    /// Set by the provider that read `data`
+    #[serde(default)]
+    pub tick_shape: TickDataShape,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Token {
+    pub chain_id: u64,
     pub address: String,
     pub symbol: String,
-    pub decimals: String,
+    /// `None` when the provider does not know the token
+    pub decimals: Option<u8>,
+    /// As reported by the provider listing the pool
+    pub usd_price: Option<f64>,
+}
+
+impl Token {
+    /// Decimals for amount and tick math; unknown tokens are assumed to use 18
+    pub fn decimals_or_default(&self) -> u32 {
+        self.decimals.unwrap_or(18) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn legacy_serializer_keeps_the_v1_shape() {
+        let pool = UnifiedPool {
+            chain_id: 1329,
+            id: "0xpool".to_string(),
+            protocol: "Sailor".to_string(),
+            token0: Token {
+                chain_id: 1329,
+                address: "0xa".to_string(),
+                symbol: "SEI".to_string(),
+                decimals: Some(18),
+                usd_price: Some(0.5),
+            },
+            token1: Token {
+                chain_id: 1329,
+                address: "0xb".to_string(),
+                symbol: "USDC".to_string(),
+                decimals: None,
+                usd_price: None,
+            },
+            tvl: Some(Decimal::from_str("12500.5").unwrap()),
+            daily_volume: None,
+            apr: Some(12.0),
+            fee_tier: Some(FeeTier::from_hundredths_bip(3000)),
+            price: None,
+            risk: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(LegacyUnifiedPool(&pool)).unwrap(),
+            serde_json::json!({
+                "id": "0xpool",
+                "protocol": "Sailor",
+                "token0": { "address": "0xa", "symbol": "SEI", "decimals": "18" },
+                "token1": { "address": "0xb", "symbol": "USDC", "decimals": "0" },
+                "tvl": 12500.5,
+                "daily_volume": null,
+                "apr": 12.0,
+                "fee_tier": "0.3"
+            })
+        );
+
+        let v2 = serde_json::to_value(&pool).unwrap();
+        assert_eq!(v2["tvl"], "12500.5");
+        assert_eq!(v2["fee_tier"]["hundredths_bip"], 3000);
+        assert_eq!(v2["token0"]["decimals"], 18);
+    }
 }
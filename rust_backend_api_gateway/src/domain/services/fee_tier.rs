@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Pool swap fee in hundredths of a basis point, the unit Uniswap v3 pools store (3000 = 0.3%)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "FeeTierJson", from = "FeeTierJson")]
+pub struct FeeTier(u32);
+
+impl FeeTier {
+    pub const fn from_hundredths_bip(fee: u32) -> Self {
+        Self(fee)
+    }
+
+    /// Fee tier from a percentage such as 0.3; `None` for zero, negative or non-finite input
+    pub fn from_percent(percent: f64) -> Option<Self> {
+        let fee = (percent * 10_000.0).round();
+        (percent.is_finite() && fee >= 1.0 && fee <= u32::MAX as f64).then_some(Self(fee as u32))
+    }
+
+    pub const fn hundredths_bip(&self) -> u32 {
+        self.0
+    }
+
+    pub fn percent(&self) -> f64 {
+        self.0 as f64 / 10_000.0
+    }
+
+    /// Tick spacing the factory assigns this fee; non-standard tiers follow the 1 tick per 50 units rule
+    pub fn tick_spacing(&self) -> i32 {
+        match self.0 {
+            100 => 1,
+            500 => 10,
+            3000 => 60,
+            10000 => 200,
+            fee => (fee / 50).max(1) as i32,
+        }
+    }
+}
+
+impl fmt::Display for FeeTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.percent())
+    }
+}
+
+/// Wire shape of a fee tier; `percent` and `tick_spacing` are derived and ignored when read back
+#[derive(Serialize, Deserialize)]
+struct FeeTierJson {
+    hundredths_bip: u32,
+    #[serde(default)]
+    percent: f64,
+    #[serde(default)]
+    tick_spacing: i32,
+}
+
+impl From<FeeTier> for FeeTierJson {
+    fn from(fee_tier: FeeTier) -> Self {
+        Self {
+            hundredths_bip: fee_tier.hundredths_bip(),
+            percent: fee_tier.percent(),
+            tick_spacing: fee_tier.tick_spacing(),
+        }
+    }
+}
+
+impl From<FeeTierJson> for FeeTier {
+    fn from(json: FeeTierJson) -> Self {
+        Self(json.hundredths_bip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_between_percent_and_hundredths_of_a_bip() {
+        let fee_tier = FeeTier::from_percent(0.3).unwrap();
+        assert_eq!(fee_tier, FeeTier::from_hundredths_bip(3000));
+        assert_eq!(fee_tier.percent(), 0.3);
+        assert_eq!(FeeTier::from_percent(0.01).unwrap().hundredths_bip(), 100);
+        assert_eq!(FeeTier::from_percent(0.0), None);
+        assert_eq!(FeeTier::from_percent(f64::NAN), None);
+    }
+
+    #[test]
+    fn derives_tick_spacing() {
+        assert_eq!(FeeTier::from_hundredths_bip(100).tick_spacing(), 1);
+        assert_eq!(FeeTier::from_hundredths_bip(500).tick_spacing(), 10);
+        assert_eq!(FeeTier::from_hundredths_bip(3000).tick_spacing(), 60);
+        assert_eq!(FeeTier::from_hundredths_bip(10000).tick_spacing(), 200);
+        assert_eq!(FeeTier::from_hundredths_bip(2500).tick_spacing(), 50);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let json = serde_json::to_value(FeeTier::from_hundredths_bip(500)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "hundredths_bip": 500, "percent": 0.05, "tick_spacing": 10 })
+        );
+        let parsed: FeeTier = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.hundredths_bip(), 500);
+    }
+}
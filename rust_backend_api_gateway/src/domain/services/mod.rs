@@ -1 +1,4 @@
 pub mod data;
+pub mod fee_tier;
+pub mod pool_risk;
+pub mod position;
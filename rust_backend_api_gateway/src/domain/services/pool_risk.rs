@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Overall risk of providing liquidity to a pool, 0 (safest) to 100 (riskiest)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolRiskScore {
+    pub score: f64,
+    /// LOW, MEDIUM or HIGH
+    pub level: String,
+    /// Share of the factor weights that had data; the score is rescaled over those factors
+    pub coverage: f64,
+    pub factors: Vec<RiskFactor>,
+}
+
+/// One input to the score and how much it moved it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFactor {
+    pub name: String,
+    pub weight: f64,
+    /// Measured value the factor is based on, in the unit named by the explanation
+    pub value: Option<f64>,
+    /// 0 (no risk) to 1 (maximum risk); `None` when there was no data
+    pub risk: Option<f64>,
+    /// Points this factor added to `score`
+    pub contribution: f64,
+    pub explanation: String,
+}
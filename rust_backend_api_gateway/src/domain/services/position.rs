@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Status of a position whose liquidity is still in the pool
+pub const POSITION_OPEN: &str = "open";
+/// Status of a position whose liquidity was fully withdrawn
+pub const POSITION_CLOSED: &str = "closed";
+
+/// A wallet's liquidity position as stored
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Position {
+    pub id: i64,
+    pub chain_id: i64,
+    pub pb_key: String,
+    /// `None` for positions recorded before schema v2
+    pub pool_address: Option<String>,
+    pub protocol: Option<String>,
+    pub token0_address: Option<String>,
+    pub token1_address: Option<String>,
+    pub tick_lower: Option<i32>,
+    pub tick_upper: Option<i32>,
+    /// Decimal strings; uint128 and uint256 values do not fit any primitive
+    pub liquidity: Option<String>,
+    pub amount0: Option<String>,
+    pub amount1: Option<String>,
+    pub nft_token_id: Option<String>,
+    pub tx_hash: Option<String>,
+    /// Price range and USD value of positions recorded before schema v2
+    pub price_lower: Option<f64>,
+    pub price_upper: Option<f64>,
+    pub value_usd: Option<f64>,
+    pub legacy_trans_id: Option<i32>,
+    /// `open` or `closed`
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    /// Block and log of the `LiquidityMinted` event, once the indexer has seen it
+    pub block_number: Option<i64>,
+    pub log_index: Option<i64>,
+}
+
+/// A position opened on-chain, before it is stored
+#[derive(Debug, Clone)]
+pub struct NewPosition {
+    pub chain_id: u64,
+    pub pb_key: String,
+    /// Addresses and the tx hash are lower-cased
+    pub pool_address: String,
+    pub protocol: Option<String>,
+    pub token0_address: String,
+    pub token1_address: String,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: String,
+    pub amount0: String,
+    pub amount1: String,
+    pub nft_token_id: Option<String>,
+    pub tx_hash: String,
+}
+
+/// Which LiquidityManager event a [`LiquidityEvent`] was decoded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidityEventKind {
+    Minted,
+    Burned,
+}
+
+/// A `LiquidityMinted` or `LiquidityBurned` log of the LiquidityManager contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquidityEvent {
+    pub kind: LiquidityEventKind,
+    pub chain_id: u64,
+    /// Addresses and the tx hash are lower-cased
+    pub pool_address: String,
+    /// Recipient of a mint, or the wallet burning
+    pub owner: String,
+    /// Pool tokens, read for mints only
+    pub token0_address: Option<String>,
+    pub token1_address: Option<String>,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: String,
+    pub amount0: String,
+    pub amount1: String,
+    /// NonfungiblePositionManager token of a mint made through it. Its liquidity sits under the
+    /// NFT, so LiquidityManager burns never take from it.
+    pub nft_token_id: Option<String>,
+    pub tx_hash: String,
+    pub block_number: u64,
+    pub log_index: u64,
+    pub block_time: DateTime<Utc>,
+}
@@ -1,15 +1,15 @@
 use crate::application::dtos::price_history::PricePoint;
 use crate::config::binance_api_base_url;
 use crate::domain::repositories::data_provider::DataProvider;
+use crate::infrastructure::data::upstream_client::UpstreamClient;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use reqwest;
 use tracing::{debug, error, info};
 
 #[derive(Debug)]
 pub struct BinanceDataProvider {
     base_url: String,
-    client: reqwest::Client,
+    client: UpstreamClient,
 }
 
 impl Default for BinanceDataProvider {
@@ -43,7 +43,6 @@ impl DataProvider for BinanceDataProvider {
         let response = self
             .client
             .get(&url)
-            .send()
             .await
             .map_err(|e| anyhow!("Failed to send request to Binance: {}", e))?;
 
@@ -56,7 +55,6 @@ impl DataProvider for BinanceDataProvider {
 
         let json_response: serde_json::Value = response
             .json()
-            .await
             .map_err(|e| anyhow!("Failed to parse Binance JSON response: {}", e))?;
 
         debug!(
@@ -93,7 +91,7 @@ impl BinanceDataProvider {
         info!("🔗 Using API base URL from .env: {}", base_url);
         Self {
             base_url,
-            client: reqwest::Client::new(),
+            client: UpstreamClient::new(reqwest::Client::new()),
         }
     }
 
@@ -1,30 +1,32 @@
 use crate::domain::repositories::data_provider::DataProvider;
 use crate::domain::repositories::dex_provider::DexProvider;
-use futures::future::join_all;
 
 use crate::application::dtos::price_history::PricePoint;
-use crate::config::dragonswap_api_base_url;
+use crate::config::ChainConfig;
 use crate::domain::services::data::{ActiveLiquidityResponse, Token};
 use crate::domain::services::data::{
     DragonSwapPool, DragonSwapResponse, DragonSwapTicksResponse, DragonSwapToken, LiquidityTick,
-    UnifiedPool,
+    TickDataShape, UnifiedPool,
 };
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::upstream_client::UpstreamClient;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use reqwest;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
 use std::collections::HashMap;
-use tracing::info;
+use tracing::{info, warn};
+
+/// Ticks DragonSwap returns per `skip` page
+const TICK_PAGE_SIZE: usize = 1000;
+/// Bounds one read to 50k ticks
+const MAX_TICK_PAGES: usize = 50;
 
 #[derive(Debug)]
 pub struct DragonSwapDataProvider {
+    chain_id: u64,
     base_url: String,
-    client: reqwest::Client,
-}
-
-impl Default for DragonSwapDataProvider {
-    fn default() -> Self {
-        Self::new()
-    }
+    client: UpstreamClient,
 }
 
 // First implement DataProvider
@@ -32,10 +34,10 @@ impl Default for DragonSwapDataProvider {
 impl DataProvider for DragonSwapDataProvider {
     async fn get_price_data(
         &self,
-        token0: &str,
-        token1: &str,
-        interval: u32, // Interval in minutes
-        limit: u32,
+        _token0: &str,
+        _token1: &str,
+        _interval: u32, // Interval in minutes
+        _limit: u32,
     ) -> Result<Vec<PricePoint>> {
         Err(anyhow!("dragonswap does not support price data retrieval"))
     }
@@ -45,70 +47,93 @@ impl DataProvider for DragonSwapDataProvider {
 #[async_trait]
 impl DexProvider for DragonSwapDataProvider {
     async fn get_liquidity_data(&self, pool_address: &str) -> Result<ActiveLiquidityResponse> {
-        let url = format!(
-            "{}/graph/factory/ticks?pool_address={}&skip=0",
-            self.base_url, pool_address
-        );
-        info!("Fetching DragonSwap liquidity ticks from: {}", url);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await?
-            .json::<DragonSwapTicksResponse>()
-            .await?;
-        let ticks: Vec<LiquidityTick> = response
-            .data
-            .ticks
-            .into_iter()
-            .map(|ds_tick| LiquidityTick {
+        let mut ticks: Vec<LiquidityTick> = Vec::new();
+        let mut complete = false;
+        for _ in 0..MAX_TICK_PAGES {
+            let url = format!(
+                "{}/graph/factory/ticks?pool_address={}&skip={}",
+                self.base_url,
+                pool_address,
+                ticks.len()
+            );
+            info!("Fetching DragonSwap liquidity ticks from: {}", url);
+            let page = self
+                .client
+                .get(&url)
+                .await?
+                .json::<DragonSwapTicksResponse>()?
+                .data
+                .ticks;
+            let full = page.len() >= TICK_PAGE_SIZE;
+            ticks.extend(page.into_iter().map(|ds_tick| LiquidityTick {
                 tick_idx: ds_tick.tick_idx,
                 liquidity_net: ds_tick.liquidity_net,
                 price0: ds_tick.price0,
                 price1: ds_tick.price1,
-            })
-            .collect();
+            }));
+            if !full {
+                complete = true;
+                break;
+            }
+        }
+        if !complete {
+            warn!(
+                "⚠️ DragonSwap ticks of {} stopped after {} pages of {}",
+                pool_address, MAX_TICK_PAGES, TICK_PAGE_SIZE
+            );
+        }
+
         Ok(ActiveLiquidityResponse {
             data: ticks,
+            tick_shape: TickDataShape::LiquidityNet,
             ..Default::default()
         })
     }
 
     async fn get_pool_list(&self) -> Result<Vec<UnifiedPool>> {
         let url = format!("{}/pools", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).await?;
 
         if !response.status().is_success() {
-            return Ok(vec![]);
+            return Err(anyhow!(
+                "DragonSwap pool list request failed with status: {}",
+                response.status()
+            ));
         }
 
-        let dragonswap_data: DragonSwapResponse = response.json().await?;
+        let dragonswap_data: DragonSwapResponse = response.json()?;
 
-        let futures = dragonswap_data
+        // Resolve tokens from this response rather than refetching /pools for every token
+        let token_map = Self::token_map(dragonswap_data.tokens);
+
+        Ok(dragonswap_data
             .pools
             .into_iter()
             .filter(|pool| pool.pool_type == "V3_POOL")
-            .filter(|pool| pool.daily_volume.unwrap_or(0.0) > 1000.0)
-            .map(|pool| self.transform_dragonswap_pool(pool));
-
-        let results = join_all(futures).await;
-
-        Ok(results.into_iter().flatten().collect())
+            .map(|pool| self.transform_dragonswap_pool(pool, &token_map))
+            .collect())
     }
 }
 
 impl DragonSwapDataProvider {
-    pub fn new() -> Self {
-        let base_url = dragonswap_api_base_url();
+    /// `None` when DragonSwap is not configured on the chain
+    pub fn for_chain(chain: &ChainConfig) -> Option<Self> {
+        let base_url = chain.dragonswap_api_base_url.clone()?;
 
-        info!("🔗 Using API base URL from .env: {}", base_url);
-        Self {
+        info!(
+            "🔗 Using DragonSwap API base URL for {}: {}",
+            chain.name, base_url
+        );
+        Some(Self {
+            chain_id: chain.id,
             base_url,
-            client: reqwest::Client::builder()
-                .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
-                .build()
-                .expect("Failed to create reqwest client"),
-        }
+            client: UpstreamClient::new(
+                reqwest::Client::builder()
+                    .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
+                    .build()
+                    .expect("Failed to create reqwest client"),
+            ),
+        })
     }
 
     pub async fn is_dragonswap_pool(&self, pool_address: &str) -> Result<bool> {
@@ -116,76 +141,156 @@ impl DragonSwapDataProvider {
 
         info!("Checking DragonSwap for pool: {}", url);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).await?;
 
         info!("Response status: {}", response.status());
         Ok(response.status().is_success())
     }
 
     /// Converts a pool from the DragonSwap format to our unified format.
-    /// Returns an `Option` so we can easily skip pools if token data is missing.
-    async fn transform_dragonswap_pool(&self, pool: DragonSwapPool) -> Option<UnifiedPool> {
-        let token0 = self
-            .transform_token_address_to_symbol(&pool.token0_address)
-            .await
-            .ok()?;
-
-        let token1 = self
-            .transform_token_address_to_symbol(&pool.token1_address)
-            .await
-            .ok()?;
-
-        Some(UnifiedPool {
+    /// Tokens missing from `token_map` are reported as "Unknown".
+    fn transform_dragonswap_pool(
+        &self,
+        pool: DragonSwapPool,
+        token_map: &HashMap<String, DragonSwapToken>,
+    ) -> UnifiedPool {
+        let token0 = self.lookup_token(token_map, &pool.token0_address);
+        let token1 = self.lookup_token(token_map, &pool.token1_address);
+        let price = token0
+            .usd_price
+            .zip(token1.usd_price)
+            .map(|(price0, price1)| price0 / price1);
+
+        UnifiedPool {
+            chain_id: self.chain_id,
             id: pool.pool_address,
             protocol: "DragonSwap".to_string(),
-            token0: token0,
-            token1: token1,
-            tvl: pool.liquidity,
-            daily_volume: pool.daily_volume,
+            token0,
+            token1,
+            tvl: pool.liquidity.and_then(Decimal::from_f64),
+            daily_volume: pool.daily_volume.and_then(Decimal::from_f64),
             apr: pool.apr,
-            fee_tier: pool
-                .fee_tier
-                .map(|f| f.to_string())
-                .unwrap_or_else(|| "unknown".to_string()),
-        })
+            // DragonSwap reports the fee as a percentage
+            fee_tier: pool.fee_tier.and_then(FeeTier::from_percent),
+            price,
+            risk: None,
+        }
     }
 
     pub async fn transform_token_address_to_symbol(&self, address: &str) -> Result<Token> {
         let url = format!("{}/pools", self.base_url);
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).await?;
 
         if !response.status().is_success() {
             return Ok(Token {
+                chain_id: self.chain_id,
                 address: address.to_string(),
                 symbol: "Unknown".to_string(),
-                decimals: 0.to_string(),
+                decimals: None,
+                usd_price: None,
             });
         }
 
-        let dragonswap_data: DragonSwapResponse = response.json().await?;
+        let dragonswap_data: DragonSwapResponse = response.json()?;
+        let token_map = Self::token_map(dragonswap_data.tokens);
 
-        let token_map: HashMap<String, DragonSwapToken> = dragonswap_data
-            .tokens
+        Ok(self.lookup_token(&token_map, address))
+    }
+
+    fn token_map(tokens: Vec<DragonSwapToken>) -> HashMap<String, DragonSwapToken> {
+        tokens
             .into_iter()
             .map(|token| (token.address.to_lowercase(), token))
-            .collect();
-
-        let token = token_map.get(&address.to_lowercase());
+            .collect()
+    }
 
-        let result = if let Some(t) = token {
-            Token {
+    fn lookup_token(&self, token_map: &HashMap<String, DragonSwapToken>, address: &str) -> Token {
+        match token_map.get(&address.to_lowercase()) {
+            Some(t) => Token {
+                chain_id: self.chain_id,
                 address: t.address.clone(),
                 symbol: t.symbol.clone(),
-                decimals: t.decimals.to_string(),
-            }
-        } else {
-            Token {
+                decimals: u8::try_from(t.decimals).ok(),
+                usd_price: t.usd_price.filter(|p| *p > 0.0),
+            },
+            None => Token {
+                chain_id: self.chain_id,
                 address: address.to_string(),
                 symbol: "Unknown".to_string(),
-                decimals: 0.to_string(),
-            }
-        };
+                decimals: None,
+                usd_price: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SEI_CHAIN_ID;
+
+    fn provider() -> DragonSwapDataProvider {
+        DragonSwapDataProvider {
+            chain_id: SEI_CHAIN_ID,
+            base_url: String::new(),
+            client: UpstreamClient::new(reqwest::Client::new()),
+        }
+    }
+
+    fn dragonswap_response() -> DragonSwapResponse {
+        serde_json::from_value(serde_json::json!({
+            "status": "ok",
+            "tokens": [
+                { "address": "0xSEI", "name": "Wrapped SEI", "symbol": "WSEI", "usd_price": 0.5, "decimals": 18 },
+                { "address": "0xusdc", "name": "USD Coin", "symbol": "USDC", "usd_price": 1.0, "decimals": 6 }
+            ],
+            "pools": [
+                {
+                    "pool_address": "0xpool", "token0_address": "0xsei", "token1_address": "0xUSDC",
+                    "daily_volume": 5000.5, "liquidity": 120000.0, "type": "V3_POOL",
+                    "fee_tier": 0.05, "apr": 7.5
+                },
+                {
+                    "pool_address": "0xother", "token0_address": "0xmissing", "token1_address": "0xusdc",
+                    "daily_volume": null, "liquidity": null, "type": "V3_POOL",
+                    "fee_tier": null, "apr": null
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_dragonswap_pool_to_v2() {
+        let response = dragonswap_response();
+        let token_map = DragonSwapDataProvider::token_map(response.tokens);
+        let mut pools = response.pools.into_iter();
+        let pool = provider().transform_dragonswap_pool(pools.next().unwrap(), &token_map);
+
+        assert_eq!(pool.chain_id, SEI_CHAIN_ID);
+        assert_eq!(pool.token1.chain_id, SEI_CHAIN_ID);
+        assert_eq!(pool.protocol, "DragonSwap");
+        assert_eq!(pool.fee_tier, Some(FeeTier::from_hundredths_bip(500)));
+        assert_eq!(pool.fee_tier.unwrap().tick_spacing(), 10);
+        assert_eq!(pool.token0.symbol, "WSEI");
+        assert_eq!(pool.token0.decimals, Some(18));
+        assert_eq!(pool.token1.decimals, Some(6));
+        assert_eq!(pool.tvl, Decimal::from_f64(120000.0));
+        assert_eq!(pool.daily_volume, Decimal::from_f64(5000.5));
+        assert_eq!(pool.price, Some(0.5));
+    }
+
+    #[test]
+    fn unknown_dragonswap_tokens_and_fees_become_none() {
+        let response = dragonswap_response();
+        let token_map = DragonSwapDataProvider::token_map(response.tokens);
+        let pool = provider()
+            .transform_dragonswap_pool(response.pools.into_iter().nth(1).unwrap(), &token_map);
 
-        Ok(result)
+        assert_eq!(pool.token0.symbol, "Unknown");
+        assert_eq!(pool.token0.decimals, None);
+        assert_eq!(pool.fee_tier, None);
+        assert_eq!(pool.tvl, None);
+        assert_eq!(pool.price, None);
     }
 }
@@ -0,0 +1,405 @@
+use crate::config::ChainConfig;
+use crate::domain::services::position::{LiquidityEvent, LiquidityEventKind};
+use alloy::primitives::Address;
+use alloy::providers::{Provider, ProviderBuilder, ReqwestProvider};
+use alloy::rpc::types::eth::{BlockNumberOrTag, Filter, Log, TransactionInput, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolEvent};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+sol! {
+    interface ILiquidityManager {
+        event LiquidityMinted(
+            address indexed pool,
+            address indexed recipient,
+            int24 tickLower,
+            int24 tickUpper,
+            uint128 liquidity,
+            uint256 amount0,
+            uint256 amount1
+        );
+
+        event LiquidityBurned(
+            address indexed pool,
+            address indexed owner,
+            int24 tickLower,
+            int24 tickUpper,
+            uint128 liquidity,
+            uint256 amount0,
+            uint256 amount1
+        );
+    }
+
+    interface INonfungiblePositionManager {
+        event IncreaseLiquidity(
+            uint256 indexed tokenId,
+            uint128 liquidity,
+            uint256 amount0,
+            uint256 amount1
+        );
+    }
+
+    interface IPoolTokens {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+    }
+}
+
+/// Reads `LiquidityMinted` and `LiquidityBurned` logs of a LiquidityManager deployment
+pub struct LiquidityManagerLogs {
+    chain_id: u64,
+    contract: Address,
+    provider: ReqwestProvider,
+    /// Token addresses of each pool seen, which never change
+    pool_tokens: Mutex<HashMap<Address, (String, String)>>,
+}
+
+impl LiquidityManagerLogs {
+    /// `None` when the chain has no LIQUIDITY_MANAGER_ADDRESS
+    pub fn for_chain(chain: &ChainConfig) -> Result<Option<Self>> {
+        let Some(contract) = &chain.liquidity_manager_address else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            chain_id: chain.id,
+            contract: contract.parse()?,
+            provider: ProviderBuilder::new().on_http(chain.onchain_rpc_url.parse()?),
+            pool_tokens: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    /// Lower-cased contract address, as the indexer cursor is keyed
+    pub fn contract(&self) -> String {
+        format!("{:#x}", self.contract)
+    }
+
+    pub async fn latest_block(&self) -> Result<u64> {
+        Ok(self.provider.get_block_number().await?)
+    }
+
+    /// Events of blocks `from_block..=to_block` in chain order
+    pub async fn events(&self, from_block: u64, to_block: u64) -> Result<Vec<LiquidityEvent>> {
+        let filter = Filter::new()
+            .address(self.contract)
+            .event_signature(vec![
+                ILiquidityManager::LiquidityMinted::SIGNATURE_HASH,
+                ILiquidityManager::LiquidityBurned::SIGNATURE_HASH,
+            ])
+            .from_block(from_block)
+            .to_block(to_block);
+        let logs = self.provider.get_logs(&filter).await?;
+
+        let mut block_times = HashMap::new();
+        let mut events = Vec::new();
+        for log in logs.iter().filter(|log| !log.removed) {
+            let block_number = log
+                .block_number
+                .ok_or_else(|| anyhow!("Log without a block number"))?;
+            let block_time = match block_times.get(&block_number) {
+                Some(block_time) => *block_time,
+                None => {
+                    let block_time = self.block_time(log, block_number).await?;
+                    block_times.insert(block_number, block_time);
+                    block_time
+                }
+            };
+
+            let Some(mut event) = decode_liquidity_log(self.chain_id, log, block_time)? else {
+                continue;
+            };
+            if event.kind == LiquidityEventKind::Minted {
+                let (token0, token1) = self.pool_tokens(event.pool_address.parse()?).await?;
+                event.token0_address = Some(token0);
+                event.token1_address = Some(token1);
+                event.nft_token_id =
+                    nfpm_token_id(&self.receipt_logs(&event.tx_hash).await?, &event);
+            }
+            events.push(event);
+        }
+
+        events.sort_by_key(|event| (event.block_number, event.log_index));
+        Ok(events)
+    }
+
+    /// From the log when the node includes it, otherwise from the block header
+    async fn block_time(&self, log: &Log, block_number: u64) -> Result<DateTime<Utc>> {
+        let timestamp = match log.block_timestamp {
+            Some(timestamp) => timestamp,
+            None => {
+                self.provider
+                    .get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+                    .await?
+                    .ok_or_else(|| anyhow!("Block {} not found", block_number))?
+                    .header
+                    .timestamp
+            }
+        };
+        DateTime::from_timestamp(timestamp as i64, 0)
+            .ok_or_else(|| anyhow!("Invalid timestamp {} of block {}", timestamp, block_number))
+    }
+
+    async fn receipt_logs(&self, tx_hash: &str) -> Result<Vec<Log>> {
+        let receipt = self
+            .provider
+            .get_transaction_receipt(tx_hash.parse()?)
+            .await?
+            .ok_or_else(|| anyhow!("Receipt of {} not found", tx_hash))?;
+        Ok(receipt.inner.logs().to_vec())
+    }
+
+    async fn pool_tokens(&self, pool: Address) -> Result<(String, String)> {
+        if let Some(tokens) = self
+            .pool_tokens
+            .lock()
+            .expect("pool tokens lock poisoned")
+            .get(&pool)
+        {
+            return Ok(tokens.clone());
+        }
+
+        let token0 = self
+            .call(pool, IPoolTokens::token0Call {}.abi_encode())
+            .await?;
+        let token1 = self
+            .call(pool, IPoolTokens::token1Call {}.abi_encode())
+            .await?;
+        let tokens = (
+            format!(
+                "{:#x}",
+                IPoolTokens::token0Call::abi_decode_returns(&token0, true)?._0
+            ),
+            format!(
+                "{:#x}",
+                IPoolTokens::token1Call::abi_decode_returns(&token1, true)?._0
+            ),
+        );
+        self.pool_tokens
+            .lock()
+            .expect("pool tokens lock poisoned")
+            .insert(pool, tokens.clone());
+        Ok(tokens)
+    }
+
+    async fn call(&self, to: Address, input: Vec<u8>) -> Result<Vec<u8>> {
+        let request = TransactionRequest::default()
+            .to(to)
+            .input(TransactionInput::new(input.into()));
+        Ok(self.provider.call(&request).await?.to_vec())
+    }
+}
+
+/// Token id of the NFPM position behind a mint, from the `IncreaseLiquidity` log that
+/// `mintLiquidityUsingNFPM` emits just before `LiquidityMinted` with the same liquidity. `None`
+/// for liquidity minted straight into the pool.
+pub fn nfpm_token_id(receipt_logs: &[Log], minted: &LiquidityEvent) -> Option<String> {
+    receipt_logs
+        .iter()
+        .filter(|log| {
+            log.topics().first()
+                == Some(&INonfungiblePositionManager::IncreaseLiquidity::SIGNATURE_HASH)
+                && log
+                    .log_index
+                    .is_some_and(|log_index| log_index < minted.log_index)
+        })
+        .filter_map(|log| {
+            let increased =
+                INonfungiblePositionManager::IncreaseLiquidity::decode_log(&log.inner, true)
+                    .ok()?;
+            Some((log.log_index, increased.data))
+        })
+        .filter(|(_, increased)| increased.liquidity.to_string() == minted.liquidity)
+        .max_by_key(|(log_index, _)| *log_index)
+        .map(|(_, increased)| increased.tokenId.to_string())
+}
+
+/// `None` for logs of other events. Pool tokens and the NFPM token id are left unset.
+pub fn decode_liquidity_log(
+    chain_id: u64,
+    log: &Log,
+    block_time: DateTime<Utc>,
+) -> Result<Option<LiquidityEvent>> {
+    let event = match log.topics().first() {
+        Some(&ILiquidityManager::LiquidityMinted::SIGNATURE_HASH) => {
+            let minted = ILiquidityManager::LiquidityMinted::decode_log(&log.inner, true)?.data;
+            (
+                LiquidityEventKind::Minted,
+                minted.pool,
+                minted.recipient,
+                minted.tickLower,
+                minted.tickUpper,
+                minted.liquidity,
+                minted.amount0,
+                minted.amount1,
+            )
+        }
+        Some(&ILiquidityManager::LiquidityBurned::SIGNATURE_HASH) => {
+            let burned = ILiquidityManager::LiquidityBurned::decode_log(&log.inner, true)?.data;
+            (
+                LiquidityEventKind::Burned,
+                burned.pool,
+                burned.owner,
+                burned.tickLower,
+                burned.tickUpper,
+                burned.liquidity,
+                burned.amount0,
+                burned.amount1,
+            )
+        }
+        _ => return Ok(None),
+    };
+    let (kind, pool, owner, tick_lower, tick_upper, liquidity, amount0, amount1) = event;
+
+    Ok(Some(LiquidityEvent {
+        kind,
+        chain_id,
+        pool_address: format!("{:#x}", pool),
+        owner: format!("{:#x}", owner),
+        token0_address: None,
+        token1_address: None,
+        tick_lower,
+        tick_upper,
+        liquidity: liquidity.to_string(),
+        amount0: amount0.to_string(),
+        amount1: amount1.to_string(),
+        nft_token_id: None,
+        tx_hash: format!(
+            "{:#x}",
+            log.transaction_hash
+                .ok_or_else(|| anyhow!("Log without a transaction hash"))?
+        ),
+        block_number: log
+            .block_number
+            .ok_or_else(|| anyhow!("Log without a block number"))?,
+        log_index: log
+            .log_index
+            .ok_or_else(|| anyhow!("Log without a log index"))?,
+        block_time,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{B256, U256, address};
+
+    const POOL: Address = address!("80fE558C54f1F43263E08F0E1Fa3E02D8B897F93");
+    const WALLET: Address = address!("79dAa774769334aF120f6CAA57E828FBBF56b39a");
+
+    fn log(data: alloy::primitives::LogData) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: address!("29491F712637415f4E27B7471F4DD8147094C555"),
+                data,
+            },
+            block_hash: Some(B256::repeat_byte(1)),
+            block_number: Some(42),
+            block_timestamp: None,
+            transaction_hash: Some(B256::repeat_byte(0xab)),
+            transaction_index: Some(0),
+            log_index: Some(3),
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn decodes_mints_and_burns() {
+        let block_time = DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        let minted = ILiquidityManager::LiquidityMinted {
+            pool: POOL,
+            recipient: WALLET,
+            tickLower: -887220,
+            tickUpper: 887220,
+            liquidity: u128::MAX,
+            amount0: U256::from(100_000),
+            amount1: U256::from(10).pow(U256::from(30)),
+        };
+
+        let event = decode_liquidity_log(1329, &log(minted.encode_log_data()), block_time)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.kind, LiquidityEventKind::Minted);
+        assert_eq!(
+            event.pool_address,
+            "0x80fe558c54f1f43263e08f0e1fa3e02d8b897f93"
+        );
+        assert_eq!(event.owner, "0x79daa774769334af120f6caa57e828fbbf56b39a");
+        assert_eq!((event.tick_lower, event.tick_upper), (-887220, 887220));
+        assert_eq!(event.liquidity, u128::MAX.to_string());
+        assert_eq!(event.amount1, format!("1{}", "0".repeat(30)));
+        assert_eq!(event.tx_hash, format!("0x{}", "ab".repeat(32)));
+        assert_eq!((event.block_number, event.log_index), (42, 3));
+        assert_eq!(event.block_time, block_time);
+
+        let burned = ILiquidityManager::LiquidityBurned {
+            pool: POOL,
+            owner: WALLET,
+            tickLower: -600,
+            tickUpper: 600,
+            liquidity: 5,
+            amount0: U256::from(1),
+            amount1: U256::from(2),
+        };
+        let event = decode_liquidity_log(1329, &log(burned.encode_log_data()), block_time)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.kind, LiquidityEventKind::Burned);
+        assert_eq!(event.liquidity, "5");
+    }
+
+    #[test]
+    fn reads_the_nfpm_token_id_of_the_mint() {
+        let block_time = DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        let minted = ILiquidityManager::LiquidityMinted {
+            pool: POOL,
+            recipient: WALLET,
+            tickLower: -600,
+            tickUpper: 600,
+            liquidity: 500,
+            amount0: U256::from(1),
+            amount1: U256::from(2),
+        };
+        let mut minted_log = log(minted.encode_log_data());
+        minted_log.log_index = Some(5);
+        let event = decode_liquidity_log(1329, &minted_log, block_time)
+            .unwrap()
+            .unwrap();
+
+        let increase = |token_id: u64, liquidity: u128, log_index: u64| {
+            let mut log = log(INonfungiblePositionManager::IncreaseLiquidity {
+                tokenId: U256::from(token_id),
+                liquidity,
+                amount0: U256::from(1),
+                amount1: U256::from(2),
+            }
+            .encode_log_data());
+            log.log_index = Some(log_index);
+            log
+        };
+        // Only the closest earlier increase of the same liquidity belongs to this mint
+        let receipt_logs = [
+            increase(7, 500, 1),
+            increase(8, 300, 3),
+            increase(9, 500, 4),
+            minted_log.clone(),
+            increase(10, 500, 6),
+        ];
+        assert_eq!(nfpm_token_id(&receipt_logs, &event).as_deref(), Some("9"));
+
+        // Minted straight into the pool
+        assert_eq!(nfpm_token_id(&[minted_log], &event), None);
+        assert_eq!(nfpm_token_id(&[], &event), None);
+    }
+
+    #[test]
+    fn skips_other_events() {
+        let data = alloy::primitives::LogData::new_unchecked(vec![B256::ZERO], Default::default());
+        assert!(
+            decode_liquidity_log(1329, &log(data), Utc::now())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
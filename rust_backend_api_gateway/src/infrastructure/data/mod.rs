@@ -1,25 +1,110 @@
 pub mod binance_data_provider;
 pub mod dragonswap_data_provider;
+pub mod liquidity_manager_logs;
+pub mod onchain_dex_provider;
 pub mod sailor_data_provider;
+pub mod subgraph_dex_provider;
+pub mod upstream_client;
 
+use crate::config::{ChainConfig, UpstreamMode, upstream_mode};
 use crate::domain::repositories::{data_provider::DataProvider, dex_provider::DexProvider};
 use binance_data_provider::BinanceDataProvider;
 use dragonswap_data_provider::DragonSwapDataProvider;
+use onchain_dex_provider::OnChainDexProvider;
 use sailor_data_provider::SailorDataProvider;
+use subgraph_dex_provider::SubgraphDexProvider;
+use tracing::{debug, warn};
 
-pub fn get_data_provider(wallet_type: &str) -> Option<Box<dyn DataProvider>> {
+/// `None` for unknown providers and for DEXes not configured on the chain. Binance is off-chain
+/// and answers the same on every chain.
+pub fn get_data_provider(wallet_type: &str, chain: &ChainConfig) -> Option<Box<dyn DataProvider>> {
     match wallet_type {
         "binance" => Some(Box::new(BinanceDataProvider::new())),
-        "dragonswap" => Some(Box::new(DragonSwapDataProvider::new())),
-        "sailor" => Some(Box::new(SailorDataProvider::new())),
-        _ => None,
+        "dragonswap" => {
+            DragonSwapDataProvider::for_chain(chain).map(|p| Box::new(p) as Box<dyn DataProvider>)
+        }
+        "sailor" => {
+            SailorDataProvider::for_chain(chain).map(|p| Box::new(p) as Box<dyn DataProvider>)
+        }
+        "onchain" => onchain_provider(chain).map(|p| Box::new(p) as Box<dyn DataProvider>),
+        _ => subgraph_provider(wallet_type, chain).map(|p| Box::new(p) as Box<dyn DataProvider>),
     }
 }
 
-pub fn get_dex_provider(wallet_type: &str) -> Option<Box<dyn DexProvider>> {
+/// A provider a request needs is not configured on the chain
+#[derive(Debug, thiserror::Error)]
+#[error("{provider} price data is not available on {chain}")]
+pub struct ProviderUnavailable {
+    pub provider: String,
+    pub chain: String,
+}
+
+/// `get_data_provider` for use cases that cannot work without the provider
+pub fn require_data_provider(
+    name: &str,
+    chain: &ChainConfig,
+) -> Result<Box<dyn DataProvider>, ProviderUnavailable> {
+    get_data_provider(name, chain).ok_or_else(|| ProviderUnavailable {
+        provider: name.to_string(),
+        chain: chain.name.clone(),
+    })
+}
+
+/// Built-in DEX providers pools are aggregated from
+pub const DEX_PROVIDERS: [&str; 2] = ["dragonswap", "sailor"];
+
+/// Every DEX provider pools are aggregated from on the chain: the built-in ones followed by the
+/// chain's subgraph DEXes
+pub fn dex_provider_names(chain: &ChainConfig) -> Vec<String> {
+    let mut names: Vec<String> = DEX_PROVIDERS.iter().map(|name| name.to_string()).collect();
+    for dex in &chain.subgraph_dexes {
+        if names.contains(&dex.name) {
+            warn!(
+                "⚠️ Subgraph DEX {} on {} shadows a built-in provider and is ignored",
+                dex.name, chain.name
+            );
+            continue;
+        }
+        names.push(dex.name.clone());
+    }
+    names
+}
+
+pub fn get_dex_provider(wallet_type: &str, chain: &ChainConfig) -> Option<Box<dyn DexProvider>> {
     match wallet_type {
-        "dragonswap" => Some(Box::new(DragonSwapDataProvider::new())),
-        "sailor" => Some(Box::new(SailorDataProvider::new())),
-        _ => None,
+        "dragonswap" => {
+            DragonSwapDataProvider::for_chain(chain).map(|p| Box::new(p) as Box<dyn DexProvider>)
+        }
+        "sailor" => {
+            SailorDataProvider::for_chain(chain).map(|p| Box::new(p) as Box<dyn DexProvider>)
+        }
+        "onchain" => onchain_provider(chain).map(|p| Box::new(p) as Box<dyn DexProvider>),
+        _ => subgraph_provider(wallet_type, chain).map(|p| Box::new(p) as Box<dyn DexProvider>),
+    }
+}
+
+/// A DEX listed in the chain's SUBGRAPH_DEXES
+fn subgraph_provider(name: &str, chain: &ChainConfig) -> Option<SubgraphDexProvider> {
+    chain
+        .subgraph_dexes
+        .iter()
+        .find(|dex| dex.name == name)
+        .map(|dex| SubgraphDexProvider::for_chain_dex(chain, dex))
+}
+
+/// Not part of `DEX_PROVIDERS`: it reads the chain's ONCHAIN_POOL_ADDRESSES and backs up the
+/// REST providers' tick data. JSON-RPC cannot be replayed, so it is off in replay mode.
+pub fn onchain_provider(chain: &ChainConfig) -> Option<OnChainDexProvider> {
+    if upstream_mode() == UpstreamMode::Replay {
+        debug!("On-chain provider for {} is off in replay mode", chain.name);
+        return None;
     }
+    OnChainDexProvider::for_chain(chain)
+        .inspect_err(|e| {
+            warn!(
+                "⚠️ On-chain provider for {} is misconfigured: {}",
+                chain.name, e
+            )
+        })
+        .ok()
 }
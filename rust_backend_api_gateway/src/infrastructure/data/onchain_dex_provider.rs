@@ -0,0 +1,638 @@
+use crate::application::dtos::price_history::PricePoint;
+use crate::config::{ChainConfig, onchain_tick_word_radius};
+use crate::domain::repositories::data_provider::DataProvider;
+use crate::domain::repositories::dex_provider::DexProvider;
+use crate::domain::services::data::{
+    ActiveLiquidityResponse, LiquidityTick, TickDataShape, Token, UnifiedPool,
+};
+use crate::domain::services::fee_tier::FeeTier;
+use crate::math::tick_to_price;
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::providers::{Provider, ProviderBuilder, ReqwestProvider};
+use alloy::rpc::types::eth::{TransactionInput, TransactionRequest};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::{info, warn};
+
+/// Calls per `aggregate3`, small enough for public RPC gas and response limits
+const MULTICALL_BATCH_SIZE: usize = 500;
+
+sol! {
+    interface IUniswapV3Pool {
+        function token0() external view returns (address);
+        function token1() external view returns (address);
+        function fee() external view returns (uint24);
+        function tickSpacing() external view returns (int24);
+        function slot0() external view returns (
+            uint160 sqrtPriceX96,
+            int24 tick,
+            uint16 observationIndex,
+            uint16 observationCardinality,
+            uint16 observationCardinalityNext,
+            uint8 feeProtocol,
+            bool unlocked
+        );
+        function liquidity() external view returns (uint128);
+        function feeGrowthGlobal0X128() external view returns (uint256);
+        function feeGrowthGlobal1X128() external view returns (uint256);
+        function tickBitmap(int16 wordPosition) external view returns (uint256);
+        function ticks(int24 tick) external view returns (
+            uint128 liquidityGross,
+            int128 liquidityNet,
+            uint256 feeGrowthOutside0X128,
+            uint256 feeGrowthOutside1X128,
+            int56 tickCumulativeOutside,
+            uint160 secondsPerLiquidityOutsideX128,
+            uint32 secondsOutside,
+            bool initialized
+        );
+        function observe(uint32[] secondsAgos) external view returns (
+            int56[] tickCumulatives,
+            uint160[] secondsPerLiquidityCumulativeX128s
+        );
+    }
+
+    interface IERC20Metadata {
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+    }
+}
+
+/// Pool state read straight from a Uniswap V3 pool contract
+#[derive(Debug, Clone)]
+pub struct OnChainPoolState {
+    pub address: Address,
+    pub token0: Token,
+    pub token1: Token,
+    pub fee_tier: FeeTier,
+    pub tick_spacing: i32,
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    /// Liquidity active at the current tick
+    pub liquidity: u128,
+    pub fee_growth_global0_x128: U256,
+    pub fee_growth_global1_x128: U256,
+}
+
+impl OnChainPoolState {
+    /// Token1 per token0, adjusted for decimals
+    pub fn price(&self) -> f64 {
+        let sqrt_price = self
+            .sqrt_price_x96
+            .to_string()
+            .parse::<f64>()
+            .unwrap_or(0.0)
+            / 2f64.powi(96);
+        sqrt_price.powi(2)
+            * 10f64.powi(
+                self.token0.decimals_or_default() as i32 - self.token1.decimals_or_default() as i32,
+            )
+    }
+}
+
+/// Reads V3 pools over JSON-RPC, batching every read through Multicall3
+pub struct OnChainDexProvider {
+    chain_id: u64,
+    pool_addresses: Vec<String>,
+    provider: ReqwestProvider,
+    multicall: Address,
+}
+
+#[async_trait]
+impl DataProvider for OnChainDexProvider {
+    async fn get_price_data(
+        &self,
+        _token0: &str,
+        _token1: &str,
+        _interval: u32,
+        _limit: u32,
+    ) -> Result<Vec<PricePoint>> {
+        Err(anyhow!("onchain does not support price data retrieval"))
+    }
+}
+
+#[async_trait]
+impl DexProvider for OnChainDexProvider {
+    /// Active liquidity of every initialised tick within `ONCHAIN_TICK_WORD_RADIUS` bitmap words
+    /// of the current tick, anchored on the pool's current liquidity
+    async fn get_liquidity_data(&self, pool_address: &str) -> Result<ActiveLiquidityResponse> {
+        let address: Address = pool_address.parse()?;
+        let state = self
+            .pool_states(&[address])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("Pool {} could not be read on-chain", pool_address))?;
+        info!(
+            "⛓️ Reading tick bitmap of {} around tick {}",
+            pool_address, state.tick
+        );
+
+        let liquidity_net = self.initialized_ticks(&state).await?;
+
+        Ok(ActiveLiquidityResponse {
+            status: "ok".to_string(),
+            data: active_liquidity_ticks(&state, &liquidity_net),
+            tick_shape: TickDataShape::ActiveLiquidity,
+            ..Default::default()
+        })
+    }
+
+    async fn get_pool_list(&self) -> Result<Vec<UnifiedPool>> {
+        let addresses = self
+            .pool_addresses
+            .iter()
+            .map(|address| address.parse::<Address>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self
+            .pool_states(&addresses)
+            .await?
+            .into_iter()
+            .map(|state| {
+                let price = state.price();
+                UnifiedPool {
+                    chain_id: self.chain_id,
+                    id: state.address.to_string(),
+                    protocol: "OnChain".to_string(),
+                    token0: state.token0,
+                    token1: state.token1,
+                    tvl: None,
+                    daily_volume: None,
+                    apr: None,
+                    fee_tier: Some(state.fee_tier),
+                    price: Some(price).filter(|p| p.is_finite() && *p > 0.0),
+                    risk: None,
+                }
+            })
+            .collect())
+    }
+}
+
+impl OnChainDexProvider {
+    pub fn for_chain(chain: &ChainConfig) -> Result<Self> {
+        info!(
+            "🔗 Using RPC URL for {}: {}",
+            chain.name, chain.onchain_rpc_url
+        );
+
+        Ok(Self {
+            chain_id: chain.id,
+            pool_addresses: chain.onchain_pool_addresses.clone(),
+            provider: ProviderBuilder::new().on_http(chain.onchain_rpc_url.parse()?),
+            multicall: chain.multicall3_address.parse()?,
+        })
+    }
+
+    /// Slot0, liquidity, fee growth and token metadata of each pool, in two multicall rounds.
+    /// Pools whose reads fail are left out.
+    pub async fn pool_states(&self, pools: &[Address]) -> Result<Vec<OnChainPoolState>> {
+        let mut calls = Vec::new();
+        for pool in pools {
+            calls.extend([
+                (*pool, IUniswapV3Pool::token0Call {}.abi_encode()),
+                (*pool, IUniswapV3Pool::token1Call {}.abi_encode()),
+                (*pool, IUniswapV3Pool::feeCall {}.abi_encode()),
+                (*pool, IUniswapV3Pool::tickSpacingCall {}.abi_encode()),
+                (*pool, IUniswapV3Pool::slot0Call {}.abi_encode()),
+                (*pool, IUniswapV3Pool::liquidityCall {}.abi_encode()),
+                (
+                    *pool,
+                    IUniswapV3Pool::feeGrowthGlobal0X128Call {}.abi_encode(),
+                ),
+                (
+                    *pool,
+                    IUniswapV3Pool::feeGrowthGlobal1X128Call {}.abi_encode(),
+                ),
+            ]);
+        }
+        let results = self.multicall(calls).await?;
+
+        let mut states: Vec<OnChainPoolState> = pools
+            .iter()
+            .zip(results.chunks(8))
+            .filter_map(|(pool, reads)| {
+                decode_pool_state(self.chain_id, *pool, reads).or_else(|| {
+                    warn!("⚠️ Pool {} could not be read on-chain", pool);
+                    None
+                })
+            })
+            .collect();
+
+        let tokens: Vec<Address> = states
+            .iter()
+            .flat_map(|state| [&state.token0.address, &state.token1.address])
+            .filter_map(|address| address.parse().ok())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let metadata = self.token_metadata(&tokens).await?;
+        for token in states
+            .iter_mut()
+            .flat_map(|state| [&mut state.token0, &mut state.token1])
+        {
+            if let Some((symbol, decimals)) = token
+                .address
+                .parse::<Address>()
+                .ok()
+                .and_then(|address| metadata.get(&address))
+            {
+                token.symbol = symbol.clone();
+                token.decimals = *decimals;
+            }
+        }
+
+        Ok(states)
+    }
+
+    /// Arithmetic mean tick of a pool over the last `window_secs`, from its `observe` oracle.
+    /// Fails when the pool's observations do not reach back that far.
+    pub async fn twap_tick(&self, pool: Address, window_secs: u32) -> Result<i32> {
+        let observe = IUniswapV3Pool::observeCall {
+            secondsAgos: vec![window_secs, 0],
+        };
+        let read = self
+            .multicall(vec![(pool, observe.abi_encode())])
+            .await?
+            .pop()
+            .flatten();
+        let observed = decode::<IUniswapV3Pool::observeCall>(&read).ok_or_else(|| {
+            anyhow!(
+                "Pool {} has no observations {} seconds back",
+                pool,
+                window_secs
+            )
+        })?;
+
+        mean_tick(&observed.tickCumulatives, window_secs)
+    }
+
+    /// Symbol and decimals of each token; tokens that do not answer are left out
+    async fn token_metadata(
+        &self,
+        tokens: &[Address],
+    ) -> Result<HashMap<Address, (String, Option<u8>)>> {
+        let calls = tokens
+            .iter()
+            .flat_map(|token| {
+                [
+                    (*token, IERC20Metadata::symbolCall {}.abi_encode()),
+                    (*token, IERC20Metadata::decimalsCall {}.abi_encode()),
+                ]
+            })
+            .collect();
+        let results = self.multicall(calls).await?;
+
+        Ok(tokens
+            .iter()
+            .zip(results.chunks(2))
+            .filter_map(|(token, reads)| {
+                let symbol = decode::<IERC20Metadata::symbolCall>(&reads[0])?._0;
+                let decimals = decode::<IERC20Metadata::decimalsCall>(&reads[1]).map(|d| d._0);
+                Some((*token, (symbol, decimals)))
+            })
+            .collect())
+    }
+
+    /// `liquidityNet` of every initialised tick in the bitmap words around the current tick
+    async fn initialized_ticks(&self, state: &OnChainPoolState) -> Result<BTreeMap<i32, i128>> {
+        let spacing = state.tick_spacing.max(1);
+        let radius = onchain_tick_word_radius().max(0);
+        let current_word = (state.tick.div_euclid(spacing) >> 8) as i16;
+        let words: Vec<i16> =
+            (current_word.saturating_sub(radius)..=current_word.saturating_add(radius)).collect();
+
+        let bitmaps = self
+            .multicall(
+                words
+                    .iter()
+                    .map(|word| {
+                        (
+                            state.address,
+                            IUniswapV3Pool::tickBitmapCall {
+                                wordPosition: *word,
+                            }
+                            .abi_encode(),
+                        )
+                    })
+                    .collect(),
+            )
+            .await?;
+
+        let mut ticks = Vec::new();
+        for (word, bitmap) in words.iter().zip(&bitmaps) {
+            let Some(bitmap) = decode::<IUniswapV3Pool::tickBitmapCall>(bitmap) else {
+                continue;
+            };
+            for bit in 0..256usize {
+                if bitmap._0.bit(bit) {
+                    ticks.push((*word as i32 * 256 + bit as i32) * spacing);
+                }
+            }
+        }
+
+        let reads = self
+            .multicall(
+                ticks
+                    .iter()
+                    .map(|tick| {
+                        (
+                            state.address,
+                            IUniswapV3Pool::ticksCall { tick: *tick }.abi_encode(),
+                        )
+                    })
+                    .collect(),
+            )
+            .await?;
+
+        Ok(ticks
+            .into_iter()
+            .zip(&reads)
+            .filter_map(|(tick, read)| {
+                Some((
+                    tick,
+                    decode::<IUniswapV3Pool::ticksCall>(read)?.liquidityNet,
+                ))
+            })
+            .collect())
+    }
+
+    /// Run calls through Multicall3 `aggregate3` in batches, allowing individual calls to fail
+    async fn multicall(&self, calls: Vec<(Address, Vec<u8>)>) -> Result<Vec<Option<Bytes>>> {
+        let mut results = Vec::with_capacity(calls.len());
+        for batch in calls.chunks(MULTICALL_BATCH_SIZE) {
+            let aggregate = IMulticall3::aggregate3Call {
+                calls: batch
+                    .iter()
+                    .map(|(target, call_data)| IMulticall3::Call3 {
+                        target: *target,
+                        allowFailure: true,
+                        callData: call_data.clone().into(),
+                    })
+                    .collect(),
+            };
+            let request = TransactionRequest::default()
+                .to(self.multicall)
+                .input(TransactionInput::new(aggregate.abi_encode().into()));
+
+            let output = self.provider.call(&request).await?;
+            results.extend(decode_aggregate3(&output)?);
+        }
+
+        Ok(results)
+    }
+}
+
+/// Return data of each call in an `aggregate3` result, `None` for calls that reverted
+fn decode_aggregate3(output: &[u8]) -> Result<Vec<Option<Bytes>>> {
+    let decoded = IMulticall3::aggregate3Call::abi_decode_returns(output, true)?;
+    Ok(decoded
+        .returnData
+        .into_iter()
+        .map(|result| result.success.then_some(result.returnData))
+        .collect())
+}
+
+/// Pool state from the eight reads `pool_states` issues per pool, tokens not yet resolved
+fn decode_pool_state(
+    chain_id: u64,
+    address: Address,
+    reads: &[Option<Bytes>],
+) -> Option<OnChainPoolState> {
+    let token = |address: Address| Token {
+        chain_id,
+        address: address.to_string(),
+        symbol: "Unknown".to_string(),
+        decimals: None,
+        usd_price: None,
+    };
+    let slot0 = decode::<IUniswapV3Pool::slot0Call>(&reads[4])?;
+
+    Some(OnChainPoolState {
+        address,
+        token0: token(decode::<IUniswapV3Pool::token0Call>(&reads[0])?._0),
+        token1: token(decode::<IUniswapV3Pool::token1Call>(&reads[1])?._0),
+        fee_tier: FeeTier::from_hundredths_bip(decode::<IUniswapV3Pool::feeCall>(&reads[2])?._0),
+        tick_spacing: decode::<IUniswapV3Pool::tickSpacingCall>(&reads[3])?._0,
+        sqrt_price_x96: U256::from(slot0.sqrtPriceX96),
+        tick: slot0.tick,
+        liquidity: decode::<IUniswapV3Pool::liquidityCall>(&reads[5])?._0,
+        fee_growth_global0_x128: decode::<IUniswapV3Pool::feeGrowthGlobal0X128Call>(&reads[6])?._0,
+        fee_growth_global1_x128: decode::<IUniswapV3Pool::feeGrowthGlobal1X128Call>(&reads[7])?._0,
+    })
+}
+
+/// Mean tick between two cumulative tick readings, rounded down like Uniswap's `OracleLibrary`
+fn mean_tick(tick_cumulatives: &[i64], window_secs: u32) -> Result<i32> {
+    let [start, end] = tick_cumulatives else {
+        return Err(anyhow!(
+            "observe returned {} tick cumulatives instead of 2",
+            tick_cumulatives.len()
+        ));
+    };
+    if window_secs == 0 {
+        return Err(anyhow!("TWAP window must be positive"));
+    }
+
+    Ok((end - start).div_euclid(window_secs as i64) as i32)
+}
+
+fn decode<C: SolCall>(result: &Option<Bytes>) -> Option<C::Return> {
+    C::abi_decode_returns(result.as_ref()?, true).ok()
+}
+
+/// Active liquidity from each initialised tick to the next, walking out from the current tick,
+/// where the pool's own `liquidity` is known, and applying `liquidityNet` at each crossing
+fn active_liquidity_ticks(
+    state: &OnChainPoolState,
+    liquidity_net: &BTreeMap<i32, i128>,
+) -> Vec<LiquidityTick> {
+    let mut active = BTreeMap::new();
+
+    let mut running = state.liquidity as i128;
+    for (tick, net) in liquidity_net.range(..=state.tick).rev() {
+        active.insert(*tick, running);
+        running -= net;
+    }
+    let mut running = state.liquidity as i128;
+    for (tick, net) in liquidity_net.range(state.tick + 1..) {
+        running += net;
+        active.insert(*tick, running);
+    }
+
+    let (decimals0, decimals1) = (
+        state.token0.decimals_or_default(),
+        state.token1.decimals_or_default(),
+    );
+    active
+        .into_iter()
+        .map(|(tick, liquidity)| {
+            let price0 = tick_to_price(tick, decimals0, decimals1);
+            LiquidityTick {
+                tick_idx: tick.to_string(),
+                liquidity_net: liquidity.max(0).to_string(),
+                price0: price0.to_string(),
+                price1: (1.0 / price0).to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const POOL: Address = address!("80fE558C54f1F43263E08F0E1Fa3E02D8B897F93");
+    const TOKEN0: Address = address!("E30feDd158A2e3b13e9badaeABaFc5516e95e8C7");
+    const TOKEN1: Address = address!("3894085Ef7Ff0f0aeDf52E2A2704928d1Ec074F1");
+
+    fn pool_reads(sqrt_price_x96: U256, tick: i32, liquidity: u128) -> Vec<Option<Bytes>> {
+        vec![
+            Some(IUniswapV3Pool::token0Call::abi_encode_returns(&(TOKEN0,)).into()),
+            Some(IUniswapV3Pool::token1Call::abi_encode_returns(&(TOKEN1,)).into()),
+            Some(IUniswapV3Pool::feeCall::abi_encode_returns(&(3000u32,)).into()),
+            Some(IUniswapV3Pool::tickSpacingCall::abi_encode_returns(&(60i32,)).into()),
+            Some(
+                IUniswapV3Pool::slot0Call::abi_encode_returns(&(
+                    sqrt_price_x96,
+                    tick,
+                    0u16,
+                    1u16,
+                    1u16,
+                    0u8,
+                    true,
+                ))
+                .into(),
+            ),
+            Some(IUniswapV3Pool::liquidityCall::abi_encode_returns(&(liquidity,)).into()),
+            Some(
+                IUniswapV3Pool::feeGrowthGlobal0X128Call::abi_encode_returns(&(U256::from(7),))
+                    .into(),
+            ),
+            Some(
+                IUniswapV3Pool::feeGrowthGlobal1X128Call::abi_encode_returns(&(U256::from(9),))
+                    .into(),
+            ),
+        ]
+    }
+
+    fn state(tick: i32, liquidity: u128) -> OnChainPoolState {
+        let mut state = decode_pool_state(
+            1329,
+            POOL,
+            &pool_reads(U256::from(1u8) << 96, tick, liquidity),
+        )
+        .unwrap();
+        state.token0.decimals = Some(18);
+        state.token1.decimals = Some(18);
+        state
+    }
+
+    #[test]
+    fn decodes_aggregate3_results() {
+        let output = IMulticall3::aggregate3Call::abi_encode_returns(&(vec![
+            IMulticall3::Result {
+                success: true,
+                returnData: Bytes::from(vec![1, 2, 3]),
+            },
+            IMulticall3::Result {
+                success: false,
+                returnData: Bytes::from(vec![4]),
+            },
+        ],));
+
+        let results = decode_aggregate3(&output).unwrap();
+        assert_eq!(results, [Some(Bytes::from(vec![1, 2, 3])), None]);
+
+        let empty =
+            IMulticall3::aggregate3Call::abi_encode_returns(&(Vec::<IMulticall3::Result>::new(),));
+        assert!(decode_aggregate3(&empty).unwrap().is_empty());
+        assert!(decode_aggregate3(&[0xff; 7]).is_err());
+    }
+
+    #[test]
+    fn decodes_pool_reads() {
+        let state =
+            decode_pool_state(1329, POOL, &pool_reads(U256::from(1u8) << 96, -5, 1000)).unwrap();
+
+        assert_eq!(state.token0.address, TOKEN0.to_string());
+        assert_eq!(state.token1.address, TOKEN1.to_string());
+        assert_eq!(state.fee_tier, FeeTier::from_hundredths_bip(3000));
+        assert_eq!(state.tick_spacing, 60);
+        assert_eq!(state.tick, -5);
+        assert_eq!(state.liquidity, 1000);
+        assert_eq!(state.fee_growth_global0_x128, U256::from(7));
+        assert_eq!(state.fee_growth_global1_x128, U256::from(9));
+        // Decimals default to 18 on both sides until metadata is read
+        assert!((state.price() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn skips_pools_with_a_failed_or_malformed_read() {
+        let mut reverted = pool_reads(U256::from(1u8) << 96, 0, 0);
+        reverted[4] = None;
+        assert!(decode_pool_state(1329, POOL, &reverted).is_none());
+
+        let mut malformed = pool_reads(U256::from(1u8) << 96, 0, 0);
+        malformed[5] = Some(Bytes::from(vec![1]));
+        assert!(decode_pool_state(1329, POOL, &malformed).is_none());
+    }
+
+    #[test]
+    fn averages_observed_tick_cumulatives() {
+        let output = IUniswapV3Pool::observeCall::abi_encode_returns(&(
+            vec![-1_000_000i64, -1_000_000 + 3600 * 42],
+            vec![U256::ZERO, U256::from(1u8)],
+        ));
+        let observed = decode::<IUniswapV3Pool::observeCall>(&Some(output.into())).unwrap();
+        assert_eq!(mean_tick(&observed.tickCumulatives, 3600).unwrap(), 42);
+
+        // Negative averages round towards negative infinity
+        assert_eq!(mean_tick(&[0, -3601], 3600).unwrap(), -2);
+        assert_eq!(mean_tick(&[0, -3600], 3600).unwrap(), -1);
+        assert!(mean_tick(&[0], 3600).is_err());
+        assert!(mean_tick(&[0, 0], 0).is_err());
+    }
+
+    #[test]
+    fn walks_active_liquidity_out_from_the_current_tick() {
+        let liquidity_net = BTreeMap::from([(-120, 300), (-60, 200), (60, -200), (120, -300)]);
+        let ticks = active_liquidity_ticks(&state(0, 500), &liquidity_net);
+
+        let active: Vec<(&str, &str)> = ticks
+            .iter()
+            .map(|tick| (tick.tick_idx.as_str(), tick.liquidity_net.as_str()))
+            .collect();
+        assert_eq!(
+            active,
+            [("-120", "300"), ("-60", "500"), ("60", "300"), ("120", "0")]
+        );
+    }
+
+    #[test]
+    fn handles_pools_without_liquidity_or_ticks() {
+        assert!(active_liquidity_ticks(&state(0, 0), &BTreeMap::new()).is_empty());
+
+        // Inconsistent nets never report negative liquidity
+        let ticks = active_liquidity_ticks(&state(0, 0), &BTreeMap::from([(60, -100)]));
+        assert_eq!(ticks[0].liquidity_net, "0");
+    }
+}
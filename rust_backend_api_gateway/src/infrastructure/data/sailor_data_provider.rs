@@ -2,27 +2,27 @@ use crate::domain::repositories::data_provider::DataProvider;
 use crate::domain::repositories::dex_provider::DexProvider;
 
 use crate::application::dtos::price_history::PricePoint;
-use crate::config::sailor_api_base_url;
-use crate::domain::services::data::{ActiveLiquidityResponse, LiquidityTick, Token};
+use crate::config::ChainConfig;
+use crate::domain::services::data::{ActiveLiquidityResponse, LiquidityTick, TickDataShape, Token};
 use crate::domain::services::data::{
-    KlineResponse, SailorPoolListResponse, SailorPoolStats, UnifiedPool,
+    KlineResponse, SailorPoolListResponse, SailorPoolStats, SailorTokenInfo, UnifiedPool,
 };
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::upstream_client::UpstreamClient;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use reqwest;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
 use tracing::info;
 
 #[derive(Debug)]
 pub struct SailorDataProvider {
+    chain_id: u64,
+    /// Sailor's name for the chain; pools tagged with another chain are dropped
+    chain: String,
     base_url: String,
-    client: reqwest::Client,
-}
-
-impl Default for SailorDataProvider {
-    fn default() -> Self {
-        Self::new()
-    }
+    client: UpstreamClient,
 }
 
 #[async_trait]
@@ -43,7 +43,7 @@ impl DataProvider for SailorDataProvider {
             limit
         );
 
-        let response = reqwest::get(&url).await?.json::<KlineResponse>().await?;
+        let response = self.client.get(&url).await?.json::<KlineResponse>()?;
 
         let parsed_response = response
             .data
@@ -72,8 +72,8 @@ impl DexProvider for SailorDataProvider {
 
         info!("Fetching Sailor liquidity data from: {}", url);
 
-        let response = self.client.get(&url).send().await?.error_for_status()?;
-        let body_text = response.text().await?;
+        let response = self.client.get(&url).await?.error_for_status()?;
+        let body_text = response.text();
 
         let sailor_response: ActiveLiquidityResponse =
             serde_json::from_str(&body_text).map_err(|e| {
@@ -100,64 +100,76 @@ impl DexProvider for SailorDataProvider {
             status: sailor_response.status,
             data: ticks,
             active_liquidity: Vec::new(), // Clear this since we've moved data to 'data' field
-            ..Default::default()
+            tick_shape: TickDataShape::ActiveLiquidity,
         })
     }
 
     async fn get_pool_list(&self) -> Result<Vec<UnifiedPool>> {
-        let mut unified_pools: Vec<UnifiedPool> = Vec::new();
-
         let url = format!("{}/sailor_poolapi/getPoolList", self.base_url);
-        let response = self.client.get(&url).send().await?;
-        if response.status().is_success() {
-            match response.json::<SailorPoolListResponse>().await {
-                Ok(sailor_data) => {
-                    let transformed = sailor_data
-                        .pool_stats
-                        .into_iter()
-                        .map(SailorDataProvider::transform_sailor_pool)
-                        .filter(|pool| pool.daily_volume.unwrap_or(0.0) > 1000.0)
-                        .filter(|pool| pool.tvl.unwrap_or(0.0) > 1000.0);
-
-                    unified_pools.extend(transformed);
-                }
-                Err(e) => eprintln!("Failed to parse Sailor JSON: {}", e),
-            }
-        }
-        Ok(unified_pools)
+        let response = self.client.get(&url).await?.error_for_status()?;
+
+        let sailor_data = response
+            .json::<SailorPoolListResponse>()
+            .map_err(|e| anyhow::anyhow!("Failed to parse Sailor pool list: {}", e))?;
+
+        Ok(sailor_data
+            .pool_stats
+            .into_iter()
+            .filter(|pool| pool.chain.eq_ignore_ascii_case(&self.chain))
+            .map(|pool| self.transform_sailor_pool(pool))
+            .collect())
     }
 }
 
 impl SailorDataProvider {
-    pub fn new() -> Self {
-        let base_url = sailor_api_base_url();
+    /// `None` when Sailor is not configured on the chain
+    pub fn for_chain(chain: &ChainConfig) -> Option<Self> {
+        let base_url = chain.sailor_api_base_url.clone()?;
 
-        info!("🔗 Using API base URL from .env: {}", base_url);
-        Self {
+        info!(
+            "🔗 Using Sailor API base URL for {}: {}",
+            chain.name, base_url
+        );
+        Some(Self {
+            chain_id: chain.id,
+            chain: chain.sailor_chain.clone(),
             base_url,
-            client: reqwest::Client::builder()
-                .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
-                .build()
-                .expect("Failed to create reqwest client"),
-        }
+            client: UpstreamClient::new(
+                reqwest::Client::builder()
+                    .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/123.0.0.0 Safari/537.36")
+                    .build()
+                    .expect("Failed to create reqwest client"),
+            ),
+        })
     }
 
     /// Converts a pool from the Sailor Finance format to our unified format.
-    pub fn transform_sailor_pool(pool: SailorPoolStats) -> UnifiedPool {
-        let fee_tier_val = match pool.fee_tier.parse::<f64>() {
-            Ok(value) => value / 10000.0,
-            Err(_) => 0.0, // fallback if parsing fails
-        };
-        let fee_tier = if fee_tier_val > 0.0 {
-            fee_tier_val.to_string()
-        } else {
-            "N/A".to_string()
-        };
+    pub fn transform_sailor_pool(&self, pool: SailorPoolStats) -> UnifiedPool {
+        // Sailor reports the raw pool fee, already in hundredths of a bip
+        let fee_tier = pool
+            .fee_tier
+            .parse::<f64>()
+            .ok()
+            .filter(|fee| fee.is_finite() && *fee >= 1.0)
+            .map(|fee| FeeTier::from_hundredths_bip(fee.round() as u32));
+        let fee_tier_val = fee_tier.map_or(0.0, |f| f.percent());
 
         let daily_volume = pool.day.volume.unwrap_or(0.0);
         let tvl = pool.tvl.unwrap_or(0.0);
         let boost_apr = pool.boost_apr.unwrap_or(0.0);
 
+        let usd_price = |token: &SailorTokenInfo| {
+            token
+                .price
+                .as_deref()
+                .and_then(|p| p.parse::<f64>().ok())
+                .filter(|p| *p > 0.0)
+        };
+        let (usd_price0, usd_price1) = (usd_price(&pool.token0), usd_price(&pool.token1));
+        let price = usd_price0
+            .zip(usd_price1)
+            .map(|(price0, price1)| price0 / price1);
+
         let apr = if tvl > 0.0 {
             ((daily_volume * fee_tier_val / tvl) * 365.0) + (boost_apr * 100.0)
         } else {
@@ -165,22 +177,96 @@ impl SailorDataProvider {
         };
 
         UnifiedPool {
+            chain_id: self.chain_id,
             id: pool.id,
             protocol: "Sailor".to_string(),
             token0: Token {
+                chain_id: self.chain_id,
                 address: pool.token0.id,
                 symbol: pool.token0.symbol,
-                decimals: pool.token0.decimals,
+                decimals: pool.token0.decimals.parse().ok(),
+                usd_price: usd_price0,
             },
             token1: Token {
+                chain_id: self.chain_id,
                 address: pool.token1.id,
                 symbol: pool.token1.symbol,
-                decimals: pool.token1.decimals,
+                decimals: pool.token1.decimals.parse().ok(),
+                usd_price: usd_price1,
             },
-            tvl: pool.tvl,
-            daily_volume: pool.day.volume,
+            tvl: pool.tvl.and_then(Decimal::from_f64),
+            daily_volume: pool.day.volume.and_then(Decimal::from_f64),
             apr: Some(apr),
             fee_tier,
+            price,
+            risk: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SEI_CHAIN_ID;
+
+    fn provider() -> SailorDataProvider {
+        SailorDataProvider {
+            chain_id: SEI_CHAIN_ID,
+            chain: "sei".to_string(),
+            base_url: String::new(),
+            client: UpstreamClient::new(reqwest::Client::new()),
         }
     }
+
+    fn sailor_pool(fee_tier: &str, token1_decimals: &str) -> SailorPoolStats {
+        serde_json::from_value(serde_json::json!({
+            "chain": "sei",
+            "feeTier": fee_tier,
+            "id": "0xpool",
+            "protocolVersion": "v3",
+            "totalLiquidity": { "value": "250000" },
+            "txCount": "42",
+            "day": { "volume": 100000.0, "max_price": null, "min_price": null, "price": null },
+            "week": { "volume": null, "max_price": null, "min_price": null, "price": null },
+            "month": { "volume": null, "max_price": null, "min_price": null, "price": null },
+            "boostApr": 0.1,
+            "tvl": 250000.25,
+            "token0": {
+                "id": "0xsei", "symbol": "WSEI", "name": "Wrapped SEI",
+                "decimals": "18", "token0Price": "0.5", "url": ""
+            },
+            "token1": {
+                "id": "0xusdc", "symbol": "USDC", "name": "USD Coin",
+                "decimals": token1_decimals, "token1Price": "1", "url": ""
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_sailor_pool_to_v2() {
+        let pool = provider().transform_sailor_pool(sailor_pool("3000", "6"));
+
+        assert_eq!(pool.chain_id, SEI_CHAIN_ID);
+        assert_eq!(pool.token0.chain_id, SEI_CHAIN_ID);
+        assert_eq!(pool.protocol, "Sailor");
+        assert_eq!(pool.fee_tier, Some(FeeTier::from_hundredths_bip(3000)));
+        assert_eq!(pool.fee_tier.unwrap().tick_spacing(), 60);
+        assert_eq!(pool.token0.decimals, Some(18));
+        assert_eq!(pool.token1.decimals, Some(6));
+        assert_eq!(pool.tvl, Decimal::from_f64(250000.25));
+        assert_eq!(pool.daily_volume, Decimal::from_f64(100000.0));
+        assert_eq!(pool.price, Some(0.5));
+        // 100k * 0.3% / 250k * 365 plus the 10% boost
+        let apr = pool.apr.unwrap();
+        assert!((apr - (100000.0 * 0.3 / 250000.25 * 365.0 + 10.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unusable_sailor_fields_become_none() {
+        let pool = provider().transform_sailor_pool(sailor_pool("N/A", "six"));
+
+        assert_eq!(pool.fee_tier, None);
+        assert_eq!(pool.token1.decimals, None);
+    }
 }
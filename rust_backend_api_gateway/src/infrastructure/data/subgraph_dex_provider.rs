@@ -0,0 +1,587 @@
+use crate::domain::repositories::data_provider::DataProvider;
+use crate::domain::repositories::dex_provider::DexProvider;
+
+use crate::application::dtos::price_history::PricePoint;
+use crate::config::{ChainConfig, SubgraphDexConfig};
+use crate::domain::services::data::{
+    ActiveLiquidityResponse, GraphQlResponse, LiquidityTick, SubgraphBundleData, SubgraphCandle,
+    SubgraphCandlesData, SubgraphPairPoolsData, SubgraphPool, SubgraphPoolRef, SubgraphPoolsData,
+    SubgraphTick, SubgraphTicksData, SubgraphToken, TickDataShape, Token, UnifiedPool,
+};
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::upstream_client::UpstreamClient;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use futures::future::try_join;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+/// Largest `first` graph-node accepts
+const PAGE_SIZE: usize = 1000;
+/// Bounds one listing to 50k pools or ticks
+const MAX_PAGES: usize = 50;
+
+const POOLS_QUERY: &str = r#"
+query Pools($first: Int!, $lastId: String!) {
+  pools(first: $first, where: { id_gt: $lastId }, orderBy: id, orderDirection: asc) {
+    id
+    feeTier
+    token0 { id symbol decimals derivedETH }
+    token1 { id symbol decimals derivedETH }
+    token1Price
+    totalValueLockedUSD
+    poolDayData(first: 1, orderBy: date, orderDirection: desc) { volumeUSD feesUSD }
+  }
+}"#;
+
+const BUNDLE_QUERY: &str = r#"
+query Bundle {
+  bundle(id: "1") { ethPriceUSD }
+}"#;
+
+const TICKS_QUERY: &str = r#"
+query Ticks($pool: String!, $first: Int!, $lastId: String!) {
+  ticks(first: $first, where: { pool: $pool, id_gt: $lastId }, orderBy: id, orderDirection: asc) {
+    id
+    tickIdx
+    liquidityNet
+    price0
+    price1
+  }
+}"#;
+
+const PAIR_POOLS_QUERY: &str = r#"
+query PairPools($token0: [String!]!, $token1: [String!]!) {
+  direct: pools(
+    first: 1
+    orderBy: totalValueLockedUSD
+    orderDirection: desc
+    where: { token0_: { symbol_in: $token0 }, token1_: { symbol_in: $token1 } }
+  ) { id totalValueLockedUSD }
+  inverse: pools(
+    first: 1
+    orderBy: totalValueLockedUSD
+    orderDirection: desc
+    where: { token0_: { symbol_in: $token1 }, token1_: { symbol_in: $token0 } }
+  ) { id totalValueLockedUSD }
+}"#;
+
+/// Pools, ticks and candles of any DEX indexed by a standard Uniswap V3 subgraph
+#[derive(Debug)]
+pub struct SubgraphDexProvider {
+    chain_id: u64,
+    protocol: String,
+    url: String,
+    page_size: usize,
+    client: UpstreamClient,
+}
+
+#[async_trait]
+impl DataProvider for SubgraphDexProvider {
+    /// Candles of the pair's deepest pool; the subgraph keeps hourly and daily candles only
+    async fn get_price_data(
+        &self,
+        token0: &str,
+        token1: &str,
+        interval: u32,
+        limit: u32,
+    ) -> Result<Vec<PricePoint>> {
+        let (operation, entity, start_field) = match interval {
+            60 => ("HourCandles", "poolHourDatas", "periodStartUnix"),
+            1440 => ("DayCandles", "poolDayDatas", "date"),
+            _ => {
+                return Err(anyhow!(
+                    "{} subgraph has no {}-minute candles, only 60 and 1440",
+                    self.protocol,
+                    interval
+                ));
+            }
+        };
+        let (pool, inverted) = self.find_pair_pool(token0, token1).await?;
+
+        let query = format!(
+            r#"
+query {operation}($pool: String!, $first: Int!, $before: Int!) {{
+  candles: {entity}(first: $first, orderBy: {start_field}, orderDirection: desc, where: {{ pool: $pool, {start_field}_lt: $before }}) {{
+    start: {start_field}
+    open
+    high
+    low
+    close
+    volumeUSD
+  }}
+}}"#
+        );
+
+        let limit = limit as usize;
+        let mut candles: Vec<SubgraphCandle> = Vec::new();
+        let mut before = i32::MAX as i64;
+        while candles.len() < limit {
+            let first = self.page_size.min(limit - candles.len());
+            let page = self
+                .query::<SubgraphCandlesData>(
+                    operation,
+                    &query,
+                    json!({ "pool": pool, "first": first, "before": before }),
+                )
+                .await?
+                .candles;
+            let full = page.len() >= first;
+            if let Some(oldest) = page.last() {
+                before = oldest.start;
+            }
+            candles.extend(page);
+            if !full {
+                break;
+            }
+        }
+
+        // Newest first from the subgraph; callers expect oldest first
+        Ok(candles
+            .into_iter()
+            .rev()
+            .filter_map(|candle| candle_to_price_point(candle, inverted))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl DexProvider for SubgraphDexProvider {
+    async fn get_liquidity_data(&self, pool_address: &str) -> Result<ActiveLiquidityResponse> {
+        info!(
+            "Fetching {} liquidity data for {} from the subgraph",
+            self.protocol, pool_address
+        );
+
+        let mut ticks: Vec<LiquidityTick> = self
+            .query_all(
+                "Ticks",
+                TICKS_QUERY,
+                json!({ "pool": pool_address.to_lowercase() }),
+                |data: SubgraphTicksData| data.ticks,
+                |tick: &SubgraphTick| tick.id.clone(),
+            )
+            .await?
+            .into_iter()
+            .map(|tick| LiquidityTick {
+                tick_idx: tick.tick_idx,
+                liquidity_net: tick.liquidity_net,
+                price0: tick.price0,
+                price1: tick.price1,
+            })
+            .collect();
+        // Paged by entity id, which sorts ticks as strings
+        ticks.sort_by_key(|tick| tick.tick_idx.parse::<i64>().unwrap_or_default());
+
+        Ok(ActiveLiquidityResponse {
+            status: "success".to_string(),
+            active_liquidity: Vec::new(),
+            data: ticks,
+            tick_shape: TickDataShape::LiquidityNet,
+        })
+    }
+
+    async fn get_pool_list(&self) -> Result<Vec<UnifiedPool>> {
+        let (pools, bundle) = try_join(
+            self.query_all(
+                "Pools",
+                POOLS_QUERY,
+                json!({}),
+                |data: SubgraphPoolsData| data.pools,
+                |pool: &SubgraphPool| pool.id.clone(),
+            ),
+            self.query::<SubgraphBundleData>("Bundle", BUNDLE_QUERY, json!({})),
+        )
+        .await?;
+        let eth_price_usd = bundle
+            .bundle
+            .and_then(|bundle| parse_positive(&bundle.eth_price_usd));
+
+        Ok(pools
+            .into_iter()
+            .map(|pool| self.transform_subgraph_pool(pool, eth_price_usd))
+            .collect())
+    }
+}
+
+impl SubgraphDexProvider {
+    pub fn for_chain_dex(chain: &ChainConfig, dex: &SubgraphDexConfig) -> Self {
+        info!(
+            "🔗 Using {} subgraph for {}: {}",
+            dex.name, chain.name, dex.url
+        );
+        Self {
+            chain_id: chain.id,
+            protocol: dex.protocol.clone(),
+            url: dex.url.clone(),
+            page_size: PAGE_SIZE,
+            client: UpstreamClient::new(reqwest::Client::new()),
+        }
+    }
+
+    /// POST one GraphQL operation; any reported error fails it, even with partial data
+    async fn query<T: DeserializeOwned>(
+        &self,
+        operation: &str,
+        query: &str,
+        variables: Value,
+    ) -> Result<T> {
+        let request = json!({
+            "operationName": operation,
+            "query": query,
+            "variables": variables,
+        });
+        let response = self
+            .client
+            .post_json(&self.url, &request)
+            .await?
+            .error_for_status()?
+            .json::<GraphQlResponse<T>>()
+            .map_err(|e| {
+                anyhow!(
+                    "Failed to parse {} {} response: {}",
+                    self.protocol,
+                    operation,
+                    e
+                )
+            })?;
+
+        if !response.errors.is_empty() {
+            let messages: Vec<String> = response.errors.into_iter().map(|e| e.message).collect();
+            return Err(anyhow!(
+                "{} subgraph rejected {}: {}",
+                self.protocol,
+                operation,
+                messages.join("; ")
+            ));
+        }
+        response.data.ok_or_else(|| {
+            anyhow!(
+                "{} subgraph returned no data for {}",
+                self.protocol,
+                operation
+            )
+        })
+    }
+
+    /// Every entity of a query taking `$first` and `$lastId`, paged by ascending id
+    async fn query_all<D: DeserializeOwned, T>(
+        &self,
+        operation: &str,
+        query: &str,
+        mut variables: Value,
+        items: impl Fn(D) -> Vec<T>,
+        id: impl Fn(&T) -> String,
+    ) -> Result<Vec<T>> {
+        let mut all = Vec::new();
+        let mut last_id = String::new();
+        for _ in 0..MAX_PAGES {
+            variables["first"] = json!(self.page_size);
+            variables["lastId"] = json!(last_id);
+            let page = items(self.query(operation, query, variables.clone()).await?);
+            let full = page.len() >= self.page_size;
+            if let Some(last) = page.last() {
+                last_id = id(last);
+            }
+            all.extend(page);
+            if !full {
+                return Ok(all);
+            }
+        }
+
+        warn!(
+            "⚠️ {} {} stopped after {} pages of {}",
+            self.protocol, operation, MAX_PAGES, self.page_size
+        );
+        Ok(all)
+    }
+
+    /// Deepest pool of the pair in either token order, and whether the pool lists them reversed
+    async fn find_pair_pool(&self, token0: &str, token1: &str) -> Result<(String, bool)> {
+        let pools = self
+            .query::<SubgraphPairPoolsData>(
+                "PairPools",
+                PAIR_POOLS_QUERY,
+                json!({ "token0": symbol_variants(token0), "token1": symbol_variants(token1) }),
+            )
+            .await?;
+
+        let tvl =
+            |pool: &SubgraphPoolRef| parse_positive(&pool.total_value_locked_usd).unwrap_or(0.0);
+        let direct = pools
+            .direct
+            .into_iter()
+            .next()
+            .map(|pool| (tvl(&pool), pool.id, false));
+        let inverse = pools
+            .inverse
+            .into_iter()
+            .next()
+            .map(|pool| (tvl(&pool), pool.id, true));
+        direct
+            .into_iter()
+            .chain(inverse)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, id, inverted)| (id, inverted))
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} subgraph has no {}/{} pool",
+                    self.protocol,
+                    token0,
+                    token1
+                )
+            })
+    }
+
+    /// Converts a pool from the Uniswap V3 subgraph format to our unified format.
+    pub fn transform_subgraph_pool(
+        &self,
+        pool: SubgraphPool,
+        eth_price_usd: Option<f64>,
+    ) -> UnifiedPool {
+        let fee_tier = pool
+            .fee_tier
+            .parse::<u32>()
+            .ok()
+            .filter(|fee| *fee >= 1)
+            .map(FeeTier::from_hundredths_bip);
+
+        let tvl = parse_positive(&pool.total_value_locked_usd);
+        let day = pool.pool_day_data.first();
+        let daily_volume = day.and_then(|day| day.volume_usd.parse::<f64>().ok());
+        let daily_fees = day.and_then(|day| day.fees_usd.parse::<f64>().ok());
+        let apr = tvl
+            .zip(daily_fees)
+            .map(|(tvl, fees)| fees * 365.0 / tvl * 100.0);
+
+        UnifiedPool {
+            chain_id: self.chain_id,
+            id: pool.id,
+            protocol: self.protocol.clone(),
+            token0: self.transform_subgraph_token(pool.token0, eth_price_usd),
+            token1: self.transform_subgraph_token(pool.token1, eth_price_usd),
+            tvl: tvl.and_then(Decimal::from_f64),
+            daily_volume: daily_volume.and_then(Decimal::from_f64),
+            apr,
+            fee_tier,
+            price: parse_positive(&pool.token1_price),
+            risk: None,
+        }
+    }
+
+    fn transform_subgraph_token(&self, token: SubgraphToken, eth_price_usd: Option<f64>) -> Token {
+        // Priced in the chain's native token, which the bundle prices in USD
+        let usd_price = token
+            .derived_eth
+            .as_deref()
+            .and_then(parse_positive)
+            .zip(eth_price_usd)
+            .map(|(derived, eth)| derived * eth);
+
+        Token {
+            chain_id: self.chain_id,
+            address: token.id,
+            symbol: token.symbol,
+            decimals: token.decimals.parse().ok(),
+            usd_price,
+        }
+    }
+}
+
+/// Subgraph candles price token0 in token1; unless the pool lists the pair reversed, the
+/// requested price is the reciprocal, whose high and low swap places
+fn candle_to_price_point(candle: SubgraphCandle, inverted: bool) -> Option<PricePoint> {
+    let open = parse_positive(&candle.open)?;
+    let high = parse_positive(&candle.high)?;
+    let low = parse_positive(&candle.low)?;
+    let close = parse_positive(&candle.close)?;
+    let (open, high, low, close) = if inverted {
+        (open, high, low, close)
+    } else {
+        (1.0 / open, 1.0 / low, 1.0 / high, 1.0 / close)
+    };
+
+    Some(PricePoint {
+        tick: candle.start,
+        open,
+        high,
+        low,
+        close,
+        volume: candle.volume_usd.parse().ok(),
+    })
+}
+
+/// Symbols are matched exactly by the subgraph, so try the common casings
+fn symbol_variants(symbol: &str) -> Vec<String> {
+    let mut variants = vec![symbol.to_string()];
+    for variant in [symbol.to_uppercase(), symbol.to_lowercase()] {
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+    variants
+}
+
+fn parse_positive(value: &str) -> Option<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SEI_CHAIN_ID;
+    use actix_web::{App, HttpResponse, HttpServer, web};
+
+    /// Serves the responses recorded from a Uniswap V3 subgraph, keyed by operation name and
+    /// the pool, pair and paging cursor asked for
+    fn mock_subgraph() -> String {
+        let fixtures: Value =
+            serde_json::from_str(include_str!("../../../tests/fixtures/subgraph/sei.json"))
+                .unwrap();
+
+        let server = HttpServer::new(move || {
+            let fixtures = fixtures.clone();
+            App::new().route(
+                "/",
+                web::post().to(move |body: web::Json<Value>| {
+                    let fixtures = fixtures.clone();
+                    async move {
+                        let operation = body["operationName"].as_str().unwrap_or_default();
+                        let variables = &body["variables"];
+                        let cursor = ["pool", "token0", "token1", "lastId", "before"]
+                            .into_iter()
+                            .filter_map(|key| match &variables[key] {
+                                Value::String(value) => Some(value.clone()),
+                                Value::Number(value) => Some(value.to_string()),
+                                Value::Array(values) => values[0].as_str().map(str::to_string),
+                                _ => None,
+                            })
+                            .filter(|part| !part.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        match fixtures[operation].get(&cursor) {
+                            Some(response) => HttpResponse::Ok().json(response),
+                            None => HttpResponse::Ok().json(json!({
+                                "data": null,
+                                "errors": [{ "message": format!("no fixture for {} at {:?}", operation, cursor) }]
+                            })),
+                        }
+                    }
+                }),
+            )
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let url = format!("http://{}/", server.addrs()[0]);
+        actix_web::rt::spawn(server.run());
+        url
+    }
+
+    fn provider(url: String) -> SubgraphDexProvider {
+        SubgraphDexProvider {
+            chain_id: SEI_CHAIN_ID,
+            protocol: "Oku".to_string(),
+            url,
+            page_size: 2,
+            client: UpstreamClient::new(reqwest::Client::new()),
+        }
+    }
+
+    #[actix_web::test]
+    async fn pages_through_pools_and_prices_tokens() {
+        let pools = provider(mock_subgraph()).get_pool_list().await.unwrap();
+
+        let ids: Vec<&str> = pools.iter().map(|pool| pool.id.as_str()).collect();
+        assert_eq!(ids, ["0x1a2b", "0x3c4d", "0x5e6f"]);
+
+        let pool = &pools[0];
+        assert_eq!(pool.chain_id, SEI_CHAIN_ID);
+        assert_eq!(pool.protocol, "Oku");
+        assert_eq!(pool.fee_tier, Some(FeeTier::from_hundredths_bip(3000)));
+        assert_eq!(pool.token0.symbol, "WSEI");
+        assert_eq!(pool.token0.decimals, Some(18));
+        assert_eq!(pool.token1.decimals, Some(6));
+        assert_eq!(pool.price, Some(0.25));
+        assert_eq!(pool.tvl, Decimal::from_f64(500000.0));
+        assert_eq!(pool.daily_volume, Decimal::from_f64(120000.0));
+        // derivedETH 1 and 4 against a native price of 0.25
+        assert_eq!(pool.token0.usd_price, Some(0.25));
+        assert_eq!(pool.token1.usd_price, Some(1.0));
+        // $360 of fees a day on $500k
+        assert!((pool.apr.unwrap() - 360.0 * 365.0 / 500000.0 * 100.0).abs() < 1e-9);
+
+        // No day data yet: volume and APR are unknown rather than zero
+        assert_eq!(pools[2].daily_volume, None);
+        assert_eq!(pools[2].apr, None);
+    }
+
+    #[actix_web::test]
+    async fn pages_through_ticks_in_tick_order() {
+        let liquidity = provider(mock_subgraph())
+            .get_liquidity_data("0x1A2B")
+            .await
+            .unwrap();
+
+        let ticks: Vec<&str> = liquidity.data.iter().map(|t| t.tick_idx.as_str()).collect();
+        assert_eq!(ticks, ["-887220", "-600", "60"]);
+        assert_eq!(liquidity.data[1].liquidity_net, "-3500");
+    }
+
+    #[actix_web::test]
+    async fn inverts_candles_into_the_requested_pair_oldest_first() {
+        let candles = provider(mock_subgraph())
+            .get_price_data("WSEI", "USDC", 60, 3)
+            .await
+            .unwrap();
+
+        let ticks: Vec<i64> = candles.iter().map(|c| c.tick).collect();
+        assert_eq!(ticks, [1760774400, 1760778000, 1760781600]);
+
+        // Recorded as WSEI per USDC; high and low swap when inverted
+        let last = &candles[2];
+        assert_eq!(last.open, 0.25);
+        assert_eq!(last.high, 0.32);
+        assert_eq!(last.low, 0.2);
+        assert_eq!(last.close, 0.32);
+        assert_eq!(last.volume, Some(5000.0));
+    }
+
+    #[actix_web::test]
+    async fn keeps_candles_of_a_reversed_pool_as_recorded() {
+        let candles = provider(mock_subgraph())
+            .get_price_data("USDC", "WSEI", 1440, 1)
+            .await
+            .unwrap();
+
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].close, 4.0);
+    }
+
+    #[actix_web::test]
+    async fn rejects_intervals_the_subgraph_does_not_keep() {
+        let error = provider(mock_subgraph())
+            .get_price_data("WSEI", "USDC", 5, 10)
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("5-minute"));
+    }
+
+    #[actix_web::test]
+    async fn surfaces_graphql_errors() {
+        let error = provider(mock_subgraph())
+            .get_price_data("WSEI", "UNKNOWN", 60, 10)
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("no fixture"));
+    }
+}
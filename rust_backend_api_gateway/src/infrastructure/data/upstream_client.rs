@@ -0,0 +1,209 @@
+use crate::config::{UpstreamMode, upstream_fixtures_dir, upstream_mode};
+
+use anyhow::{Result, anyhow};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Longest fixture file name before it is shortened to a prefix and a hash
+const MAX_FIXTURE_NAME: usize = 120;
+
+/// HTTP client every REST and GraphQL provider goes through, so upstream responses can be
+/// recorded to fixtures and replayed offline (UPSTREAM_MODE)
+#[derive(Debug, Clone)]
+pub struct UpstreamClient {
+    client: reqwest::Client,
+    mode: UpstreamMode,
+    fixtures_dir: PathBuf,
+}
+
+/// Status and body of an upstream response, live or replayed
+#[derive(Debug)]
+pub struct UpstreamResponse {
+    url: String,
+    status: StatusCode,
+    body: String,
+}
+
+/// One recorded exchange; JSON bodies are stored as JSON so fixtures stay readable
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<Value>,
+    status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+impl UpstreamClient {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            mode: upstream_mode(),
+            fixtures_dir: PathBuf::from(upstream_fixtures_dir()),
+        }
+    }
+
+    pub async fn get(&self, url: &str) -> Result<UpstreamResponse> {
+        self.send("GET", url, None).await
+    }
+
+    pub async fn post_json(&self, url: &str, request: &Value) -> Result<UpstreamResponse> {
+        self.send("POST", url, Some(request)).await
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        url: &str,
+        request: Option<&Value>,
+    ) -> Result<UpstreamResponse> {
+        let path = self.fixture_path(method, url, request)?;
+        if self.mode == UpstreamMode::Replay {
+            return replay(&path, url).await;
+        }
+
+        let response = self.fetch(method, url, request).await?;
+        if self.mode == UpstreamMode::Record {
+            record(&path, method, request, &response).await?;
+        }
+        Ok(response)
+    }
+
+    async fn fetch(
+        &self,
+        method: &str,
+        url: &str,
+        request: Option<&Value>,
+    ) -> Result<UpstreamResponse> {
+        let builder = match request {
+            Some(request) => self.client.post(url).json(request),
+            None if method == "GET" => self.client.get(url),
+            None => return Err(anyhow!("Unsupported upstream method {}", method)),
+        };
+        let response = builder.send().await?;
+
+        Ok(UpstreamResponse {
+            url: url.to_string(),
+            status: response.status(),
+            body: response.text().await?,
+        })
+    }
+
+    /// `<dir>/<host>/<METHOD>_<path and query>.json`, with a hash of the request body for POSTs
+    /// and of the whole name when it is too long for a file name
+    fn fixture_path(&self, method: &str, url: &str, request: Option<&Value>) -> Result<PathBuf> {
+        let parsed = reqwest::Url::parse(url)?;
+        let host = match parsed.port() {
+            Some(port) => format!("{}_{}", parsed.host_str().unwrap_or("unknown"), port),
+            None => parsed.host_str().unwrap_or("unknown").to_string(),
+        };
+
+        let mut name = format!("{}{}", method, parsed.path());
+        if let Some(query) = parsed.query() {
+            name = format!("{}?{}", name, query);
+        }
+        let mut name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if let Some(request) = request {
+            name = format!("{}_{:016x}", name, fnv1a(&request.to_string()));
+        }
+        if name.len() > MAX_FIXTURE_NAME {
+            name = format!("{}_{:016x}", &name[..MAX_FIXTURE_NAME - 17], fnv1a(&name));
+        }
+
+        Ok(self.fixtures_dir.join(host).join(format!("{}.json", name)))
+    }
+}
+
+impl UpstreamResponse {
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn error_for_status(self) -> Result<Self> {
+        if self.status.is_success() {
+            Ok(self)
+        } else {
+            Err(anyhow!("HTTP status {} from {}", self.status, self.url))
+        }
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_str(&self.body)
+            .map_err(|e| anyhow!("Failed to parse response from {}: {}", self.url, e))
+    }
+
+    pub fn text(self) -> String {
+        self.body
+    }
+}
+
+async fn replay(path: &Path, url: &str) -> Result<UpstreamResponse> {
+    let contents = tokio::fs::read_to_string(path).await.map_err(|e| {
+        anyhow!(
+            "No recorded response for {} at {}: {}",
+            url,
+            path.display(),
+            e
+        )
+    })?;
+    let fixture: Fixture = serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Invalid fixture {}: {}", path.display(), e))?;
+    debug!("📼 Replaying {} from {}", url, path.display());
+
+    Ok(UpstreamResponse {
+        url: url.to_string(),
+        status: StatusCode::from_u16(fixture.status)?,
+        body: match (fixture.body, fixture.text) {
+            (Some(body), _) => body.to_string(),
+            (None, text) => text.unwrap_or_default(),
+        },
+    })
+}
+
+async fn record(
+    path: &Path,
+    method: &str,
+    request: Option<&Value>,
+    response: &UpstreamResponse,
+) -> Result<()> {
+    let body = serde_json::from_str::<Value>(&response.body).ok();
+    let fixture = Fixture {
+        method: method.to_string(),
+        url: response.url.clone(),
+        request: request.cloned(),
+        status: response.status.as_u16(),
+        text: body.is_none().then(|| response.body.clone()),
+        body,
+    };
+
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    tokio::fs::write(path, serde_json::to_string_pretty(&fixture)?).await?;
+    info!("📼 Recorded {} to {}", response.url, path.display());
+    Ok(())
+}
+
+/// Stable across builds and platforms, unlike std's hasher
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
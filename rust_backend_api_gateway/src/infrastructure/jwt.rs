@@ -7,13 +7,15 @@ use serde::Serialize;
 #[derive(Serialize)]
 pub struct Claims {
     pub sub: String,
+    /// EVM chain id the session was opened on
+    pub chain_id: u64,
     pub exp: usize,
 }
 
 pub struct Hs256Jwt;
 
 impl JwtEncoder for Hs256Jwt {
-    fn encode(&self, subject: &str) -> Result<String, String> {
+    fn encode(&self, subject: &str, chain_id: u64) -> Result<String, String> {
         let exp = Utc::now()
             .checked_add_signed(Duration::hours(2))
             .unwrap()
@@ -21,6 +23,7 @@ impl JwtEncoder for Hs256Jwt {
 
         let claims = Claims {
             sub: subject.to_string(),
+            chain_id,
             exp,
         };
 
@@ -1,3 +1,5 @@
 pub mod data;
+pub mod entities;
 pub mod jwt;
+pub mod repositories;
 pub mod wallet;
@@ -0,0 +1,461 @@
+use crate::domain::repositories::position_repository::PositionRepository;
+use crate::domain::services::position::{
+    LiquidityEvent, LiquidityEventKind, NewPosition, POSITION_CLOSED, POSITION_OPEN, Position,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::DbErr;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tracing::warn;
+
+/// Positions kept in process, for tests and running without Postgres
+#[derive(Default)]
+pub struct InMemoryPositionRepository {
+    state: Mutex<State>,
+}
+
+#[derive(Default, Clone)]
+struct State {
+    wallets: HashSet<String>,
+    positions: Vec<Position>,
+    last_id: i64,
+    cursors: HashMap<(u64, String), u64>,
+}
+
+impl State {
+    fn apply_mint(&mut self, event: &LiquidityEvent) {
+        let recorded = self.positions.iter_mut().find(|position| {
+            position.chain_id == event.chain_id as i64
+                && position.tx_hash.as_deref() == Some(event.tx_hash.as_str())
+                && position.pool_address.as_deref() == Some(event.pool_address.as_str())
+                && position.tick_lower == Some(event.tick_lower)
+                && position.tick_upper == Some(event.tick_upper)
+                && position
+                    .log_index
+                    .is_none_or(|log_index| log_index == event.log_index as i64)
+        });
+        if let Some(position) = recorded {
+            position.token0_address = position
+                .token0_address
+                .take()
+                .or_else(|| event.token0_address.clone());
+            position.token1_address = position
+                .token1_address
+                .take()
+                .or_else(|| event.token1_address.clone());
+            position.nft_token_id = event
+                .nft_token_id
+                .clone()
+                .or_else(|| position.nft_token_id.take());
+            position.liquidity = Some(event.liquidity.clone());
+            position.amount0 = Some(event.amount0.clone());
+            position.amount1 = Some(event.amount1.clone());
+            position.block_number = Some(event.block_number as i64);
+            position.log_index = Some(event.log_index as i64);
+            return;
+        }
+
+        self.wallets.insert(event.owner.clone());
+        self.last_id += 1;
+        self.positions.push(Position {
+            id: self.last_id,
+            chain_id: event.chain_id as i64,
+            pb_key: event.owner.clone(),
+            pool_address: Some(event.pool_address.clone()),
+            protocol: None,
+            token0_address: event.token0_address.clone(),
+            token1_address: event.token1_address.clone(),
+            tick_lower: Some(event.tick_lower),
+            tick_upper: Some(event.tick_upper),
+            liquidity: Some(event.liquidity.clone()),
+            amount0: Some(event.amount0.clone()),
+            amount1: Some(event.amount1.clone()),
+            nft_token_id: event.nft_token_id.clone(),
+            tx_hash: Some(event.tx_hash.clone()),
+            price_lower: None,
+            price_upper: None,
+            value_usd: None,
+            legacy_trans_id: None,
+            status: POSITION_OPEN.to_string(),
+            created_at: event.block_time,
+            closed_at: None,
+            block_number: Some(event.block_number as i64),
+            log_index: Some(event.log_index as i64),
+        });
+    }
+
+    fn apply_burn(&mut self, event: &LiquidityEvent) -> Result<(), DbErr> {
+        let mut remaining = to_liquidity(&event.liquidity)?;
+        let mut open: Vec<&mut Position> = self
+            .positions
+            .iter_mut()
+            .filter(|position| {
+                position.chain_id == event.chain_id as i64
+                    && position.status == POSITION_OPEN
+                    && position.nft_token_id.is_none()
+                    && position.pool_address.as_deref() == Some(event.pool_address.as_str())
+                    && position.tick_lower == Some(event.tick_lower)
+                    && position.tick_upper == Some(event.tick_upper)
+            })
+            .collect();
+        open.sort_by_key(|position| {
+            (
+                !position.pb_key.eq_ignore_ascii_case(&event.owner),
+                position.created_at,
+                position.id,
+            )
+        });
+
+        for position in open {
+            if remaining == 0 {
+                break;
+            }
+            let liquidity = to_liquidity(position.liquidity.as_deref().unwrap_or("0"))?;
+            let taken = liquidity.min(remaining);
+            remaining -= taken;
+            position.liquidity = Some((liquidity - taken).to_string());
+            if taken == liquidity {
+                position.status = POSITION_CLOSED.to_string();
+                position.closed_at = Some(event.block_time);
+            }
+        }
+        if remaining > 0 {
+            warn!(
+                "⚠️ Burn in {} took {} more liquidity than indexed for {}",
+                event.tx_hash, remaining, event.owner
+            );
+        }
+        Ok(())
+    }
+}
+
+fn to_liquidity(value: &str) -> Result<u128, DbErr> {
+    value
+        .parse()
+        .map_err(|e| DbErr::Type(format!("Invalid liquidity {}: {}", value, e)))
+}
+
+impl InMemoryPositionRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wallets registered by stored positions
+    pub fn wallets(&self) -> Vec<String> {
+        let state = self.state.lock().expect("position state lock poisoned");
+        let mut wallets: Vec<String> = state.wallets.iter().cloned().collect();
+        wallets.sort();
+        wallets
+    }
+}
+
+#[async_trait]
+impl PositionRepository for InMemoryPositionRepository {
+    async fn add(&self, position: NewPosition) -> Result<Position, DbErr> {
+        let mut state = self.state.lock().expect("position state lock poisoned");
+        state.wallets.insert(position.pb_key.clone());
+        state.last_id += 1;
+
+        let created = Position {
+            id: state.last_id,
+            chain_id: position.chain_id as i64,
+            pb_key: position.pb_key,
+            pool_address: Some(position.pool_address),
+            protocol: position.protocol,
+            token0_address: Some(position.token0_address),
+            token1_address: Some(position.token1_address),
+            tick_lower: Some(position.tick_lower),
+            tick_upper: Some(position.tick_upper),
+            liquidity: Some(position.liquidity),
+            amount0: Some(position.amount0),
+            amount1: Some(position.amount1),
+            nft_token_id: position.nft_token_id,
+            tx_hash: Some(position.tx_hash),
+            price_lower: None,
+            price_upper: None,
+            value_usd: None,
+            legacy_trans_id: None,
+            status: POSITION_OPEN.to_string(),
+            created_at: Utc::now(),
+            closed_at: None,
+            block_number: None,
+            log_index: None,
+        };
+        state.positions.push(created.clone());
+        Ok(created)
+    }
+
+    async fn delete(&self, pb_key: &str, id: i64) -> Result<(), DbErr> {
+        let mut state = self.state.lock().expect("position state lock poisoned");
+        let before = state.positions.len();
+        state
+            .positions
+            .retain(|position| !(position.pb_key == pb_key && position.id == id));
+
+        if state.positions.len() == before {
+            return Err(DbErr::RecordNotFound("Position not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn list_for_wallet(&self, pb_key: &str) -> Result<Vec<Position>, DbErr> {
+        let state = self.state.lock().expect("position state lock poisoned");
+        let mut positions: Vec<Position> = state
+            .positions
+            .iter()
+            .filter(|position| position.pb_key == pb_key)
+            .cloned()
+            .collect();
+        positions.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(positions)
+    }
+
+    async fn indexed_block(&self, chain_id: u64, contract: &str) -> Result<Option<u64>, DbErr> {
+        let state = self.state.lock().expect("position state lock poisoned");
+        Ok(state
+            .cursors
+            .get(&(chain_id, contract.to_string()))
+            .copied())
+    }
+
+    async fn apply_liquidity_events(
+        &self,
+        chain_id: u64,
+        contract: &str,
+        events: &[LiquidityEvent],
+        to_block: u64,
+    ) -> Result<(), DbErr> {
+        let mut state = self.state.lock().expect("position state lock poisoned");
+        // Applied to a copy, kept only when every event succeeds
+        let mut next = state.clone();
+        for event in events {
+            match event.kind {
+                LiquidityEventKind::Minted => next.apply_mint(event),
+                LiquidityEventKind::Burned => next.apply_burn(event)?,
+            }
+        }
+        next.cursors
+            .insert((chain_id, contract.to_string()), to_block);
+        *state = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_position(pb_key: &str, tick_lower: i32) -> NewPosition {
+        NewPosition {
+            chain_id: 1329,
+            pb_key: pb_key.to_string(),
+            pool_address: "0x882f62fe8e9594470d1da0f70bc85096f6c60423".to_string(),
+            protocol: Some("DragonSwap".to_string()),
+            token0_address: "0xe30fedd158a2e3b13e9badaeabafc5516e95e8c7".to_string(),
+            token1_address: "0x3894085ef7ff0f0aedf52e2a2704928d1ec074f1".to_string(),
+            tick_lower,
+            tick_upper: tick_lower + 1200,
+            liquidity: "340282366920938463463374607431768211455".to_string(),
+            amount0: "1000000000000000000".to_string(),
+            amount1: "250000".to_string(),
+            nft_token_id: None,
+            tx_hash: format!("0x{}", "ab".repeat(32)),
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_a_wallets_positions_newest_first() {
+        let repository = InMemoryPositionRepository::new();
+        let first = repository.add(new_position("alice", -600)).await.unwrap();
+        let second = repository.add(new_position("alice", 0)).await.unwrap();
+        repository.add(new_position("bob", 600)).await.unwrap();
+
+        let positions = repository.list_for_wallet("alice").await.unwrap();
+        assert_eq!(positions, [second, first]);
+        assert_eq!(positions[0].status, POSITION_OPEN);
+        assert_eq!(
+            positions[0].liquidity.as_deref(),
+            Some("340282366920938463463374607431768211455")
+        );
+        assert_eq!(repository.wallets(), ["alice", "bob"]);
+    }
+
+    #[tokio::test]
+    async fn deletes_only_the_wallets_own_positions() {
+        let repository = InMemoryPositionRepository::new();
+        let position = repository.add(new_position("alice", 0)).await.unwrap();
+
+        assert!(matches!(
+            repository.delete("bob", position.id).await,
+            Err(DbErr::RecordNotFound(_))
+        ));
+        repository.delete("alice", position.id).await.unwrap();
+        assert!(
+            repository
+                .list_for_wallet("alice")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn liquidity_event(
+        kind: LiquidityEventKind,
+        liquidity: &str,
+        log_index: u64,
+    ) -> LiquidityEvent {
+        LiquidityEvent {
+            kind,
+            chain_id: 1329,
+            pool_address: "0x882f62fe8e9594470d1da0f70bc85096f6c60423".to_string(),
+            owner: "0x79daa774769334af120f6caa57e828fbbf56b39a".to_string(),
+            token0_address: Some("0xe30fedd158a2e3b13e9badaeabafc5516e95e8c7".to_string()),
+            token1_address: Some("0x3894085ef7ff0f0aedf52e2a2704928d1ec074f1".to_string()),
+            tick_lower: -600,
+            tick_upper: 600,
+            liquidity: liquidity.to_string(),
+            amount0: "1000".to_string(),
+            amount1: "2000".to_string(),
+            nft_token_id: None,
+            tx_hash: format!("0x{}", "ab".repeat(32)),
+            block_number: 42,
+            log_index,
+            block_time: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn completes_recorded_positions_from_mint_logs() {
+        let repository = InMemoryPositionRepository::new();
+        let mut recorded = new_position("0x79dAa774769334aF120f6CAA57E828FBBF56b39a", -600);
+        recorded.tick_upper = 600;
+        recorded.liquidity = "1".to_string();
+        let recorded = repository.add(recorded).await.unwrap();
+
+        let mint = liquidity_event(LiquidityEventKind::Minted, "500", 3);
+        for to_block in [42, 43] {
+            // Replaying the same log leaves a single position
+            repository
+                .apply_liquidity_events(1329, "0xmanager", std::slice::from_ref(&mint), to_block)
+                .await
+                .unwrap();
+        }
+
+        let positions = repository.list_for_wallet(&recorded.pb_key).await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].id, recorded.id);
+        assert_eq!(positions[0].liquidity.as_deref(), Some("500"));
+        assert_eq!(positions[0].log_index, Some(3));
+        assert_eq!(
+            repository.indexed_block(1329, "0xmanager").await.unwrap(),
+            Some(43)
+        );
+        assert_eq!(
+            repository.indexed_block(1328, "0xmanager").await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn burns_take_liquidity_from_the_oldest_position_first() {
+        let repository = InMemoryPositionRepository::new();
+        let events = [
+            liquidity_event(LiquidityEventKind::Minted, "300", 1),
+            liquidity_event(LiquidityEventKind::Minted, "200", 2),
+            liquidity_event(LiquidityEventKind::Burned, "400", 3),
+        ];
+        repository
+            .apply_liquidity_events(1329, "0xmanager", &events, 42)
+            .await
+            .unwrap();
+
+        let owner = &events[0].owner;
+        let mut positions = repository.list_for_wallet(owner).await.unwrap();
+        positions.sort_by_key(|position| position.id);
+        assert_eq!(positions[0].status, POSITION_CLOSED);
+        assert_eq!(positions[0].liquidity.as_deref(), Some("0"));
+        assert!(positions[0].closed_at.is_some());
+        assert_eq!(positions[1].status, POSITION_OPEN);
+        assert_eq!(positions[1].liquidity.as_deref(), Some("100"));
+        assert_eq!(repository.wallets(), [owner.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn burns_match_the_range_and_skip_nfpm_positions() {
+        let repository = InMemoryPositionRepository::new();
+        let mut nfpm_mint = liquidity_event(LiquidityEventKind::Minted, "1000", 1);
+        nfpm_mint.owner = "0xnft".to_string();
+        nfpm_mint.nft_token_id = Some("7".to_string());
+        let mut other_mint = liquidity_event(LiquidityEventKind::Minted, "300", 2);
+        other_mint.owner = "0xother".to_string();
+        let own_mint = liquidity_event(LiquidityEventKind::Minted, "200", 3);
+        let mut other_range = liquidity_event(LiquidityEventKind::Minted, "500", 4);
+        other_range.tick_upper = 1200;
+        let mut own_burn = liquidity_event(LiquidityEventKind::Burned, "150", 5);
+        own_burn.owner = own_mint.owner.to_uppercase();
+        let mut burn = liquidity_event(LiquidityEventKind::Burned, "200", 6);
+        burn.owner = "0xburner".to_string();
+
+        repository
+            .apply_liquidity_events(
+                1329,
+                "0xmanager",
+                &[
+                    nfpm_mint,
+                    other_mint,
+                    own_mint.clone(),
+                    other_range,
+                    own_burn,
+                    burn,
+                ],
+                42,
+            )
+            .await
+            .unwrap();
+
+        // The NFT keeps its liquidity
+        let nft = repository.list_for_wallet("0xnft").await.unwrap();
+        assert_eq!(nft[0].nft_token_id.as_deref(), Some("7"));
+        assert_eq!(nft[0].status, POSITION_OPEN);
+        assert_eq!(nft[0].liquidity.as_deref(), Some("1000"));
+
+        // The owner's burn takes from its own mint before the older one of another wallet
+        let mut own = repository.list_for_wallet(&own_mint.owner).await.unwrap();
+        own.sort_by_key(|position| position.id);
+        assert_eq!(own[0].liquidity.as_deref(), Some("50"));
+        assert_eq!(own[1].tick_upper, Some(1200));
+        assert_eq!(own[1].liquidity.as_deref(), Some("500"));
+
+        // A wallet with no mint in the range still burns from it, oldest first
+        let other = repository.list_for_wallet("0xother").await.unwrap();
+        assert_eq!(other[0].status, POSITION_OPEN);
+        assert_eq!(other[0].liquidity.as_deref(), Some("100"));
+    }
+
+    #[tokio::test]
+    async fn applies_nothing_when_an_event_fails() {
+        let repository = InMemoryPositionRepository::new();
+        let events = [
+            liquidity_event(LiquidityEventKind::Minted, "300", 1),
+            liquidity_event(LiquidityEventKind::Burned, "not a number", 2),
+        ];
+        assert!(
+            repository
+                .apply_liquidity_events(1329, "0xmanager", &events, 42)
+                .await
+                .is_err()
+        );
+        assert!(
+            repository
+                .list_for_wallet(&events[0].owner)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            repository.indexed_block(1329, "0xmanager").await.unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod in_memory_position_repository;
+pub mod sea_orm_position_repository;
+
+pub use in_memory_position_repository::*;
+pub use sea_orm_position_repository::*;
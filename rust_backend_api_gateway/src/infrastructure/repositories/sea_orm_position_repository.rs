@@ -0,0 +1,311 @@
+use crate::domain::repositories::position_repository::PositionRepository;
+use crate::domain::services::position::{
+    LiquidityEvent, LiquidityEventKind, NewPosition, POSITION_CLOSED, POSITION_OPEN, Position,
+};
+use crate::infrastructure::entities::{indexer_cursors, positions, wallets};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::prelude::BigDecimal;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, IntoActiveModel, NotSet, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use std::str::FromStr;
+use tracing::warn;
+
+/// Positions stored in the `positions` and `wallets` tables
+pub struct SeaOrmPositionRepository {
+    db: DatabaseConnection,
+}
+
+impl SeaOrmPositionRepository {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl PositionRepository for SeaOrmPositionRepository {
+    async fn add(&self, position: NewPosition) -> Result<Position, DbErr> {
+        let txn = self.db.begin().await?;
+        register_wallet(&txn, &position.pb_key).await?;
+
+        let created = positions::ActiveModel {
+            id: NotSet,
+            chain_id: Set(position.chain_id as i64),
+            pb_key: Set(position.pb_key),
+            pool_address: Set(Some(position.pool_address)),
+            protocol: Set(position.protocol),
+            token0_address: Set(Some(position.token0_address)),
+            token1_address: Set(Some(position.token1_address)),
+            tick_lower: Set(Some(position.tick_lower)),
+            tick_upper: Set(Some(position.tick_upper)),
+            liquidity: Set(Some(to_numeric(&position.liquidity)?)),
+            amount0: Set(Some(to_numeric(&position.amount0)?)),
+            amount1: Set(Some(to_numeric(&position.amount1)?)),
+            nft_token_id: Set(position
+                .nft_token_id
+                .as_deref()
+                .map(to_numeric)
+                .transpose()?),
+            tx_hash: Set(Some(position.tx_hash)),
+            price_lower: Set(None),
+            price_upper: Set(None),
+            value_usd: Set(None),
+            legacy_trans_id: Set(None),
+            status: Set(POSITION_OPEN.to_string()),
+            created_at: Set(Utc::now().fixed_offset()),
+            closed_at: Set(None),
+            block_number: Set(None),
+            log_index: Set(None),
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(to_position(created))
+    }
+
+    async fn delete(&self, pb_key: &str, id: i64) -> Result<(), DbErr> {
+        let result = positions::Entity::delete_many()
+            .filter(positions::Column::PbKey.eq(pb_key))
+            .filter(positions::Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound("Position not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn list_for_wallet(&self, pb_key: &str) -> Result<Vec<Position>, DbErr> {
+        Ok(positions::Entity::find()
+            .filter(positions::Column::PbKey.eq(pb_key))
+            .order_by_desc(positions::Column::CreatedAt)
+            .order_by_desc(positions::Column::Id)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(to_position)
+            .collect())
+    }
+
+    async fn indexed_block(&self, chain_id: u64, contract: &str) -> Result<Option<u64>, DbErr> {
+        Ok(
+            indexer_cursors::Entity::find_by_id((chain_id as i64, contract.to_string()))
+                .one(&self.db)
+                .await?
+                .map(|cursor| cursor.block_number as u64),
+        )
+    }
+
+    async fn apply_liquidity_events(
+        &self,
+        chain_id: u64,
+        contract: &str,
+        events: &[LiquidityEvent],
+        to_block: u64,
+    ) -> Result<(), DbErr> {
+        let txn = self.db.begin().await?;
+        for event in events {
+            match event.kind {
+                LiquidityEventKind::Minted => apply_mint(&txn, event).await?,
+                LiquidityEventKind::Burned => apply_burn(&txn, event).await?,
+            }
+        }
+
+        indexer_cursors::Entity::insert(indexer_cursors::ActiveModel {
+            chain_id: Set(chain_id as i64),
+            contract_address: Set(contract.to_string()),
+            block_number: Set(to_block as i64),
+            updated_at: Set(Utc::now().fixed_offset()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                indexer_cursors::Column::ChainId,
+                indexer_cursors::Column::ContractAddress,
+            ])
+            .update_columns([
+                indexer_cursors::Column::BlockNumber,
+                indexer_cursors::Column::UpdatedAt,
+            ])
+            .to_owned(),
+        )
+        .exec(&txn)
+        .await?;
+
+        txn.commit().await
+    }
+}
+
+async fn register_wallet<C: ConnectionTrait>(db: &C, pb_key: &str) -> Result<(), DbErr> {
+    if wallets::Entity::find_by_id(pb_key.to_string())
+        .one(db)
+        .await?
+        .is_none()
+    {
+        wallets::ActiveModel {
+            pb_key: Set(pb_key.to_string()),
+            email: Set(None),
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+fn in_range(event: &LiquidityEvent) -> Condition {
+    Condition::all()
+        .add(positions::Column::ChainId.eq(event.chain_id as i64))
+        .add(positions::Column::PoolAddress.eq(event.pool_address.as_str()))
+        .add(positions::Column::TickLower.eq(event.tick_lower))
+        .add(positions::Column::TickUpper.eq(event.tick_upper))
+}
+
+async fn apply_mint<C: ConnectionTrait>(db: &C, event: &LiquidityEvent) -> Result<(), DbErr> {
+    let recorded = positions::Entity::find()
+        .filter(in_range(event))
+        .filter(positions::Column::TxHash.eq(event.tx_hash.as_str()))
+        .filter(
+            Condition::any()
+                .add(positions::Column::LogIndex.is_null())
+                .add(positions::Column::LogIndex.eq(event.log_index as i64)),
+        )
+        .order_by_asc(positions::Column::Id)
+        .one(db)
+        .await?;
+
+    if let Some(position) = recorded {
+        let token0_address = position
+            .token0_address
+            .clone()
+            .or_else(|| event.token0_address.clone());
+        let token1_address = position
+            .token1_address
+            .clone()
+            .or_else(|| event.token1_address.clone());
+        let nft_token_id = match &event.nft_token_id {
+            Some(token_id) => Some(to_numeric(token_id)?),
+            None => position.nft_token_id.clone(),
+        };
+        let mut position = position.into_active_model();
+        position.token0_address = Set(token0_address);
+        position.token1_address = Set(token1_address);
+        position.nft_token_id = Set(nft_token_id);
+        position.liquidity = Set(Some(to_numeric(&event.liquidity)?));
+        position.amount0 = Set(Some(to_numeric(&event.amount0)?));
+        position.amount1 = Set(Some(to_numeric(&event.amount1)?));
+        position.block_number = Set(Some(event.block_number as i64));
+        position.log_index = Set(Some(event.log_index as i64));
+        position.update(db).await?;
+        return Ok(());
+    }
+
+    register_wallet(db, &event.owner).await?;
+    positions::ActiveModel {
+        id: NotSet,
+        chain_id: Set(event.chain_id as i64),
+        pb_key: Set(event.owner.clone()),
+        pool_address: Set(Some(event.pool_address.clone())),
+        protocol: Set(None),
+        token0_address: Set(event.token0_address.clone()),
+        token1_address: Set(event.token1_address.clone()),
+        tick_lower: Set(Some(event.tick_lower)),
+        tick_upper: Set(Some(event.tick_upper)),
+        liquidity: Set(Some(to_numeric(&event.liquidity)?)),
+        amount0: Set(Some(to_numeric(&event.amount0)?)),
+        amount1: Set(Some(to_numeric(&event.amount1)?)),
+        nft_token_id: Set(event.nft_token_id.as_deref().map(to_numeric).transpose()?),
+        tx_hash: Set(Some(event.tx_hash.clone())),
+        price_lower: Set(None),
+        price_upper: Set(None),
+        value_usd: Set(None),
+        legacy_trans_id: Set(None),
+        status: Set(POSITION_OPEN.to_string()),
+        created_at: Set(event.block_time.fixed_offset()),
+        closed_at: Set(None),
+        block_number: Set(Some(event.block_number as i64)),
+        log_index: Set(Some(event.log_index as i64)),
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+async fn apply_burn<C: ConnectionTrait>(db: &C, event: &LiquidityEvent) -> Result<(), DbErr> {
+    // The pool holds one position per range for the LiquidityManager, so a burn can take from
+    // any wallet's mint in it. NFPM mints sit under their NFT and are never burned here.
+    let mut open = positions::Entity::find()
+        .filter(in_range(event))
+        .filter(positions::Column::Status.eq(POSITION_OPEN))
+        .filter(positions::Column::NftTokenId.is_null())
+        .order_by_asc(positions::Column::CreatedAt)
+        .order_by_asc(positions::Column::Id)
+        .all(db)
+        .await?;
+    // The burner's own mints go first. Positions recorded by the frontend keep the wallet key as
+    // it was sent.
+    open.sort_by_key(|position| !position.pb_key.eq_ignore_ascii_case(&event.owner));
+
+    let zero = BigDecimal::from(0);
+    let mut remaining = to_numeric(&event.liquidity)?;
+    for position in open {
+        if remaining == zero {
+            break;
+        }
+        let liquidity = position.liquidity.clone().unwrap_or_else(|| zero.clone());
+        let taken = liquidity.clone().min(remaining.clone());
+        remaining -= &taken;
+
+        let closed = taken == liquidity;
+        let mut position = position.into_active_model();
+        position.liquidity = Set(Some(liquidity - taken));
+        if closed {
+            position.status = Set(POSITION_CLOSED.to_string());
+            position.closed_at = Set(Some(event.block_time.fixed_offset()));
+        }
+        position.update(db).await?;
+    }
+    if remaining > zero {
+        warn!(
+            "⚠️ Burn in {} took {} more liquidity than indexed for {}",
+            event.tx_hash, remaining, event.owner
+        );
+    }
+    Ok(())
+}
+
+fn to_numeric(value: &str) -> Result<BigDecimal, DbErr> {
+    BigDecimal::from_str(value)
+        .map_err(|e| DbErr::Type(format!("Invalid NUMERIC {}: {}", value, e)))
+}
+
+fn to_position(model: positions::Model) -> Position {
+    Position {
+        id: model.id,
+        chain_id: model.chain_id,
+        pb_key: model.pb_key,
+        pool_address: model.pool_address,
+        protocol: model.protocol,
+        token0_address: model.token0_address,
+        token1_address: model.token1_address,
+        tick_lower: model.tick_lower,
+        tick_upper: model.tick_upper,
+        liquidity: model.liquidity.map(|v| v.to_string()),
+        amount0: model.amount0.map(|v| v.to_string()),
+        amount1: model.amount1.map(|v| v.to_string()),
+        nft_token_id: model.nft_token_id.map(|v| v.to_string()),
+        tx_hash: model.tx_hash,
+        price_lower: model.price_lower,
+        price_upper: model.price_upper,
+        value_usd: model.value_usd,
+        legacy_trans_id: model.legacy_trans_id,
+        status: model.status,
+        created_at: model.created_at.to_utc(),
+        closed_at: model.closed_at.map(|closed_at| closed_at.to_utc()),
+        block_number: model.block_number,
+        log_index: model.log_index,
+    }
+}
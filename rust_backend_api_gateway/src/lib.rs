@@ -0,0 +1,25 @@
+use sea_orm::DatabaseConnection;
+use std::sync::Arc;
+
+use application::service::chat_service::ChatService;
+use application::service::price_oracle::PriceOracle;
+use application::service::stream_service::StreamHub;
+use config::Chains;
+use domain::repositories::position_repository::PositionRepository;
+
+pub mod application;
+pub mod config;
+pub mod domain;
+pub mod infrastructure;
+pub mod math;
+pub mod presentation;
+
+/// Shared by every handler
+pub struct AppState {
+    pub db_connection: DatabaseConnection,
+    pub chains: Chains,
+    pub chat_service: ChatService,
+    pub price_oracle: PriceOracle,
+    pub positions: Arc<dyn PositionRepository>,
+    pub stream_hub: StreamHub,
+}
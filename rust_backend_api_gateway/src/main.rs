@@ -3,25 +3,22 @@ use actix_web::{App, HttpServer, web};
 use dotenvy::dotenv;
 use sea_orm::{Database, DatabaseConnection};
 use std::env;
+use std::sync::Arc;
 
-use application::service::chat_service::ChatService;
-use presentation::routes::init_routes;
+use rust_backend_api_gateway::AppState;
+use rust_backend_api_gateway::application::service::chat_service::ChatService;
+use rust_backend_api_gateway::application::service::divergence_service::spawn_divergence_monitor;
+use rust_backend_api_gateway::application::service::position_indexer::spawn_position_indexer;
+use rust_backend_api_gateway::application::service::price_oracle::PriceOracle;
+use rust_backend_api_gateway::application::service::snapshot_service::spawn_snapshot_scheduler;
+use rust_backend_api_gateway::application::service::stream_service::StreamHub;
+use rust_backend_api_gateway::config::Chains;
+use rust_backend_api_gateway::infrastructure::repositories::SeaOrmPositionRepository;
+use rust_backend_api_gateway::presentation::routes::init_routes;
 
 use tracing_actix_web::TracingLogger;
 use tracing_subscriber::EnvFilter;
 
-mod application;
-mod config;
-mod domain;
-mod infrastructure;
-mod math;
-mod presentation;
-
-struct AppState {
-    db_connection: DatabaseConnection,
-    chat_service: ChatService,
-}
-
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     tracing_subscriber::fmt()
@@ -42,13 +39,22 @@ async fn main() -> std::io::Result<()> {
         }
         Err(e) => {
             eprintln!("Failed to connect to database: {:?}", e);
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to connect to database: {}", e),
-            ));
+            return Err(std::io::Error::other(format!(
+                "Failed to connect to database: {}",
+                e
+            )));
         }
     };
 
+    // Parsed once; handlers and background tasks share the same chain list
+    let chains = Chains::from_env();
+
+    // Background price divergence monitor across DEXes and Binance
+    spawn_divergence_monitor(db_connection.clone(), chains.clone());
+
+    // Scheduled pool and token price snapshots for history endpoints
+    spawn_snapshot_scheduler(db_connection.clone(), chains.clone());
+
     // MongoDB Chat Service connection
     let mongo_uri =
         env::var("MONGODB_URI").unwrap_or_else(|_| "mongodb://localhost:27017".to_string());
@@ -56,6 +62,13 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to connect to MongoDB");
 
+    // Shared by every worker so each /stream topic is polled once
+    let stream_hub = StreamHub::new();
+    let positions = Arc::new(SeaOrmPositionRepository::new(db_connection.clone()));
+
+    // Positions minted and burned through LiquidityManager, whichever client sent them
+    spawn_position_indexer(positions.clone(), &chains);
+
     HttpServer::new(move || {
         let cors = Cors::default()
             .allow_any_origin()
@@ -67,7 +80,11 @@ async fn main() -> std::io::Result<()> {
         App::new()
             .app_data(web::Data::new(AppState {
                 db_connection: db_connection.clone(),
+                chains: chains.clone(),
                 chat_service: chat_service.clone(),
+                price_oracle: PriceOracle::new(db_connection.clone()),
+                positions: positions.clone(),
+                stream_hub: stream_hub.clone(),
             }))
             .wrap(cors)
             // This TracingLogger replaces the old Logger::default()
@@ -0,0 +1,243 @@
+use crate::application::dtos::price_history::PricePoint;
+
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0;
+
+/// Inputs for replaying a single concentrated liquidity position over a price series
+#[derive(Debug, Clone)]
+pub struct BacktestParams {
+    pub lower_price: f64,
+    pub upper_price: f64,
+    /// Capital deposited at the first candle, in token1 (quote) units
+    pub capital: f64,
+    /// Swap fee as a fraction, e.g. 0.003 for a 0.3% pool
+    pub fee_rate: f64,
+    /// Pool TVL in token1 units, used to estimate our share of the active liquidity
+    pub pool_tvl: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub liquidity: f64,
+    pub fees_earned: f64,
+    pub fee_apr: f64,
+    pub final_position_value: f64,
+    pub hold_value: f64,
+    pub impermanent_loss: f64,
+    pub time_in_range: f64,
+    pub pnl: f64,
+    pub pnl_percent: f64,
+}
+
+/// Token amounts (amount0, amount1) held by `liquidity` in [lower, upper] at `price`
+pub fn position_amounts(liquidity: f64, price: f64, lower: f64, upper: f64) -> (f64, f64) {
+    let sqrt_price = price.sqrt();
+    let sqrt_lower = lower.sqrt();
+    let sqrt_upper = upper.sqrt();
+
+    if price <= lower {
+        (liquidity * (1.0 / sqrt_lower - 1.0 / sqrt_upper), 0.0)
+    } else if price >= upper {
+        (0.0, liquidity * (sqrt_upper - sqrt_lower))
+    } else {
+        (
+            liquidity * (1.0 / sqrt_price - 1.0 / sqrt_upper),
+            liquidity * (sqrt_price - sqrt_lower),
+        )
+    }
+}
+
+/// Value in token1 units of `liquidity` in [lower, upper] at `price`
+pub fn position_value(liquidity: f64, price: f64, lower: f64, upper: f64) -> f64 {
+    let (amount0, amount1) = position_amounts(liquidity, price, lower, upper);
+    amount0 * price + amount1
+}
+
+/// Liquidity obtained by depositing `capital` (token1 units) into [lower, upper] at `price`
+pub fn liquidity_for_capital(capital: f64, price: f64, lower: f64, upper: f64) -> f64 {
+    let value_per_unit = position_value(1.0, price, lower, upper);
+    if value_per_unit <= 0.0 {
+        return 0.0;
+    }
+    capital / value_per_unit
+}
+
+/// Liquidity a full-range position worth `tvl` (token1 units) would provide at `price`
+pub fn full_range_liquidity(tvl: f64, price: f64) -> f64 {
+    if price <= 0.0 {
+        return 0.0;
+    }
+    tvl / (2.0 * price.sqrt())
+}
+
+/// Fees earned over one in-range candle by `liquidity`, given the pool fee rate and TVL
+pub fn estimate_candle_fees(
+    point: &PricePoint,
+    liquidity: f64,
+    fee_rate: f64,
+    pool_tvl: f64,
+) -> f64 {
+    let price = point.close;
+    let pool_liquidity = full_range_liquidity(pool_tvl, price);
+    if liquidity + pool_liquidity <= 0.0 {
+        return 0.0;
+    }
+    let share = liquidity / (liquidity + pool_liquidity);
+    let volume_in_token1 = point.volume.unwrap_or(0.0) * price;
+
+    volume_in_token1 * fee_rate * share
+}
+
+/// Replay a price series for a fixed range and estimate fees, IL, time in range and PnL.
+///
+/// Fees are accrued per candle while the close is in range, as candle volume (token0 units,
+/// converted at the close) times the fee rate times our share of the active liquidity.
+/// The pool's liquidity is approximated as full-range liquidity worth `pool_tvl`.
+pub fn backtest_range(prices: &[PricePoint], params: &BacktestParams) -> BacktestResult {
+    let lower = params.lower_price;
+    let upper = params.upper_price;
+
+    let Some(first) = prices.first() else {
+        return BacktestResult {
+            lower_price: lower,
+            upper_price: upper,
+            liquidity: 0.0,
+            fees_earned: 0.0,
+            fee_apr: 0.0,
+            final_position_value: params.capital,
+            hold_value: params.capital,
+            impermanent_loss: 0.0,
+            time_in_range: 0.0,
+            pnl: 0.0,
+            pnl_percent: 0.0,
+        };
+    };
+    let last = prices.last().unwrap_or(first);
+
+    let start_price = first.close;
+    let liquidity = liquidity_for_capital(params.capital, start_price, lower, upper);
+    let (initial_amount0, initial_amount1) = position_amounts(liquidity, start_price, lower, upper);
+
+    let mut fees_earned = 0.0;
+    let mut candles_in_range = 0usize;
+
+    for point in prices {
+        let price = point.close;
+        if price < lower || price > upper {
+            continue;
+        }
+        candles_in_range += 1;
+        fees_earned += estimate_candle_fees(point, liquidity, params.fee_rate, params.pool_tvl);
+    }
+
+    let end_price = last.close;
+    let final_position_value = position_value(liquidity, end_price, lower, upper);
+    let hold_value = initial_amount0 * end_price + initial_amount1;
+    let impermanent_loss = if hold_value > 0.0 {
+        final_position_value / hold_value - 1.0
+    } else {
+        0.0
+    };
+
+    let pnl = final_position_value + fees_earned - params.capital;
+    let pnl_percent = if params.capital > 0.0 {
+        pnl / params.capital * 100.0
+    } else {
+        0.0
+    };
+
+    let elapsed_seconds = (last.tick - first.tick) as f64;
+    let fee_apr = if elapsed_seconds > 0.0 && params.capital > 0.0 {
+        fees_earned / params.capital * (SECONDS_PER_YEAR / elapsed_seconds) * 100.0
+    } else {
+        0.0
+    };
+
+    BacktestResult {
+        lower_price: lower,
+        upper_price: upper,
+        liquidity,
+        fees_earned,
+        fee_apr,
+        final_position_value,
+        hold_value,
+        impermanent_loss,
+        time_in_range: candles_in_range as f64 / prices.len() as f64,
+        pnl,
+        pnl_percent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(tick: i64, close: f64, volume: f64) -> PricePoint {
+        PricePoint {
+            tick,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: Some(volume),
+        }
+    }
+
+    fn params(pool_tvl: f64) -> BacktestParams {
+        BacktestParams {
+            lower_price: 0.9,
+            upper_price: 1.1,
+            capital: 1000.0,
+            fee_rate: 0.003,
+            pool_tvl,
+        }
+    }
+
+    #[test]
+    fn holds_a_single_asset_outside_the_range() {
+        let (amount0, amount1) = position_amounts(100.0, 0.5, 0.9, 1.1);
+        assert!(amount0 > 0.0);
+        assert_eq!(amount1, 0.0);
+
+        let (amount0, amount1) = position_amounts(100.0, 2.0, 0.9, 1.1);
+        assert_eq!(amount0, 0.0);
+        assert!((amount1 - 100.0 * (1.1f64.sqrt() - 0.9f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deposits_exactly_the_capital() {
+        let liquidity = liquidity_for_capital(1000.0, 1.0, 0.9, 1.1);
+        assert!((position_value(liquidity, 1.0, 0.9, 1.1) - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn returns_the_capital_for_an_empty_series() {
+        let result = backtest_range(&[], &params(1_000_000.0));
+        assert_eq!(result.final_position_value, 1000.0);
+        assert_eq!(result.fees_earned, 0.0);
+        assert_eq!(result.time_in_range, 0.0);
+        assert_eq!(result.pnl, 0.0);
+    }
+
+    #[test]
+    fn earns_every_fee_of_a_single_candle_when_alone_in_the_pool() {
+        let result = backtest_range(&[candle(0, 1.0, 100.0)], &params(0.0));
+        // 100 token0 at 1.0 times 0.3%, with the whole active liquidity
+        assert!((result.fees_earned - 0.3).abs() < 1e-9);
+        assert!(result.impermanent_loss.abs() < 1e-12);
+        assert_eq!(result.time_in_range, 1.0);
+        // No elapsed time to annualise over
+        assert_eq!(result.fee_apr, 0.0);
+    }
+
+    #[test]
+    fn loses_to_holding_when_price_leaves_the_range() {
+        let prices = [candle(0, 1.0, 0.0), candle(3600, 1.21, 0.0)];
+        let result = backtest_range(&prices, &params(1_000_000.0));
+        assert_eq!(result.time_in_range, 0.5);
+        assert!(result.final_position_value < result.hold_value);
+        assert!(result.impermanent_loss < 0.0);
+        assert_eq!(result.fees_earned, 0.0);
+    }
+}
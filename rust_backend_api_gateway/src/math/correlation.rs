@@ -0,0 +1,299 @@
+use crate::application::dtos::price_history::PricePoint;
+use crate::domain::services::fee_tier::FeeTier;
+use crate::math::range_optimizer::{DEFAULT_MAX_HALF_WIDTH, DEFAULT_MIN_HALF_WIDTH};
+use crate::math::statistics::{daily_volatility, linear_regression_xy, log_returns, mean};
+use std::collections::HashMap;
+
+/// Daily volatility of the price ratio below which a pair can be pegged
+const PEGGED_MAX_DAILY_VOLATILITY: f64 = 0.005;
+/// Largest log deviation from the mean ratio a pegged pair may show
+const PEGGED_MAX_DEVIATION: f64 = 0.02;
+/// Return correlation above which the two legs move together
+const CORRELATED_MIN_CORRELATION: f64 = 0.7;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PairRelationship {
+    /// The ratio stays near a fixed value, e.g. USDC/USDT or SEI/WSEI
+    Pegged,
+    /// Both legs move together, so the ratio drifts slowly
+    Correlated,
+    Uncorrelated,
+}
+
+impl PairRelationship {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PairRelationship::Pegged => "PEGGED",
+            PairRelationship::Correlated => "CORRELATED",
+            PairRelationship::Uncorrelated => "UNCORRELATED",
+        }
+    }
+
+    /// Half-widths (fractions of price) worth searching for a range on this kind of pair
+    pub fn range_half_width_bounds(&self) -> (f64, f64) {
+        match self {
+            PairRelationship::Pegged => (0.0005, 0.02),
+            PairRelationship::Correlated => (0.002, 0.2),
+            PairRelationship::Uncorrelated => (DEFAULT_MIN_HALF_WIDTH, DEFAULT_MAX_HALF_WIDTH),
+        }
+    }
+
+    /// Fee tier such pairs usually trade at, used when the pool is unknown
+    pub fn typical_fee_tier(&self) -> FeeTier {
+        match self {
+            PairRelationship::Pegged => FeeTier::from_hundredths_bip(100),
+            PairRelationship::Correlated => FeeTier::from_hundredths_bip(500),
+            PairRelationship::Uncorrelated => FeeTier::from_hundredths_bip(3000),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PairCorrelation {
+    pub relationship: PairRelationship,
+    /// Correlation of the legs' log returns over the whole sample
+    pub correlation: Option<f64>,
+    /// Entry `i` covers the returns ending at close `i + window`
+    pub rolling_correlation: Vec<Option<f64>>,
+    /// Mean-reversion half-life of the log ratio, in candles
+    pub half_life_periods: Option<f64>,
+    pub ratio_daily_volatility: f64,
+    /// Largest absolute log deviation of the ratio from its mean
+    pub max_deviation: f64,
+}
+
+/// Closes of two series joined on candle timestamp, in time order
+pub fn align_closes(a: &[PricePoint], b: &[PricePoint]) -> (Vec<i64>, Vec<f64>, Vec<f64>) {
+    let b_by_tick: HashMap<i64, f64> = b.iter().map(|p| (p.tick, p.close)).collect();
+
+    let mut joined: Vec<(i64, f64, f64)> = a
+        .iter()
+        .filter_map(|p| Some((p.tick, p.close, *b_by_tick.get(&p.tick)?)))
+        .collect();
+    joined.sort_by_key(|(tick, _, _)| *tick);
+
+    let ticks = joined.iter().map(|(tick, _, _)| *tick).collect();
+    let a_closes = joined.iter().map(|(_, a, _)| *a).collect();
+    let b_closes = joined.iter().map(|(_, _, b)| *b).collect();
+    (ticks, a_closes, b_closes)
+}
+
+/// Pearson correlation of two equally long series
+pub fn pearson_correlation(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() != y.len() || x.len() < 2 {
+        return None;
+    }
+
+    let (x_mean, y_mean) = (mean(x), mean(y));
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y) {
+        sxy += (a - x_mean) * (b - y_mean);
+        sxx += (a - x_mean).powi(2);
+        syy += (b - y_mean).powi(2);
+    }
+
+    if sxx == 0.0 || syy == 0.0 {
+        return None;
+    }
+    Some(sxy / (sxx * syy).sqrt())
+}
+
+/// Correlation over each trailing window of `window` values; windows without variance yield `None`
+pub fn rolling_correlation(x: &[f64], y: &[f64], window: usize) -> Vec<Option<f64>> {
+    if window < 2 || x.len() != y.len() || x.len() < window {
+        return Vec::new();
+    }
+
+    x.windows(window)
+        .zip(y.windows(window))
+        .map(|(a, b)| pearson_correlation(a, b))
+        .collect()
+}
+
+/// Ornstein-Uhlenbeck half-life in candles, from regressing `Δy_t` on `y_{t-1}`.
+///
+/// Returns `None` when the series does not revert (non-negative slope).
+pub fn mean_reversion_half_life(values: &[f64]) -> Option<f64> {
+    if values.len() < 3 {
+        return None;
+    }
+
+    let lagged = &values[..values.len() - 1];
+    let deltas: Vec<f64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    let beta = linear_regression_xy(lagged, &deltas)?.slope;
+
+    if beta >= 0.0 || beta <= -1.0 {
+        return None;
+    }
+    Some(-std::f64::consts::LN_2 / (1.0 + beta).ln())
+}
+
+/// Classify a pair from its price ratio and, when available, the USD prices of both legs
+pub fn classify_pair(
+    ratio: &[f64],
+    legs: Option<(&[f64], &[f64])>,
+    interval_minutes: u32,
+    window: usize,
+) -> PairCorrelation {
+    let log_ratio: Vec<f64> = ratio.iter().filter(|p| **p > 0.0).map(|p| p.ln()).collect();
+    let center = mean(&log_ratio);
+    let max_deviation = log_ratio
+        .iter()
+        .map(|v| (v - center).abs())
+        .fold(0.0, f64::max);
+    let ratio_daily_volatility = daily_volatility(ratio, interval_minutes);
+    let half_life_periods = mean_reversion_half_life(&log_ratio);
+
+    let (correlation, rolling_correlation) = match legs {
+        Some((leg0, leg1)) => {
+            let (returns0, returns1) = (log_returns(leg0), log_returns(leg1));
+            let len = returns0.len().min(returns1.len());
+            let (returns0, returns1) = (&returns0[..len], &returns1[..len]);
+            (
+                pearson_correlation(returns0, returns1),
+                rolling_correlation(returns0, returns1, window),
+            )
+        }
+        None => (None, Vec::new()),
+    };
+
+    // A pair only counts as pegged once the ratio has held for a full window
+    let relationship = if log_ratio.len() >= window.max(2)
+        && ratio_daily_volatility < PEGGED_MAX_DAILY_VOLATILITY
+        && max_deviation < PEGGED_MAX_DEVIATION
+    {
+        PairRelationship::Pegged
+    } else if correlation.is_some_and(|c| c >= CORRELATED_MIN_CORRELATION) {
+        PairRelationship::Correlated
+    } else {
+        PairRelationship::Uncorrelated
+    };
+
+    PairCorrelation {
+        relationship,
+        correlation,
+        rolling_correlation,
+        half_life_periods,
+        ratio_daily_volatility,
+        max_deviation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(tick: i64, close: f64) -> PricePoint {
+        PricePoint {
+            tick,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: None,
+        }
+    }
+
+    /// Deterministic zig-zag walk, so tests do not depend on a random source
+    fn walk(len: usize, step: f64) -> Vec<f64> {
+        (0..len)
+            .scan(100.0, |price, i| {
+                *price *= if i % 3 == 0 { 1.0 - step } else { 1.0 + step };
+                Some(*price)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn joins_closes_on_candle_time() {
+        let a = [candle(3, 30.0), candle(1, 10.0), candle(2, 20.0)];
+        let b = [candle(2, 2.0), candle(3, 3.0), candle(4, 4.0)];
+
+        let (ticks, a_closes, b_closes) = align_closes(&a, &b);
+        assert_eq!(ticks, vec![2, 3]);
+        assert_eq!(a_closes, vec![20.0, 30.0]);
+        assert_eq!(b_closes, vec![2.0, 3.0]);
+        assert!(align_closes(&a, &[]).0.is_empty());
+    }
+
+    #[test]
+    fn correlates_linear_relationships() {
+        let x = [1.0, 2.0, 4.0, 3.0, 5.0];
+        let doubled: Vec<f64> = x.iter().map(|v| v * 2.0 + 1.0).collect();
+        let negated: Vec<f64> = x.iter().map(|v| -v).collect();
+
+        assert!((pearson_correlation(&x, &doubled).unwrap() - 1.0).abs() < 1e-12);
+        assert!((pearson_correlation(&x, &negated).unwrap() + 1.0).abs() < 1e-12);
+        assert_eq!(pearson_correlation(&x, &[1.0; 5]), None);
+        assert_eq!(pearson_correlation(&x, &x[..4]), None);
+        assert_eq!(pearson_correlation(&[1.0], &[1.0]), None);
+    }
+
+    #[test]
+    fn rolls_the_correlation_window() {
+        let x = [1.0, 2.0, 4.0, 3.0, 5.0];
+
+        let rolling = rolling_correlation(&x, &x, 3);
+        assert_eq!(rolling.len(), 3);
+        assert!(rolling.iter().all(|c| (c.unwrap() - 1.0).abs() < 1e-12));
+        assert!(rolling_correlation(&x, &x, 1).is_empty());
+        assert!(rolling_correlation(&x, &x, 6).is_empty());
+    }
+
+    #[test]
+    fn measures_mean_reversion_half_life() {
+        // y_t = 0.5 * y_{t-1} halves the deviation every candle
+        let decaying: Vec<f64> = (0..10).map(|i| 0.5f64.powi(i)).collect();
+        assert!((mean_reversion_half_life(&decaying).unwrap() - 1.0).abs() < 1e-9);
+
+        let trending: Vec<f64> = (0..10).map(|i| i as f64).collect();
+        assert_eq!(mean_reversion_half_life(&trending), None);
+        assert_eq!(mean_reversion_half_life(&[1.0, 0.5]), None);
+    }
+
+    #[test]
+    fn classifies_pegged_pairs() {
+        // Deviations from the peg shrink by a fifth every candle
+        let ratio: Vec<f64> = (0..48).map(|i| (0.005 * 0.8f64.powi(i)).exp()).collect();
+
+        let pair = classify_pair(&ratio, None, 60, 24);
+        assert_eq!(pair.relationship, PairRelationship::Pegged);
+        assert!(pair.max_deviation < PEGGED_MAX_DEVIATION);
+        let half_life = pair.half_life_periods.unwrap();
+        assert!((half_life - std::f64::consts::LN_2 / -0.8f64.ln()).abs() < 1e-6);
+        assert_eq!(pair.correlation, None);
+    }
+
+    #[test]
+    fn classifies_legs_by_return_correlation() {
+        let leg0 = walk(48, 0.02);
+        let together: Vec<f64> = leg0.iter().map(|p| p * 0.5).collect();
+        let drifting: Vec<f64> = together
+            .iter()
+            .enumerate()
+            .map(|(i, p)| p * (1.0 + 0.001 * i as f64))
+            .collect();
+        let ratio: Vec<f64> = leg0.iter().zip(&drifting).map(|(a, b)| a / b).collect();
+
+        let correlated = classify_pair(&ratio, Some((&leg0, &drifting)), 60, 24);
+        assert_eq!(correlated.relationship, PairRelationship::Correlated);
+        assert_eq!(correlated.rolling_correlation.len(), 47 - 24 + 1);
+
+        let opposite: Vec<f64> = leg0.iter().map(|p| 10_000.0 / p).collect();
+        let ratio: Vec<f64> = leg0.iter().zip(&opposite).map(|(a, b)| a / b).collect();
+        let uncorrelated = classify_pair(&ratio, Some((&leg0, &opposite)), 60, 24);
+        assert_eq!(uncorrelated.relationship, PairRelationship::Uncorrelated);
+        assert!(uncorrelated.correlation.unwrap() < 0.0);
+    }
+
+    #[test]
+    fn needs_a_full_window_to_call_a_pair_pegged() {
+        for ratio in [&[][..], &[1.0][..], &[1.0; 23][..]] {
+            let pair = classify_pair(ratio, Some((ratio, ratio)), 60, 24);
+            assert_eq!(pair.relationship, PairRelationship::Uncorrelated);
+            assert_eq!(pair.correlation, None);
+            assert!(pair.rolling_correlation.is_empty());
+            assert_eq!(pair.half_life_periods, None);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::math::backtest::liquidity_for_capital;
+use anyhow::{Result, anyhow};
+
+#[derive(Debug, Clone)]
+pub struct FeeAprParams {
+    /// Position size in USD
+    pub capital: f64,
+    /// Current price, token1 per token0
+    pub current_price: f64,
+    pub price_lower: f64,
+    pub price_upper: f64,
+    pub token0_decimals: u32,
+    pub token1_decimals: u32,
+    pub token1_usd_price: f64,
+    pub daily_volume: f64,
+    pub fee_tier_percent: f64,
+    /// Raw pool liquidity active at the current tick, excluding the new position
+    pub active_liquidity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeeAprEstimate {
+    /// Raw liquidity the position would add
+    pub position_liquidity: f64,
+    /// Share of in-range liquidity the position would own, 0..1
+    pub liquidity_share: f64,
+    pub in_range: bool,
+    pub daily_fees: f64,
+    pub apr_percent: f64,
+}
+
+/// Fee APR of a hypothetical position from its share of the liquidity active at the current
+/// price, assuming daily volume keeps trading at that price. Out-of-range positions earn nothing.
+pub fn estimate_position_fee_apr(params: &FeeAprParams) -> Result<FeeAprEstimate> {
+    if params.capital <= 0.0 || params.current_price <= 0.0 || params.token1_usd_price <= 0.0 {
+        return Err(anyhow!(
+            "Capital, current price and token1 USD price must be positive"
+        ));
+    }
+    if params.price_lower <= 0.0 || params.price_lower >= params.price_upper {
+        return Err(anyhow!(
+            "price_lower must be positive and below price_upper"
+        ));
+    }
+
+    let capital_in_token1 = params.capital / params.token1_usd_price;
+    let liquidity = liquidity_for_capital(
+        capital_in_token1,
+        params.current_price,
+        params.price_lower,
+        params.price_upper,
+    );
+    // Human-unit liquidity scales by sqrt(10^d0 * 10^d1) in raw token units
+    let position_liquidity =
+        liquidity * 10f64.powf((params.token0_decimals + params.token1_decimals) as f64 / 2.0);
+
+    let in_range =
+        params.current_price >= params.price_lower && params.current_price <= params.price_upper;
+    let liquidity_share = if in_range {
+        position_liquidity / (params.active_liquidity.max(0.0) + position_liquidity)
+    } else {
+        0.0
+    };
+
+    let daily_fees = params.daily_volume * params.fee_tier_percent / 100.0 * liquidity_share;
+
+    Ok(FeeAprEstimate {
+        position_liquidity,
+        liquidity_share,
+        in_range,
+        daily_fees,
+        apr_percent: daily_fees * 365.0 / params.capital * 100.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(price_lower: f64, price_upper: f64, active_liquidity: f64) -> FeeAprParams {
+        FeeAprParams {
+            capital: 1000.0,
+            current_price: 1.0,
+            price_lower,
+            price_upper,
+            token0_decimals: 6,
+            token1_decimals: 6,
+            token1_usd_price: 1.0,
+            daily_volume: 100_000.0,
+            fee_tier_percent: 0.3,
+            active_liquidity,
+        }
+    }
+
+    #[test]
+    fn owns_every_fee_without_other_liquidity() {
+        let estimate = estimate_position_fee_apr(&params(0.9, 1.1, 0.0)).unwrap();
+
+        assert!(estimate.in_range);
+        assert_eq!(estimate.liquidity_share, 1.0);
+        assert!((estimate.daily_fees - 300.0).abs() < 1e-9);
+        assert!((estimate.apr_percent - 300.0 * 365.0 / 1000.0 * 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn narrower_ranges_earn_a_larger_share() {
+        let wide = estimate_position_fee_apr(&params(0.5, 2.0, 1e12)).unwrap();
+        let narrow = estimate_position_fee_apr(&params(0.95, 1.05, 1e12)).unwrap();
+
+        assert!(narrow.position_liquidity > wide.position_liquidity);
+        assert!(narrow.apr_percent > wide.apr_percent);
+        assert!(narrow.liquidity_share < 1.0);
+    }
+
+    #[test]
+    fn scales_liquidity_to_raw_token_units() {
+        let six = estimate_position_fee_apr(&params(0.9, 1.1, 0.0)).unwrap();
+        let eighteen = estimate_position_fee_apr(&FeeAprParams {
+            token0_decimals: 18,
+            token1_decimals: 18,
+            ..params(0.9, 1.1, 0.0)
+        })
+        .unwrap();
+
+        let ratio = eighteen.position_liquidity / six.position_liquidity;
+        assert!((ratio / 1e12 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn earns_nothing_out_of_range() {
+        let estimate = estimate_position_fee_apr(&params(1.2, 1.5, 1e12)).unwrap();
+
+        assert!(!estimate.in_range);
+        assert_eq!(estimate.daily_fees, 0.0);
+        assert_eq!(estimate.apr_percent, 0.0);
+    }
+
+    #[test]
+    fn rejects_degenerate_inputs() {
+        assert!(estimate_position_fee_apr(&params(1.1, 0.9, 0.0)).is_err());
+        assert!(estimate_position_fee_apr(&params(0.0, 1.1, 0.0)).is_err());
+        assert!(
+            estimate_position_fee_apr(&FeeAprParams {
+                capital: 0.0,
+                ..params(0.9, 1.1, 0.0)
+            })
+            .is_err()
+        );
+        assert!(
+            estimate_position_fee_apr(&FeeAprParams {
+                token1_usd_price: 0.0,
+                ..params(0.9, 1.1, 0.0)
+            })
+            .is_err()
+        );
+    }
+}
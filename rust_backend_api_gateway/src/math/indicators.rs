@@ -0,0 +1,476 @@
+use anyhow::{Result, anyhow};
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+/// Incremental indicator fed one close at a time.
+///
+/// `update` returns `None` until enough values have been seen to produce an output.
+pub trait Indicator {
+    type Output;
+
+    fn update(&mut self, value: f64) -> Option<Self::Output>;
+}
+
+/// Simple moving average
+pub struct Sma {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Sma {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+        }
+    }
+}
+
+impl Indicator for Sma {
+    type Output = f64;
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+        self.sum += value;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or(0.0);
+        }
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+}
+
+/// Exponential moving average seeded with the SMA of the first `period` values
+pub struct Ema {
+    period: usize,
+    alpha: f64,
+    seed: Sma,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            seed: Sma::new(period),
+            value: None,
+        }
+    }
+}
+
+impl Indicator for Ema {
+    type Output = f64;
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        self.value = match self.value {
+            Some(prev) => Some(prev + self.alpha * (value - prev)),
+            None if self.period > 0 => self.seed.update(value),
+            None => None,
+        };
+        self.value
+    }
+}
+
+/// Wilder's relative strength index
+pub struct Rsi {
+    period: usize,
+    prev: Option<f64>,
+    seen: usize,
+    avg_gain: f64,
+    avg_loss: f64,
+}
+
+impl Rsi {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            prev: None,
+            seen: 0,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+        }
+    }
+}
+
+impl Indicator for Rsi {
+    type Output = f64;
+
+    fn update(&mut self, value: f64) -> Option<f64> {
+        let prev = self.prev.replace(value)?;
+        if self.period == 0 {
+            return None;
+        }
+
+        let change = value - prev;
+        let (gain, loss) = (change.max(0.0), (-change).max(0.0));
+        let period = self.period as f64;
+        self.seen += 1;
+
+        if self.seen <= self.period {
+            // Seed with the plain average of the first `period` changes
+            self.avg_gain += gain / period;
+            self.avg_loss += loss / period;
+            if self.seen < self.period {
+                return None;
+            }
+        } else {
+            self.avg_gain = (self.avg_gain * (period - 1.0) + gain) / period;
+            self.avg_loss = (self.avg_loss * (period - 1.0) + loss) / period;
+        }
+
+        if self.avg_loss == 0.0 {
+            return Some(if self.avg_gain == 0.0 { 50.0 } else { 100.0 });
+        }
+        let rs = self.avg_gain / self.avg_loss;
+        Some(100.0 - 100.0 / (1.0 + rs))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BollingerOutput {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+/// Bollinger Bands: SMA ± `multiplier` population standard deviations
+pub struct BollingerBands {
+    period: usize,
+    multiplier: f64,
+    window: VecDeque<f64>,
+}
+
+impl BollingerBands {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            period,
+            multiplier,
+            window: VecDeque::with_capacity(period),
+        }
+    }
+}
+
+impl Indicator for BollingerBands {
+    type Output = BollingerOutput;
+
+    fn update(&mut self, value: f64) -> Option<BollingerOutput> {
+        self.window.push_back(value);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        if self.period == 0 || self.window.len() < self.period {
+            return None;
+        }
+
+        let n = self.period as f64;
+        let middle = self.window.iter().sum::<f64>() / n;
+        let variance = self
+            .window
+            .iter()
+            .map(|v| (v - middle).powi(2))
+            .sum::<f64>()
+            / n;
+        let band = self.multiplier * variance.sqrt();
+
+        Some(BollingerOutput {
+            upper: middle + band,
+            middle,
+            lower: middle - band,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MacdOutput {
+    pub macd: f64,
+    pub signal: Option<f64>,
+    pub histogram: Option<f64>,
+}
+
+/// Moving average convergence divergence with its signal line
+pub struct Macd {
+    fast: Ema,
+    slow: Ema,
+    signal: Ema,
+}
+
+impl Macd {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        Self {
+            fast: Ema::new(fast),
+            slow: Ema::new(slow),
+            signal: Ema::new(signal),
+        }
+    }
+}
+
+impl Indicator for Macd {
+    type Output = MacdOutput;
+
+    fn update(&mut self, value: f64) -> Option<MacdOutput> {
+        let fast = self.fast.update(value);
+        let slow = self.slow.update(value);
+        let macd = fast? - slow?;
+        let signal = self.signal.update(macd);
+
+        Some(MacdOutput {
+            macd,
+            signal,
+            histogram: signal.map(|s| macd - s),
+        })
+    }
+}
+
+/// Indicator requested through the `set` query parameter, e.g. `ema20`, `rsi14`, `bb20`, `macd`
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndicatorSpec {
+    Sma(usize),
+    Ema(usize),
+    Rsi(usize),
+    Bollinger(usize),
+    Macd(usize, usize, usize),
+}
+
+impl IndicatorSpec {
+    pub fn name(&self) -> String {
+        match self {
+            IndicatorSpec::Sma(n) => format!("sma{}", n),
+            IndicatorSpec::Ema(n) => format!("ema{}", n),
+            IndicatorSpec::Rsi(n) => format!("rsi{}", n),
+            IndicatorSpec::Bollinger(n) => format!("bb{}", n),
+            IndicatorSpec::Macd(fast, slow, signal) => format!("macd{}_{}_{}", fast, slow, signal),
+        }
+    }
+}
+
+impl FromStr for IndicatorSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        let split = s.find(|c: char| c.is_ascii_digit()).unwrap_or(s.len());
+        let (kind, params) = s.split_at(split);
+
+        let period = |default: usize| -> Result<usize> {
+            let period = if params.is_empty() {
+                default
+            } else {
+                params
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid indicator period in '{}'", s))?
+            };
+            if period == 0 || period > 500 {
+                return Err(anyhow!(
+                    "Indicator period must be between 1 and 500 in '{}'",
+                    s
+                ));
+            }
+            Ok(period)
+        };
+
+        match kind {
+            "sma" => Ok(IndicatorSpec::Sma(period(20)?)),
+            "ema" => Ok(IndicatorSpec::Ema(period(20)?)),
+            "rsi" => Ok(IndicatorSpec::Rsi(period(14)?)),
+            "bb" => Ok(IndicatorSpec::Bollinger(period(20)?)),
+            "macd" if params.is_empty() => Ok(IndicatorSpec::Macd(12, 26, 9)),
+            "macd" => {
+                let parts: Vec<usize> = params
+                    .split('_')
+                    .map(|p| p.parse())
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|_| anyhow!("Invalid MACD parameters in '{}'", s))?;
+                match parts.as_slice() {
+                    [fast, slow, signal] if *fast > 0 && fast < slow && *signal > 0 => {
+                        Ok(IndicatorSpec::Macd(*fast, *slow, *signal))
+                    }
+                    _ => Err(anyhow!(
+                        "MACD expects fast_slow_signal with fast < slow, e.g. macd12_26_9"
+                    )),
+                }
+            }
+            _ => Err(anyhow!("Unsupported indicator '{}'", s)),
+        }
+    }
+}
+
+/// Run `indicator` over `values`, keeping one output slot per input value
+pub fn indicator_series<I: Indicator>(indicator: &mut I, values: &[f64]) -> Vec<Option<I::Output>> {
+    values.iter().map(|v| indicator.update(*v)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Closes from StockCharts' EMA worksheet
+    const EMA_CLOSES: [f64; 30] = [
+        22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29, 22.15, 22.39, 22.38,
+        22.61, 23.36, 24.05, 23.75, 23.83, 23.95, 23.63, 23.82, 23.87, 23.65, 23.19, 23.10, 23.33,
+        22.68, 23.10, 22.40, 22.17,
+    ];
+
+    /// Closes from StockCharts' RSI worksheet
+    const RSI_CLOSES: [f64; 33] = [
+        44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61,
+        46.28, 46.28, 46.00, 46.03, 46.41, 46.22, 45.64, 46.21, 46.25, 45.71, 46.45, 45.78, 45.35,
+        44.03, 44.18, 44.22, 44.57, 43.42, 42.66, 43.13,
+    ];
+
+    /// Closes from StockCharts' Bollinger Bands worksheet
+    const BB_CLOSES: [f64; 42] = [
+        86.16, 89.09, 88.78, 90.32, 89.07, 91.15, 89.44, 89.18, 86.93, 87.68, 86.96, 89.43, 89.32,
+        88.72, 87.45, 87.26, 89.50, 87.90, 89.13, 90.70, 92.90, 92.98, 91.80, 92.66, 92.68, 92.30,
+        92.77, 92.54, 92.95, 93.20, 91.07, 89.83, 89.74, 90.40, 90.74, 88.02, 88.09, 88.84, 90.78,
+        90.54, 91.39, 90.65,
+    ];
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {} ± {}, got {}",
+            expected,
+            tolerance,
+            actual
+        );
+    }
+
+    #[test]
+    fn averages_the_last_period_values() {
+        let series = indicator_series(&mut Sma::new(3), &[1.0, 2.0, 3.0, 4.0, 8.0]);
+        assert_eq!(series, vec![None, None, Some(2.0), Some(3.0), Some(5.0)]);
+
+        let series = indicator_series(&mut Sma::new(10), &EMA_CLOSES);
+        assert!(series[..9].iter().all(Option::is_none));
+        assert_close(series[9].unwrap(), 22.22, 0.005);
+    }
+
+    #[test]
+    fn matches_the_ema_reference() {
+        let expected = [
+            22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34, 23.43,
+            23.51, 23.53, 23.47, 23.40, 23.39, 23.26, 23.23, 23.08, 22.92,
+        ];
+        let series = indicator_series(&mut Ema::new(10), &EMA_CLOSES);
+
+        assert!(series[..9].iter().all(Option::is_none));
+        for (actual, expected) in series[9..].iter().zip(expected) {
+            assert_close(actual.unwrap(), expected, 0.01);
+        }
+    }
+
+    #[test]
+    fn matches_the_wilder_rsi_reference() {
+        let expected = [
+            70.46, 66.25, 66.48, 69.35, 66.29, 57.92, 62.88, 63.21, 56.01, 62.34, 54.67, 50.39,
+            40.02, 41.49, 41.90, 45.50, 37.32, 33.09, 37.79,
+        ];
+        let series = indicator_series(&mut Rsi::new(14), &RSI_CLOSES);
+
+        // 14 changes need 15 closes
+        assert!(series[..14].iter().all(Option::is_none));
+        for (actual, expected) in series[14..].iter().zip(expected) {
+            assert_close(actual.unwrap(), expected, 0.01);
+        }
+    }
+
+    #[test]
+    fn pins_rsi_on_one_sided_moves() {
+        let rising: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        assert_eq!(
+            indicator_series(&mut Rsi::new(14), &rising)[19],
+            Some(100.0)
+        );
+        assert_eq!(
+            indicator_series(&mut Rsi::new(14), &[5.0; 20])[19],
+            Some(50.0)
+        );
+    }
+
+    #[test]
+    fn matches_the_bollinger_reference() {
+        let series = indicator_series(&mut BollingerBands::new(20, 2.0), &BB_CLOSES);
+
+        assert!(series[..19].iter().all(Option::is_none));
+        let first = series[19].unwrap();
+        assert_close(first.middle, 88.71, 0.005);
+        assert_close(first.upper, 91.29, 0.005);
+        assert_close(first.lower, 86.12, 0.01);
+        let last = series[41].unwrap();
+        assert_close(last.middle, 91.05, 0.005);
+        assert_close(last.upper, 94.15, 0.005);
+        assert_close(last.lower, 87.95, 0.005);
+    }
+
+    #[test]
+    fn matches_the_macd_reference() {
+        // EMA12 - EMA26 of the Bollinger worksheet closes with a 9-period signal, each EMA
+        // seeded with the SMA of its first period
+        let series = indicator_series(&mut Macd::new(12, 26, 9), &BB_CLOSES);
+
+        assert!(series[..25].iter().all(Option::is_none));
+        let first = series[25].unwrap();
+        assert_close(first.macd, 1.5849, 0.0001);
+        assert!(first.signal.is_none() && first.histogram.is_none());
+
+        // The signal needs 9 MACD values
+        assert!(series[32].unwrap().signal.is_none());
+        let signal_start = series[33].unwrap();
+        assert_close(signal_start.macd, 0.7372, 0.0001);
+        assert_close(signal_start.signal.unwrap(), 1.3249, 0.0001);
+        assert_close(signal_start.histogram.unwrap(), -0.5877, 0.0001);
+
+        let last = series[41].unwrap();
+        assert_close(last.macd, 0.1948, 0.0001);
+        assert_close(last.signal.unwrap(), 0.3665, 0.0001);
+        assert_close(last.histogram.unwrap(), -0.1716, 0.0001);
+    }
+
+    #[test]
+    fn parses_indicator_specs() {
+        assert_eq!(
+            "ema20".parse::<IndicatorSpec>().unwrap(),
+            IndicatorSpec::Ema(20)
+        );
+        assert_eq!(
+            " RSI ".parse::<IndicatorSpec>().unwrap(),
+            IndicatorSpec::Rsi(14)
+        );
+        assert_eq!(
+            "bb".parse::<IndicatorSpec>().unwrap(),
+            IndicatorSpec::Bollinger(20)
+        );
+        assert_eq!(
+            "macd".parse::<IndicatorSpec>().unwrap(),
+            IndicatorSpec::Macd(12, 26, 9)
+        );
+        assert_eq!(
+            "macd5_35_5".parse::<IndicatorSpec>().unwrap(),
+            IndicatorSpec::Macd(5, 35, 5)
+        );
+        assert_eq!(IndicatorSpec::Macd(12, 26, 9).name(), "macd12_26_9");
+    }
+
+    #[test]
+    fn rejects_invalid_indicator_specs() {
+        for spec in [
+            "ema0",
+            "sma501",
+            "rsi1x",
+            "vwap14",
+            "macd26_12_9",
+            "macd12_26",
+            "macd12_26_0",
+            "macd0_26_9",
+            "",
+        ] {
+            assert!(
+                spec.parse::<IndicatorSpec>().is_err(),
+                "'{}' should be rejected",
+                spec
+            );
+        }
+    }
+}
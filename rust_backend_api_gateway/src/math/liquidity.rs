@@ -0,0 +1,236 @@
+use crate::domain::services::data::{ActiveLiquidityResponse, TickDataShape};
+
+/// Active liquidity from each initialised tick up to the next one, sorted by tick.
+///
+/// `liquidityNet` deltas (DragonSwap, subgraphs) are accumulated from the lowest tick; providers
+/// that report active liquidity per tick (Sailor, the on-chain reader) are used as is.
+pub fn liquidity_distribution(liquidity: &ActiveLiquidityResponse) -> Vec<(i32, f64)> {
+    let mut parsed: Vec<(i32, f64)> = liquidity
+        .data
+        .iter()
+        .filter_map(|t| Some((t.tick_idx.parse().ok()?, t.liquidity_net.parse().ok()?)))
+        .collect();
+    parsed.sort_by_key(|(tick, _)| *tick);
+
+    if liquidity.tick_shape == TickDataShape::LiquidityNet {
+        let mut active = 0.0;
+        for (_, liquidity) in parsed.iter_mut() {
+            active += *liquidity;
+            *liquidity = active;
+        }
+    }
+
+    parsed
+        .into_iter()
+        .map(|(tick, liquidity)| (tick, liquidity.max(0.0)))
+        .collect()
+}
+
+/// Raw pool liquidity active at `current_tick`
+pub fn active_liquidity_at_tick(
+    liquidity: &ActiveLiquidityResponse,
+    current_tick: i32,
+) -> Option<f64> {
+    liquidity_distribution(liquidity)
+        .into_iter()
+        .take_while(|(tick, _)| *tick <= current_tick)
+        .last()
+        .map(|(_, liquidity)| liquidity)
+}
+
+/// Share of the pool's liquidity (weighted by tick width) placed within `tick_radius` of `current_tick`
+pub fn liquidity_concentration(
+    distribution: &[(i32, f64)],
+    current_tick: i32,
+    tick_radius: i32,
+) -> Option<f64> {
+    let (near_lower, near_upper) = (current_tick - tick_radius, current_tick + tick_radius);
+
+    let (mut total, mut near) = (0.0, 0.0);
+    for segment in distribution.windows(2) {
+        let ((start, liquidity), (end, _)) = (segment[0], segment[1]);
+        total += liquidity * (end - start) as f64;
+        let overlap = (end.min(near_upper) - start.max(near_lower)).max(0);
+        near += liquidity * overlap as f64;
+    }
+
+    (total > 0.0).then(|| near / total)
+}
+
+fn sqrt_price_at(tick: f64) -> f64 {
+    1.0001f64.powf(tick / 2.0)
+}
+
+/// Segments of constant active liquidity, as `(start_tick, end_tick, liquidity)`
+fn liquidity_segments(
+    distribution: &[(i32, f64)],
+) -> impl DoubleEndedIterator<Item = (f64, f64, f64)> {
+    distribution
+        .windows(2)
+        .map(|segment| (segment[0].0 as f64, segment[1].0 as f64, segment[0].1))
+}
+
+/// Raw token1 it takes to push the price up from `from_tick` to `to_tick`
+pub fn token1_depth(distribution: &[(i32, f64)], from_tick: f64, to_tick: f64) -> f64 {
+    liquidity_segments(distribution)
+        .map(|(start, end, liquidity)| {
+            let (lower, upper) = (start.max(from_tick), end.min(to_tick));
+            if upper <= lower {
+                return 0.0;
+            }
+            liquidity * (sqrt_price_at(upper) - sqrt_price_at(lower))
+        })
+        .sum()
+}
+
+/// Raw token0 it takes to push the price down from `from_tick` to `to_tick`
+pub fn token0_depth(distribution: &[(i32, f64)], from_tick: f64, to_tick: f64) -> f64 {
+    liquidity_segments(distribution)
+        .map(|(start, end, liquidity)| {
+            let (lower, upper) = (start.max(to_tick), end.min(from_tick));
+            if upper <= lower {
+                return 0.0;
+            }
+            liquidity * (1.0 / sqrt_price_at(lower) - 1.0 / sqrt_price_at(upper))
+        })
+        .sum()
+}
+
+/// Tick the price moves to after swapping `amount` raw token1 into the pool (price goes up).
+///
+/// Returns `None` when the initialised liquidity runs out before the amount is absorbed.
+pub fn tick_after_token1_in(
+    distribution: &[(i32, f64)],
+    from_tick: f64,
+    amount: f64,
+) -> Option<f64> {
+    let mut remaining = amount;
+    for (start, end, liquidity) in liquidity_segments(distribution) {
+        if end <= from_tick || liquidity <= 0.0 {
+            continue;
+        }
+        let lower = start.max(from_tick);
+        let capacity = liquidity * (sqrt_price_at(end) - sqrt_price_at(lower));
+        if remaining <= capacity {
+            let sqrt_price = sqrt_price_at(lower) + remaining / liquidity;
+            return Some(2.0 * sqrt_price.ln() / 1.0001f64.ln());
+        }
+        remaining -= capacity;
+    }
+    None
+}
+
+/// Tick the price moves to after swapping `amount` raw token0 into the pool (price goes down).
+///
+/// Returns `None` when the initialised liquidity runs out before the amount is absorbed.
+pub fn tick_after_token0_in(
+    distribution: &[(i32, f64)],
+    from_tick: f64,
+    amount: f64,
+) -> Option<f64> {
+    let mut remaining = amount;
+    for (start, end, liquidity) in liquidity_segments(distribution).rev() {
+        if start >= from_tick || liquidity <= 0.0 {
+            continue;
+        }
+        let upper = end.min(from_tick);
+        let capacity = liquidity * (1.0 / sqrt_price_at(start) - 1.0 / sqrt_price_at(upper));
+        if remaining <= capacity {
+            let inverse_sqrt_price = 1.0 / sqrt_price_at(upper) + remaining / liquidity;
+            return Some(-2.0 * inverse_sqrt_price.ln() / 1.0001f64.ln());
+        }
+        remaining -= capacity;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::services::data::LiquidityTick;
+
+    fn ticks(shape: TickDataShape, ticks: &[(i32, &str)]) -> ActiveLiquidityResponse {
+        ActiveLiquidityResponse {
+            data: ticks
+                .iter()
+                .map(|(tick, liquidity)| LiquidityTick {
+                    tick_idx: tick.to_string(),
+                    liquidity_net: liquidity.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            tick_shape: shape,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accumulates_liquidity_net_deltas() {
+        // Unsorted, and without a negative delta in the page that was read
+        let liquidity = ticks(
+            TickDataShape::LiquidityNet,
+            &[(60, "50"), (-60, "100"), (120, "25")],
+        );
+
+        assert_eq!(
+            liquidity_distribution(&liquidity),
+            vec![(-60, 100.0), (60, 150.0), (120, 175.0)]
+        );
+        assert_eq!(active_liquidity_at_tick(&liquidity, 0), Some(100.0));
+        assert_eq!(active_liquidity_at_tick(&liquidity, -61), None);
+    }
+
+    #[test]
+    fn keeps_active_liquidity_as_reported() {
+        let liquidity = ticks(
+            TickDataShape::ActiveLiquidity,
+            &[(-60, "100"), (60, "40"), (120, "0")],
+        );
+
+        assert_eq!(
+            liquidity_distribution(&liquidity),
+            vec![(-60, 100.0), (60, 40.0), (120, 0.0)]
+        );
+        assert_eq!(active_liquidity_at_tick(&liquidity, 90), Some(40.0));
+    }
+
+    #[test]
+    fn handles_empty_and_unparseable_ticks() {
+        let empty = ticks(TickDataShape::LiquidityNet, &[]);
+        assert!(liquidity_distribution(&empty).is_empty());
+        assert_eq!(active_liquidity_at_tick(&empty, 0), None);
+        assert_eq!(liquidity_concentration(&[], 0, 100), None);
+
+        let garbage = ticks(TickDataShape::ActiveLiquidity, &[(0, "n/a"), (60, "10")]);
+        assert_eq!(liquidity_distribution(&garbage), vec![(60, 10.0)]);
+    }
+
+    #[test]
+    fn measures_concentration_by_tick_width() {
+        let distribution = [(-100, 10.0), (-10, 30.0), (10, 10.0), (100, 0.0)];
+
+        // 600 of the 2400 liquidity-ticks sit within 10 ticks of the price
+        let near = liquidity_concentration(&distribution, 0, 10).unwrap();
+        assert!((near - 0.25).abs() < 1e-12);
+        assert_eq!(liquidity_concentration(&[(0, 0.0), (10, 0.0)], 0, 10), None);
+    }
+
+    #[test]
+    fn depth_and_price_impact_agree() {
+        let distribution = [(-1000, 1e6), (1000, 0.0)];
+
+        let depth = token1_depth(&distribution, 0.0, 500.0);
+        let tick = tick_after_token1_in(&distribution, 0.0, depth).unwrap();
+        assert!((tick - 500.0).abs() < 1e-6);
+
+        let depth = token0_depth(&distribution, 0.0, -500.0);
+        let tick = tick_after_token0_in(&distribution, 0.0, depth).unwrap();
+        assert!((tick + 500.0).abs() < 1e-6);
+
+        // More than the initialised range holds
+        let all = token1_depth(&distribution, 0.0, 1000.0);
+        assert_eq!(tick_after_token1_in(&distribution, 0.0, all * 2.0), None);
+        // Zero liquidity absorbs nothing
+        assert_eq!(tick_after_token1_in(&[(0, 0.0), (10, 0.0)], 0.0, 1.0), None);
+    }
+}
@@ -1,2 +1,22 @@
+pub mod backtest;
+pub mod correlation;
+pub mod fee_apr;
+pub mod indicators;
+pub mod liquidity;
+pub mod monte_carlo;
 pub mod price_analysis;
+pub mod range_optimizer;
+pub mod rebalance;
+pub mod risk;
+pub mod statistics;
+pub use backtest::*;
+pub use correlation::*;
+pub use fee_apr::*;
+pub use indicators::*;
+pub use liquidity::*;
+pub use monte_carlo::*;
 pub use price_analysis::*;
+pub use range_optimizer::*;
+pub use rebalance::*;
+pub use risk::*;
+pub use statistics::*;
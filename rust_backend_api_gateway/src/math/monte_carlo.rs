@@ -0,0 +1,290 @@
+use crate::math::statistics::{mean, std_dev};
+use anyhow::{Result, anyhow};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Models accepted by `ReturnModel::calibrate`
+pub const RETURN_MODELS: [&str; 2] = ["gbm", "bootstrap"];
+
+/// Most steps a single simulated path takes; longer horizons are simulated in coarser steps
+pub const MAX_SIMULATION_STEPS: usize = 2_000;
+
+/// Number of candles of `interval_minutes` covering `days`, or `None` for a zero interval or a
+/// horizon that does not fit in memory
+pub fn horizon_candles(days: u32, interval_minutes: u32) -> Option<usize> {
+    if interval_minutes == 0 {
+        return None;
+    }
+    let minutes = u64::from(days).checked_mul(1440)?;
+    usize::try_from(minutes.div_ceil(u64::from(interval_minutes)).max(1)).ok()
+}
+
+/// Candles folded into each simulated step so that `candles` fits in `MAX_SIMULATION_STEPS`
+pub fn candles_per_step(candles: usize) -> usize {
+    candles.div_ceil(MAX_SIMULATION_STEPS).max(1)
+}
+
+/// Distribution that per-candle log returns are drawn from
+#[derive(Debug, Clone)]
+pub enum ReturnModel {
+    /// Geometric Brownian motion with per-candle drift and volatility of log returns
+    Gbm { drift: f64, volatility: f64 },
+    /// Resample historical per-candle log returns with replacement
+    Bootstrap { returns: Vec<f64> },
+}
+
+impl ReturnModel {
+    /// Fit the model named `name` ("gbm" or "bootstrap") to observed log returns
+    pub fn calibrate(name: &str, returns: &[f64]) -> Result<Self> {
+        match name {
+            "gbm" => Ok(ReturnModel::gbm_from_returns(returns)),
+            "bootstrap" => Ok(ReturnModel::bootstrap_from_returns(returns)),
+            other => Err(anyhow!("Unsupported simulation model: {}", other)),
+        }
+    }
+
+    /// Fit a GBM to observed log returns
+    pub fn gbm_from_returns(returns: &[f64]) -> Self {
+        ReturnModel::Gbm {
+            drift: mean(returns),
+            volatility: std_dev(returns),
+        }
+    }
+
+    pub fn bootstrap_from_returns(returns: &[f64]) -> Self {
+        ReturnModel::Bootstrap {
+            returns: returns.to_vec(),
+        }
+    }
+
+    /// Model of the log return over `candles` consecutive candles, so one step covers them all.
+    ///
+    /// GBM scales exactly. Bootstrap resamples the sums of overlapping `candles`-long windows,
+    /// falling back to a GBM fit when the sample is shorter than a window.
+    pub fn over_candles(&self, candles: usize) -> Self {
+        if candles <= 1 {
+            return self.clone();
+        }
+        match self {
+            ReturnModel::Gbm { drift, volatility } => ReturnModel::Gbm {
+                drift: drift * candles as f64,
+                volatility: volatility * (candles as f64).sqrt(),
+            },
+            ReturnModel::Bootstrap { returns } if returns.len() >= candles => {
+                ReturnModel::Bootstrap {
+                    returns: returns.windows(candles).map(|w| w.iter().sum()).collect(),
+                }
+            }
+            ReturnModel::Bootstrap { returns } => {
+                ReturnModel::gbm_from_returns(returns).over_candles(candles)
+            }
+        }
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        match self {
+            ReturnModel::Gbm { drift, volatility } => drift + volatility * standard_normal(rng),
+            ReturnModel::Bootstrap { returns } => {
+                if returns.is_empty() {
+                    0.0
+                } else {
+                    returns[rng.gen_range(0..returns.len())]
+                }
+            }
+        }
+    }
+}
+
+/// Box-Muller transform
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.r#gen::<f64>();
+    let u2: f64 = rng.r#gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Simulate `paths` price paths of `steps` candles each, starting from `start_price`.
+///
+/// The start price itself is not included in the returned paths.
+pub fn simulate_price_paths(
+    model: &ReturnModel,
+    start_price: f64,
+    steps: usize,
+    paths: usize,
+    seed: Option<u64>,
+) -> Vec<Vec<f64>> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    (0..paths)
+        .map(|_| {
+            let mut log_price = start_price.ln();
+            (0..steps)
+                .map(|_| {
+                    log_price += model.sample(&mut rng);
+                    log_price.exp()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Estimate the probability that the price stays inside each range for each horizon.
+///
+/// Paths start at `start_price` and advance one candle per step; a path leaves a range the
+/// first time a simulated close falls outside it. Returns one row per range with one
+/// probability per entry of `horizons_steps`. Passing a `seed` makes the result reproducible.
+pub fn estimate_range_survival(
+    model: &ReturnModel,
+    start_price: f64,
+    ranges: &[(f64, f64)],
+    horizons_steps: &[usize],
+    paths: usize,
+    seed: Option<u64>,
+) -> Vec<Vec<f64>> {
+    let max_steps = horizons_steps.iter().copied().max().unwrap_or(0);
+    if paths == 0 || start_price <= 0.0 {
+        return vec![vec![0.0; horizons_steps.len()]; ranges.len()];
+    }
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    // survived[range][horizon] counts paths still in range at that horizon
+    let mut survived = vec![vec![0usize; horizons_steps.len()]; ranges.len()];
+    let mut exit_steps = vec![usize::MAX; ranges.len()];
+
+    for _ in 0..paths {
+        exit_steps.fill(usize::MAX);
+        let mut log_price = start_price.ln();
+        let mut remaining = ranges.len();
+
+        for step in 1..=max_steps {
+            log_price += model.sample(&mut rng);
+            let price = log_price.exp();
+
+            for (exit_step, (lower, upper)) in exit_steps.iter_mut().zip(ranges) {
+                if *exit_step == usize::MAX && (price < *lower || price > *upper) {
+                    *exit_step = step;
+                    remaining -= 1;
+                }
+            }
+
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        for (range_index, exit_step) in exit_steps.iter().enumerate() {
+            for (horizon_index, horizon) in horizons_steps.iter().enumerate() {
+                if *exit_step > *horizon {
+                    survived[range_index][horizon_index] += 1;
+                }
+            }
+        }
+    }
+
+    survived
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|count| count as f64 / paths as f64)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproduces_survival_with_the_same_seed() {
+        let model = ReturnModel::Gbm {
+            drift: 0.0,
+            volatility: 0.02,
+        };
+        let ranges = [(0.9, 1.1), (0.8, 1.25)];
+        let run = |seed| estimate_range_survival(&model, 1.0, &ranges, &[24, 168], 500, seed);
+
+        assert_eq!(run(Some(7)), run(Some(7)));
+        assert_ne!(run(Some(7)), run(Some(8)));
+    }
+
+    #[test]
+    fn survival_shrinks_with_the_horizon_and_grows_with_the_width() {
+        let model = ReturnModel::Gbm {
+            drift: 0.0,
+            volatility: 0.02,
+        };
+        let survival = estimate_range_survival(
+            &model,
+            1.0,
+            &[(0.9, 1.1), (0.5, 2.0)],
+            &[24, 168],
+            1000,
+            Some(1),
+        );
+        assert!(survival[0][0] >= survival[0][1]);
+        assert!(survival[1][1] >= survival[0][1]);
+        assert_eq!(survival[1][0], 1.0);
+    }
+
+    #[test]
+    fn gbm_paths_follow_the_drift() {
+        let (drift, volatility, steps) = (0.001, 0.01, 100);
+        let model = ReturnModel::Gbm { drift, volatility };
+        let paths = simulate_price_paths(&model, 1.0, steps, 5000, Some(42));
+
+        let end_log_returns: Vec<f64> = paths.iter().map(|p| p[steps - 1].ln()).collect();
+        // Mean log return is drift * steps with standard error volatility * sqrt(steps / paths)
+        let standard_error = volatility * (steps as f64 / 5000.0).sqrt();
+        assert!((mean(&end_log_returns) - drift * steps as f64).abs() < 4.0 * standard_error);
+        assert!((std_dev(&end_log_returns) - volatility * 10.0).abs() < 0.005);
+    }
+
+    #[test]
+    fn handles_degenerate_inputs() {
+        let model = ReturnModel::bootstrap_from_returns(&[]);
+        assert_eq!(
+            estimate_range_survival(&model, 1.0, &[(0.9, 1.1)], &[10], 0, Some(1)),
+            vec![vec![0.0]]
+        );
+        // An empty bootstrap sample never moves the price
+        assert_eq!(
+            estimate_range_survival(&model, 1.0, &[(0.9, 1.1)], &[10], 10, Some(1)),
+            vec![vec![1.0]]
+        );
+        assert!(ReturnModel::calibrate("garch", &[0.1, 0.2]).is_err());
+    }
+
+    #[test]
+    fn folds_long_horizons_into_coarser_steps() {
+        assert_eq!(horizon_candles(30, 1), Some(43_200));
+        assert_eq!(horizon_candles(1, 0), None);
+        assert_eq!(horizon_candles(u32::MAX, 1), Some(u32::MAX as usize * 1440));
+        assert_eq!(candles_per_step(43_200), 22);
+        assert_eq!(candles_per_step(100), 1);
+
+        let ReturnModel::Gbm { drift, volatility } = (ReturnModel::Gbm {
+            drift: 0.001,
+            volatility: 0.01,
+        })
+        .over_candles(4) else {
+            panic!("GBM stays GBM");
+        };
+        assert!((drift - 0.004).abs() < 1e-15);
+        assert!((volatility - 0.02).abs() < 1e-15);
+
+        let ReturnModel::Bootstrap { returns } =
+            ReturnModel::bootstrap_from_returns(&[0.1, 0.2, 0.3]).over_candles(2)
+        else {
+            panic!("long enough samples stay bootstrapped");
+        };
+        assert_eq!(returns.len(), 2);
+        assert!((returns[1] - 0.5).abs() < 1e-15);
+    }
+}
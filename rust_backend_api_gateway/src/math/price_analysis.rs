@@ -1,23 +1,16 @@
-/// Calculate volatility from price data using standard deviation
-pub fn calculate_volatility(prices: &[f64]) -> f64 {
-    if prices.len() < 2 {
-        return 0.0;
-    }
+use crate::math::statistics::{daily_volatility, linear_regression};
 
-    let mean = prices.iter().sum::<f64>() / prices.len() as f64;
-    let variance = prices
-        .iter()
-        .map(|price| {
-            let diff = price - mean;
-            diff * diff
-        })
-        .sum::<f64>()
-        / prices.len() as f64;
-
-    (variance.sqrt() / mean).abs()
+/// Minimum R² for a regression slope to count as a trend
+const TREND_MIN_R_SQUARED: f64 = 0.25;
+/// Minimum fitted move over the series for a regression slope to count as a trend
+const TREND_MIN_CHANGE: f64 = 0.02;
+
+/// Calculate daily volatility from close prices using the standard deviation of log returns
+pub fn calculate_volatility(prices: &[f64], interval_minutes: u32) -> f64 {
+    daily_volatility(prices, interval_minutes)
 }
 
-/// Determine volatility level based on volatility value
+/// Determine volatility level based on daily volatility
 pub fn determine_volatility_level(volatility: f64) -> String {
     match volatility {
         v if v < 0.02 => "LOW".to_string(),
@@ -26,33 +19,76 @@ pub fn determine_volatility_level(volatility: f64) -> String {
     }
 }
 
-/// Calculate suggested range width based on volatility
-pub fn calculate_suggested_range_width(volatility: f64) -> f64 {
-    match volatility {
-        v if v < 0.02 => 5.0,
-        v if v < 0.05 => 10.0,
-        v if v < 0.1 => 20.0,
-        _ => 30.0,
-    }
+#[derive(Debug, Clone)]
+pub struct TrendAnalysis {
+    pub direction: String, // UPWARD, DOWNWARD, SIDEWAYS
+    /// Slope of ln(price) per candle
+    pub slope: f64,
+    pub r_squared: f64,
+    /// Fitted relative price change over the whole series
+    pub change: f64,
 }
 
-/// Determine price trend from price data
-pub fn determine_trend(prices: &[f64]) -> String {
-    if prices.len() < 2 {
-        return "SIDEWAYS".to_string();
+/// Fit a linear regression to log prices and classify the trend from its slope and R²
+pub fn analyze_trend(prices: &[f64]) -> TrendAnalysis {
+    let log_prices: Vec<f64> = prices
+        .iter()
+        .filter(|p| **p > 0.0)
+        .map(|p| p.ln())
+        .collect();
+
+    let Some(regression) = linear_regression(&log_prices) else {
+        return TrendAnalysis {
+            direction: "SIDEWAYS".to_string(),
+            slope: 0.0,
+            r_squared: 0.0,
+            change: 0.0,
+        };
+    };
+
+    let change = (regression.slope * (log_prices.len() - 1) as f64).exp() - 1.0;
+
+    let direction = if regression.r_squared < TREND_MIN_R_SQUARED {
+        "SIDEWAYS"
+    } else {
+        match change {
+            c if c > TREND_MIN_CHANGE => "UPWARD",
+            c if c < -TREND_MIN_CHANGE => "DOWNWARD",
+            _ => "SIDEWAYS",
+        }
+    };
+
+    TrendAnalysis {
+        direction: direction.to_string(),
+        slope: regression.slope,
+        r_squared: regression.r_squared,
+        change,
     }
+}
 
-    let first_third = &prices[..prices.len() / 3];
-    let last_third = &prices[prices.len() * 2 / 3..];
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let first_avg = first_third.iter().sum::<f64>() / first_third.len() as f64;
-    let last_avg = last_third.iter().sum::<f64>() / last_third.len() as f64;
+    #[test]
+    fn classifies_trends_from_log_prices() {
+        let rising: Vec<f64> = (0..20).map(|i| 100.0 * 1.01f64.powi(i)).collect();
+        let trend = analyze_trend(&rising);
+        assert_eq!(trend.direction, "UPWARD");
+        assert!((trend.slope - 1.01f64.ln()).abs() < 1e-12);
+        assert!((trend.change - (1.01f64.powi(19) - 1.0)).abs() < 1e-9);
 
-    let change_percent = (last_avg - first_avg) / first_avg;
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        assert_eq!(analyze_trend(&falling).direction, "DOWNWARD");
+        assert_eq!(analyze_trend(&[100.0; 20]).direction, "SIDEWAYS");
+        assert_eq!(analyze_trend(&[100.0]).direction, "SIDEWAYS");
+    }
 
-    match change_percent {
-        c if c > 0.02 => "UPWARD".to_string(),
-        c if c < -0.02 => "DOWNWARD".to_string(),
-        _ => "SIDEWAYS".to_string(),
+    #[test]
+    fn buckets_daily_volatility() {
+        assert_eq!(determine_volatility_level(0.01), "LOW");
+        assert_eq!(determine_volatility_level(0.03), "MEDIUM");
+        assert_eq!(determine_volatility_level(0.08), "HIGH");
+        assert_eq!(calculate_volatility(&[100.0], 60), 0.0);
     }
 }
@@ -0,0 +1,355 @@
+use crate::domain::services::fee_tier::FeeTier;
+use crate::math::backtest::{liquidity_for_capital, position_amounts, position_value};
+use crate::math::monte_carlo::{ReturnModel, simulate_price_paths};
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+pub const MIN_TICK: i32 = -887272;
+pub const MAX_TICK: i32 = 887272;
+const TICK_BASE: f64 = 1.0001;
+
+/// Default smallest and largest half-widths (as fractions of the current price) to search
+pub const DEFAULT_MIN_HALF_WIDTH: f64 = 0.005;
+pub const DEFAULT_MAX_HALF_WIDTH: f64 = 0.6;
+const HALF_WIDTH_STEPS: usize = 24;
+
+/// Tick for a human-readable price (token1 per token0), not aligned to any spacing
+pub fn price_to_tick(price: f64, token0_decimals: u32, token1_decimals: u32) -> f64 {
+    let raw_price = price * 10f64.powi(token1_decimals as i32 - token0_decimals as i32);
+    raw_price.ln() / TICK_BASE.ln()
+}
+
+/// Human-readable price (token1 per token0) at `tick`
+pub fn tick_to_price(tick: i32, token0_decimals: u32, token1_decimals: u32) -> f64 {
+    TICK_BASE.powi(tick) * 10f64.powi(token0_decimals as i32 - token1_decimals as i32)
+}
+
+/// Round a tick down to the nearest usable multiple of `spacing`
+pub fn align_tick_down(tick: f64, spacing: i32) -> i32 {
+    let min_usable = (MIN_TICK as f64 / spacing as f64).ceil() as i32 * spacing;
+    ((tick / spacing as f64).floor() as i32 * spacing).max(min_usable)
+}
+
+/// Round a tick up to the nearest usable multiple of `spacing`
+pub fn align_tick_up(tick: f64, spacing: i32) -> i32 {
+    let max_usable = (MAX_TICK as f64 / spacing as f64).floor() as i32 * spacing;
+    ((tick / spacing as f64).ceil() as i32 * spacing).min(max_usable)
+}
+
+/// How much impermanent loss the user is willing to trade for fees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RiskProfile {
+    Conservative,
+    Balanced,
+    Aggressive,
+}
+
+impl RiskProfile {
+    /// Weight applied to expected impermanent loss in the objective
+    pub fn il_weight(&self) -> f64 {
+        match self {
+            RiskProfile::Conservative => 1.5,
+            RiskProfile::Balanced => 1.0,
+            RiskProfile::Aggressive => 0.75,
+        }
+    }
+
+    /// Minimum expected share of the horizon spent in range
+    pub fn min_time_in_range(&self) -> f64 {
+        match self {
+            RiskProfile::Conservative => 0.8,
+            RiskProfile::Balanced => 0.6,
+            RiskProfile::Aggressive => 0.4,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskProfile::Conservative => "conservative",
+            RiskProfile::Balanced => "balanced",
+            RiskProfile::Aggressive => "aggressive",
+        }
+    }
+}
+
+impl FromStr for RiskProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "conservative" => Ok(RiskProfile::Conservative),
+            "balanced" => Ok(RiskProfile::Balanced),
+            "aggressive" => Ok(RiskProfile::Aggressive),
+            other => Err(anyhow!("Unsupported risk profile: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RangeOptimizerParams {
+    pub current_price: f64,
+    pub token0_decimals: u32,
+    pub token1_decimals: u32,
+    /// Sets the tick spacing candidates are aligned to
+    pub fee_tier: FeeTier,
+    /// Pool-wide fee APR in percent, i.e. what a full-range position earns
+    pub pool_apr: f64,
+    /// Number of candles in the simulated horizon
+    pub horizon_steps: usize,
+    /// Candles per year for the kline interval, used to spread the APR over steps
+    pub periods_per_year: f64,
+    pub risk_profile: RiskProfile,
+    /// Half-width bounds of the candidate grid, as fractions of the current price
+    pub min_half_width: f64,
+    pub max_half_width: f64,
+    pub paths: usize,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OptimizedRange {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub tick_spacing: i32,
+    pub current_tick: i32,
+    pub price_lower: f64,
+    pub price_upper: f64,
+    /// Expected fees over the horizon as a fraction of capital
+    pub expected_fees: f64,
+    /// Expected impermanent loss versus holding, as a (positive) fraction of capital
+    pub expected_il: f64,
+    pub expected_time_in_range: f64,
+    /// `expected_fees - il_weight * expected_il`
+    pub objective: f64,
+}
+
+/// Search tick-aligned ranges around the current price for the best fee-adjusted return.
+///
+/// Price paths are drawn from `model`; for each candidate, fees accrue per in-range step at
+/// the pool APR scaled by the range's capital efficiency, and IL is measured against holding
+/// the initial token amounts at the end of the horizon. Candidates spending less than the
+/// risk profile's minimum time in range are skipped unless nothing qualifies.
+pub fn optimize_range(
+    model: &ReturnModel,
+    params: &RangeOptimizerParams,
+) -> Result<OptimizedRange> {
+    let price = params.current_price;
+    if price <= 0.0 {
+        return Err(anyhow!("Current price must be positive"));
+    }
+    if params.min_half_width <= 0.0
+        || params.min_half_width > params.max_half_width
+        || params.max_half_width >= 1.0
+    {
+        return Err(anyhow!("Invalid half-width bounds for the range search"));
+    }
+    if params.horizon_steps == 0 || params.paths == 0 || params.periods_per_year <= 0.0 {
+        return Err(anyhow!("Optimizer horizon and path count must be positive"));
+    }
+
+    let spacing = params.fee_tier.tick_spacing();
+    let decimals = (params.token0_decimals, params.token1_decimals);
+    let current_tick = price_to_tick(price, decimals.0, decimals.1);
+
+    let paths = simulate_price_paths(
+        model,
+        price,
+        params.horizon_steps,
+        params.paths,
+        params.seed,
+    );
+    let step_yield = params.pool_apr / 100.0 / params.periods_per_year;
+
+    let mut best: Option<OptimizedRange> = None;
+    let mut best_unconstrained: Option<OptimizedRange> = None;
+
+    for (tick_lower, tick_upper) in candidate_ticks(
+        current_tick,
+        spacing,
+        params.min_half_width,
+        params.max_half_width,
+    ) {
+        let lower = tick_to_price(tick_lower, decimals.0, decimals.1);
+        let upper = tick_to_price(tick_upper, decimals.0, decimals.1);
+        if !(lower < price && price < upper) {
+            continue;
+        }
+
+        let liquidity = liquidity_for_capital(1.0, price, lower, upper);
+        let (amount0, amount1) = position_amounts(liquidity, price, lower, upper);
+
+        let (mut fees, mut il, mut in_range) = (0.0, 0.0, 0.0);
+        for path in &paths {
+            let mut steps_in_range = 0usize;
+            for p in path {
+                if *p >= lower && *p <= upper {
+                    steps_in_range += 1;
+                    fees += step_yield * 2.0 * p.sqrt() * liquidity;
+                }
+            }
+            let end = *path.last().unwrap_or(&price);
+            let hold = amount0 * end + amount1;
+            il += hold - position_value(liquidity, end, lower, upper);
+            in_range += steps_in_range as f64 / path.len() as f64;
+        }
+
+        let n = paths.len() as f64;
+        let expected_fees = fees / n;
+        let expected_il = (il / n).max(0.0);
+        let expected_time_in_range = in_range / n;
+
+        let candidate = OptimizedRange {
+            tick_lower,
+            tick_upper,
+            tick_spacing: spacing,
+            current_tick: current_tick.floor() as i32,
+            price_lower: lower,
+            price_upper: upper,
+            expected_fees,
+            expected_il,
+            expected_time_in_range,
+            objective: expected_fees - params.risk_profile.il_weight() * expected_il,
+        };
+
+        if best_unconstrained
+            .as_ref()
+            .is_none_or(|b| candidate.objective > b.objective)
+        {
+            best_unconstrained = Some(candidate.clone());
+        }
+        if expected_time_in_range >= params.risk_profile.min_time_in_range()
+            && best
+                .as_ref()
+                .is_none_or(|b| candidate.objective > b.objective)
+        {
+            best = Some(candidate);
+        }
+    }
+
+    best.or(best_unconstrained)
+        .ok_or_else(|| anyhow!("No valid tick range found around the current price"))
+}
+
+/// Tick-aligned (lower, upper) pairs from a geometric grid of half-widths on each side
+fn candidate_ticks(
+    current_tick: f64,
+    spacing: i32,
+    min_half_width: f64,
+    max_half_width: f64,
+) -> Vec<(i32, i32)> {
+    let ratio = (max_half_width / min_half_width).powf(1.0 / (HALF_WIDTH_STEPS - 1) as f64);
+    let half_widths: Vec<f64> = (0..HALF_WIDTH_STEPS)
+        .map(|i| min_half_width * ratio.powi(i as i32))
+        .collect();
+
+    let tick_offset = |factor: f64| factor.ln() / TICK_BASE.ln();
+
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    for lower_width in &half_widths {
+        let tick_lower = align_tick_down(current_tick + tick_offset(1.0 - lower_width), spacing);
+        for upper_width in &half_widths {
+            let tick_upper = align_tick_up(current_tick + tick_offset(1.0 + upper_width), spacing);
+            if tick_lower < tick_upper && seen.insert((tick_lower, tick_upper)) {
+                candidates.push((tick_lower, tick_upper));
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(risk_profile: RiskProfile) -> RangeOptimizerParams {
+        RangeOptimizerParams {
+            current_price: 2.0,
+            token0_decimals: 18,
+            token1_decimals: 6,
+            fee_tier: FeeTier::from_hundredths_bip(2500),
+            pool_apr: 30.0,
+            horizon_steps: 48,
+            periods_per_year: 8760.0,
+            risk_profile,
+            min_half_width: DEFAULT_MIN_HALF_WIDTH,
+            max_half_width: DEFAULT_MAX_HALF_WIDTH,
+            paths: 100,
+            seed: Some(3),
+        }
+    }
+
+    fn model() -> ReturnModel {
+        ReturnModel::Gbm {
+            drift: 0.0,
+            volatility: 0.01,
+        }
+    }
+
+    #[test]
+    fn converts_between_prices_and_ticks() {
+        assert!(price_to_tick(1.0, 18, 18).abs() < 1e-9);
+        assert!((price_to_tick(1.0001, 18, 18) - 1.0).abs() < 1e-6);
+        assert!((tick_to_price(-276324, 18, 6) - 1.0).abs() < 1e-3);
+        let tick = price_to_tick(2.0, 18, 6);
+        assert!((tick_to_price(tick.round() as i32, 18, 6) / 2.0 - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn aligns_ticks_outward_within_bounds() {
+        assert_eq!(align_tick_down(125.0, 60), 120);
+        assert_eq!(align_tick_up(125.0, 60), 180);
+        assert_eq!(align_tick_down(-125.0, 60), -180);
+        assert_eq!(align_tick_up(-125.0, 60), -120);
+        assert_eq!(align_tick_down(-1e7, 60), -887220);
+        assert_eq!(align_tick_up(1e7, 60), 887220);
+    }
+
+    #[test]
+    fn parses_risk_profiles() {
+        assert_eq!(
+            " Aggressive".parse::<RiskProfile>().unwrap(),
+            RiskProfile::Aggressive
+        );
+        assert!("reckless".parse::<RiskProfile>().is_err());
+    }
+
+    #[test]
+    fn finds_an_aligned_range_around_the_price() {
+        let optimized = optimize_range(&model(), &params(RiskProfile::Balanced)).unwrap();
+
+        // Non-standard tiers take FeeTier's spacing, 2500 / 50
+        assert_eq!(optimized.tick_spacing, 50);
+        assert_eq!(optimized.tick_lower % optimized.tick_spacing, 0);
+        assert_eq!(optimized.tick_upper % optimized.tick_spacing, 0);
+        assert!(optimized.price_lower < 2.0 && 2.0 < optimized.price_upper);
+        assert!(optimized.expected_time_in_range >= RiskProfile::Balanced.min_time_in_range());
+        assert!(optimized.expected_fees > 0.0);
+    }
+
+    #[test]
+    fn widens_ranges_for_conservative_profiles() {
+        let width = |profile| {
+            let optimized = optimize_range(&model(), &params(profile)).unwrap();
+            optimized.tick_upper - optimized.tick_lower
+        };
+        assert!(width(RiskProfile::Conservative) >= width(RiskProfile::Aggressive));
+    }
+
+    #[test]
+    fn rejects_degenerate_searches() {
+        let mut invalid = params(RiskProfile::Balanced);
+        invalid.current_price = 0.0;
+        assert!(optimize_range(&model(), &invalid).is_err());
+
+        let mut invalid = params(RiskProfile::Balanced);
+        invalid.horizon_steps = 0;
+        assert!(optimize_range(&model(), &invalid).is_err());
+
+        let mut invalid = params(RiskProfile::Balanced);
+        invalid.min_half_width = 0.5;
+        invalid.max_half_width = 0.1;
+        assert!(optimize_range(&model(), &invalid).is_err());
+    }
+}
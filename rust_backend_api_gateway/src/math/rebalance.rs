@@ -0,0 +1,416 @@
+use crate::application::dtos::price_history::PricePoint;
+use crate::math::backtest::{
+    estimate_candle_fees, liquidity_for_capital, position_amounts, position_value,
+};
+use crate::math::statistics::{log_returns, std_dev};
+
+/// Decides where an LP position should sit as the price series unfolds.
+///
+/// `history` holds every candle up to and including the current one.
+pub trait RebalanceStrategy {
+    fn name(&self) -> String;
+
+    /// Range (lower, upper) to open the first position with
+    fn initial_range(&self, history: &[PricePoint]) -> (f64, f64);
+
+    /// New range to move the position to, or `None` to keep the current one
+    fn rebalance(&self, history: &[PricePoint], current: (f64, f64)) -> Option<(f64, f64)>;
+}
+
+fn centered_range(price: f64, width_percent: f64) -> (f64, f64) {
+    (
+        price * (1.0 - width_percent / 100.0),
+        price * (1.0 + width_percent / 100.0),
+    )
+}
+
+fn current_price(history: &[PricePoint]) -> f64 {
+    history.last().map(|p| p.close).unwrap_or(0.0)
+}
+
+/// Opens a ±`width_percent` range once and never touches it again
+pub struct FixedRange {
+    pub width_percent: f64,
+}
+
+impl RebalanceStrategy for FixedRange {
+    fn name(&self) -> String {
+        format!("fixed_{}", self.width_percent)
+    }
+
+    fn initial_range(&self, history: &[PricePoint]) -> (f64, f64) {
+        centered_range(current_price(history), self.width_percent)
+    }
+
+    fn rebalance(&self, _history: &[PricePoint], _current: (f64, f64)) -> Option<(f64, f64)> {
+        None
+    }
+}
+
+/// Recentres a ±`width_percent` range whenever the price leaves it
+pub struct RecenterOnExit {
+    pub width_percent: f64,
+}
+
+impl RebalanceStrategy for RecenterOnExit {
+    fn name(&self) -> String {
+        format!("recenter_on_exit_{}", self.width_percent)
+    }
+
+    fn initial_range(&self, history: &[PricePoint]) -> (f64, f64) {
+        centered_range(current_price(history), self.width_percent)
+    }
+
+    fn rebalance(&self, history: &[PricePoint], (lower, upper): (f64, f64)) -> Option<(f64, f64)> {
+        let price = current_price(history);
+        if price < lower || price > upper {
+            Some(centered_range(price, self.width_percent))
+        } else {
+            None
+        }
+    }
+}
+
+/// Recentres a ±`width_percent` range when the price leaves its inner band.
+///
+/// `inner_fraction` is the share of the range that counts as the inner band, e.g. 0.5
+/// recentres once the price moves past the middle 50%.
+pub struct TrailingRange {
+    pub width_percent: f64,
+    pub inner_fraction: f64,
+}
+
+impl RebalanceStrategy for TrailingRange {
+    fn name(&self) -> String {
+        format!(
+            "trailing_{}_inner_{}",
+            self.width_percent,
+            self.inner_fraction * 100.0
+        )
+    }
+
+    fn initial_range(&self, history: &[PricePoint]) -> (f64, f64) {
+        centered_range(current_price(history), self.width_percent)
+    }
+
+    fn rebalance(&self, history: &[PricePoint], (lower, upper): (f64, f64)) -> Option<(f64, f64)> {
+        let price = current_price(history);
+        let margin = (upper - lower) * (1.0 - self.inner_fraction) / 2.0;
+        if price < lower + margin || price > upper - margin {
+            Some(centered_range(price, self.width_percent))
+        } else {
+            None
+        }
+    }
+}
+
+/// Sizes the range from the rolling volatility of log returns and recentres when out of range.
+///
+/// The half-width is `multiplier` standard deviations of the return over `window` candles,
+/// clamped to [`min_width_percent`, `max_width_percent`].
+pub struct VolatilityScaledRange {
+    pub window: usize,
+    pub multiplier: f64,
+    pub min_width_percent: f64,
+    pub max_width_percent: f64,
+}
+
+impl VolatilityScaledRange {
+    fn width_percent(&self, history: &[PricePoint]) -> f64 {
+        let start = history.len().saturating_sub(self.window + 1);
+        let closes: Vec<f64> = history[start..].iter().map(|p| p.close).collect();
+        let returns = log_returns(&closes);
+
+        if returns.len() < 2 {
+            return self.max_width_percent;
+        }
+
+        let window_volatility = std_dev(&returns) * (self.window as f64).sqrt();
+
+        (window_volatility * self.multiplier * 100.0)
+            .clamp(self.min_width_percent, self.max_width_percent)
+    }
+}
+
+impl RebalanceStrategy for VolatilityScaledRange {
+    fn name(&self) -> String {
+        format!("volatility_scaled_{}x_{}", self.multiplier, self.window)
+    }
+
+    fn initial_range(&self, history: &[PricePoint]) -> (f64, f64) {
+        centered_range(current_price(history), self.width_percent(history))
+    }
+
+    fn rebalance(&self, history: &[PricePoint], (lower, upper): (f64, f64)) -> Option<(f64, f64)> {
+        let price = current_price(history);
+        if price < lower || price > upper {
+            Some(centered_range(price, self.width_percent(history)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Costs and pool parameters shared by every simulated strategy
+#[derive(Debug, Clone)]
+pub struct SimulationParams {
+    /// Capital deposited at the first candle, in token1 units
+    pub capital: f64,
+    /// Swap fee as a fraction, e.g. 0.003 for a 0.3% pool
+    pub fee_rate: f64,
+    /// Pool TVL in token1 units
+    pub pool_tvl: f64,
+    /// Flat gas cost charged per rebalance, in token1 units
+    pub gas_cost: f64,
+    /// Cost of swapping into the new range as a fraction of the swapped notional
+    pub swap_cost_rate: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct EquityPoint {
+    pub timestamp: i64,
+    pub price: f64,
+    pub equity: f64,
+    pub hold_value: f64,
+    pub lower_price: f64,
+    pub upper_price: f64,
+    pub in_range: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    pub strategy: String,
+    pub equity_curve: Vec<EquityPoint>,
+    pub final_equity: f64,
+    pub hold_value: f64,
+    pub fees_earned: f64,
+    pub rebalance_costs: f64,
+    pub rebalance_count: usize,
+    pub time_in_range: f64,
+    pub pnl: f64,
+    pub pnl_percent: f64,
+    pub max_drawdown: f64,
+}
+
+/// Replay a price series with a strategy that may move the range between candles.
+///
+/// Fees accrue to a cash balance and are not compounded. Each rebalance withdraws the
+/// position, pays the gas cost plus the swap cost on the token1 amount that changes hands,
+/// and redeposits the rest into the new range.
+pub fn simulate_strategy(
+    prices: &[PricePoint],
+    strategy: &dyn RebalanceStrategy,
+    params: &SimulationParams,
+) -> SimulationResult {
+    let mut equity_curve = Vec::with_capacity(prices.len());
+
+    let Some(first) = prices.first() else {
+        return SimulationResult {
+            strategy: strategy.name(),
+            equity_curve,
+            final_equity: params.capital,
+            hold_value: params.capital,
+            fees_earned: 0.0,
+            rebalance_costs: 0.0,
+            rebalance_count: 0,
+            time_in_range: 0.0,
+            pnl: 0.0,
+            pnl_percent: 0.0,
+            max_drawdown: 0.0,
+        };
+    };
+
+    let (mut lower, mut upper) = strategy.initial_range(&prices[..1]);
+    let mut liquidity = liquidity_for_capital(params.capital, first.close, lower, upper);
+    let (hold_amount0, hold_amount1) = position_amounts(liquidity, first.close, lower, upper);
+
+    let mut fees_earned = 0.0;
+    let mut rebalance_costs = 0.0;
+    let mut rebalance_count = 0usize;
+    let mut candles_in_range = 0usize;
+    let mut peak_equity = params.capital;
+    let mut max_drawdown: f64 = 0.0;
+
+    for (index, point) in prices.iter().enumerate() {
+        let price = point.close;
+
+        if index > 0
+            && let Some((new_lower, new_upper)) =
+                strategy.rebalance(&prices[..=index], (lower, upper))
+        {
+            let (_, old_amount1) = position_amounts(liquidity, price, lower, upper);
+            let withdrawn = position_value(liquidity, price, lower, upper);
+
+            let target_liquidity = liquidity_for_capital(withdrawn, price, new_lower, new_upper);
+            let (_, target_amount1) =
+                position_amounts(target_liquidity, price, new_lower, new_upper);
+            let cost =
+                params.gas_cost + (target_amount1 - old_amount1).abs() * params.swap_cost_rate;
+
+            let redeposited = (withdrawn - cost).max(0.0);
+            lower = new_lower;
+            upper = new_upper;
+            liquidity = liquidity_for_capital(redeposited, price, lower, upper);
+            rebalance_costs += cost;
+            rebalance_count += 1;
+        }
+
+        let in_range = price >= lower && price <= upper;
+        if in_range {
+            candles_in_range += 1;
+            fees_earned += estimate_candle_fees(point, liquidity, params.fee_rate, params.pool_tvl);
+        }
+
+        let equity = position_value(liquidity, price, lower, upper) + fees_earned;
+        peak_equity = peak_equity.max(equity);
+        if peak_equity > 0.0 {
+            max_drawdown = max_drawdown.max((peak_equity - equity) / peak_equity);
+        }
+
+        equity_curve.push(EquityPoint {
+            timestamp: point.tick,
+            price,
+            equity,
+            hold_value: hold_amount0 * price + hold_amount1,
+            lower_price: lower,
+            upper_price: upper,
+            in_range,
+        });
+    }
+
+    let final_equity = equity_curve
+        .last()
+        .map(|p| p.equity)
+        .unwrap_or(params.capital);
+    let hold_value = equity_curve
+        .last()
+        .map(|p| p.hold_value)
+        .unwrap_or(params.capital);
+    let pnl = final_equity - params.capital;
+    let pnl_percent = if params.capital > 0.0 {
+        pnl / params.capital * 100.0
+    } else {
+        0.0
+    };
+
+    SimulationResult {
+        strategy: strategy.name(),
+        equity_curve,
+        final_equity,
+        hold_value,
+        fees_earned,
+        rebalance_costs,
+        rebalance_count,
+        time_in_range: candles_in_range as f64 / prices.len() as f64,
+        pnl,
+        pnl_percent,
+        max_drawdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(closes: &[f64]) -> Vec<PricePoint> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| PricePoint {
+                tick: i as i64 * 3600,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: Some(0.0),
+            })
+            .collect()
+    }
+
+    fn params(gas_cost: f64) -> SimulationParams {
+        SimulationParams {
+            capital: 1000.0,
+            fee_rate: 0.003,
+            pool_tvl: 1_000_000.0,
+            gas_cost,
+            swap_cost_rate: 0.0,
+        }
+    }
+
+    #[test]
+    fn returns_the_capital_for_an_empty_series() {
+        let result = simulate_strategy(
+            &[],
+            &FixedRange {
+                width_percent: 10.0,
+            },
+            &params(0.0),
+        );
+        assert_eq!(result.final_equity, 1000.0);
+        assert!(result.equity_curve.is_empty());
+        assert_eq!(result.rebalance_count, 0);
+    }
+
+    #[test]
+    fn never_rebalances_a_fixed_range() {
+        let prices = candles(&[1.0, 1.5, 0.5, 1.0]);
+        let result = simulate_strategy(
+            &prices,
+            &FixedRange {
+                width_percent: 10.0,
+            },
+            &params(1.0),
+        );
+        assert_eq!(result.rebalance_count, 0);
+        assert_eq!(result.rebalance_costs, 0.0);
+        assert_eq!(result.time_in_range, 0.5);
+    }
+
+    #[test]
+    fn recentres_and_pays_gas_when_price_exits() {
+        let prices = candles(&[1.0, 1.2, 1.2]);
+        let result = simulate_strategy(
+            &prices,
+            &RecenterOnExit {
+                width_percent: 10.0,
+            },
+            &params(1.0),
+        );
+        assert_eq!(result.rebalance_count, 1);
+        assert_eq!(result.rebalance_costs, 1.0);
+        assert_eq!(result.time_in_range, 1.0);
+        let last = result.equity_curve.last().unwrap();
+        assert!((last.lower_price - 1.08).abs() < 1e-12);
+        assert!((last.upper_price - 1.32).abs() < 1e-12);
+    }
+
+    #[test]
+    fn trails_once_the_price_leaves_the_inner_band() {
+        let strategy = TrailingRange {
+            width_percent: 10.0,
+            inner_fraction: 0.5,
+        };
+        // Inner band of [0.9, 1.1] is [0.95, 1.05]
+        assert_eq!(strategy.rebalance(&candles(&[1.04]), (0.9, 1.1)), None);
+        assert!(strategy.rebalance(&candles(&[1.06]), (0.9, 1.1)).is_some());
+    }
+
+    #[test]
+    fn clamps_volatility_scaled_widths() {
+        let strategy = VolatilityScaledRange {
+            window: 4,
+            multiplier: 2.0,
+            min_width_percent: 2.0,
+            max_width_percent: 30.0,
+        };
+        // Too little history to measure volatility
+        assert_eq!(strategy.width_percent(&candles(&[1.0])), 30.0);
+        // Flat prices have no volatility
+        assert_eq!(strategy.width_percent(&candles(&[1.0; 6])), 2.0);
+        // Wild swings hit the ceiling
+        assert_eq!(
+            strategy.width_percent(&candles(&[1.0, 2.0, 1.0, 2.0, 1.0])),
+            30.0
+        );
+    }
+}
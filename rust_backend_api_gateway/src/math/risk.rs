@@ -0,0 +1,475 @@
+use crate::domain::services::pool_risk::{PoolRiskScore, RiskFactor};
+use crate::math::statistics::{mean, realized_volatility, std_dev};
+
+/// TVL at or below which the size factor is at maximum risk, and at or above which it is riskless
+const RISKY_TVL: f64 = 10_000.0;
+const SAFE_TVL: f64 = 10_000_000.0;
+/// Coefficient of variation of TVL that counts as maximum instability
+const MAX_TVL_VARIATION: f64 = 0.5;
+/// Daily volume/TVL band considered healthy; below it the pool is idle, above it volume may be washed
+const MIN_HEALTHY_TURNOVER: f64 = 0.05;
+const MAX_HEALTHY_TURNOVER: f64 = 2.0;
+const MAX_SUSPICIOUS_TURNOVER: f64 = 5.0;
+/// Annualised volatility that counts as maximum risk
+const MAX_VOLATILITY: f64 = 1.5;
+/// Share of liquidity within ±2% of the price at or above which the pool counts as deep
+const DEEP_CONCENTRATION: f64 = 0.5;
+/// Days after which a token counts as established on the gateway
+const MATURE_TOKEN_DAYS: f64 = 90.0;
+/// Divergence from other venues, in percent, that counts as maximum risk
+const MAX_DIVERGENCE_PERCENT: f64 = 5.0;
+
+/// Level boundaries on the 0-100 score
+const LOW_RISK_BELOW: f64 = 33.0;
+const MEDIUM_RISK_BELOW: f64 = 66.0;
+
+/// How a token is listed, from most to least established
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenListing {
+    /// Trades on a centralised exchange
+    Exchange,
+    /// Listed by several DEX providers
+    MultiDex,
+    /// Listed by a single DEX provider
+    SingleDex,
+}
+
+impl TokenListing {
+    fn risk(&self) -> f64 {
+        match self {
+            TokenListing::Exchange => 0.0,
+            TokenListing::MultiDex => 0.4,
+            TokenListing::SingleDex => 0.8,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            TokenListing::Exchange => "trades on Binance",
+            TokenListing::MultiDex => "is listed by several DEXes",
+            TokenListing::SingleDex => "is listed by a single DEX",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenMaturity {
+    pub symbol: String,
+    pub listing: TokenListing,
+    /// Days since the gateway first snapshotted the token's price
+    pub age_days: Option<f64>,
+}
+
+/// Everything the score is computed from; missing inputs drop their factor from the score
+#[derive(Debug, Clone, Default)]
+pub struct RiskInputs {
+    pub tvl: Option<f64>,
+    pub daily_volume: Option<f64>,
+    /// TVL over the last days, oldest first
+    pub tvl_history: Vec<f64>,
+    /// Pool price over time, oldest first, `price_interval_minutes` apart
+    pub prices: Vec<f64>,
+    pub price_interval_minutes: u32,
+    /// Share of liquidity within ±2% of the current price
+    pub concentration_2_percent: Option<f64>,
+    pub tokens: Vec<TokenMaturity>,
+    /// Largest recent spread against other venues, in percent
+    pub divergence_percent: Option<f64>,
+    /// Explanation of the divergence reading, or why it is missing
+    pub divergence_note: Option<String>,
+}
+
+pub fn score_pool_risk(inputs: &RiskInputs) -> PoolRiskScore {
+    let mut factors = vec![
+        tvl_size_factor(inputs.tvl),
+        tvl_stability_factor(&inputs.tvl_history),
+        turnover_factor(inputs.tvl, inputs.daily_volume),
+        volatility_factor(&inputs.prices, inputs.price_interval_minutes),
+        concentration_factor(inputs.concentration_2_percent),
+        token_maturity_factor(&inputs.tokens),
+        divergence_factor(inputs.divergence_percent, inputs.divergence_note.as_deref()),
+    ];
+
+    let total_weight: f64 = factors.iter().map(|f| f.weight).sum();
+    let covered_weight: f64 = factors
+        .iter()
+        .filter(|f| f.risk.is_some())
+        .map(|f| f.weight)
+        .sum();
+    for factor in &mut factors {
+        if let Some(risk) = factor.risk
+            && covered_weight > 0.0
+        {
+            factor.contribution = factor.weight * risk / covered_weight * 100.0;
+        }
+    }
+
+    let score = factors.iter().map(|f| f.contribution).sum::<f64>();
+    let level = if covered_weight == 0.0 {
+        "UNKNOWN"
+    } else if score < LOW_RISK_BELOW {
+        "LOW"
+    } else if score < MEDIUM_RISK_BELOW {
+        "MEDIUM"
+    } else {
+        "HIGH"
+    };
+
+    PoolRiskScore {
+        score,
+        level: level.to_string(),
+        coverage: covered_weight / total_weight,
+        factors,
+    }
+}
+
+fn factor(
+    name: &str,
+    weight: f64,
+    value: Option<f64>,
+    risk: Option<f64>,
+    explanation: String,
+) -> RiskFactor {
+    RiskFactor {
+        name: name.to_string(),
+        weight,
+        value,
+        risk: risk.map(|r| r.clamp(0.0, 1.0)),
+        contribution: 0.0,
+        explanation,
+    }
+}
+
+/// Small pools move more per trade and are easier to drain
+fn tvl_size_factor(tvl: Option<f64>) -> RiskFactor {
+    let Some(tvl) = tvl.filter(|tvl| *tvl > 0.0) else {
+        return factor(
+            "tvl_size",
+            0.2,
+            None,
+            None,
+            "TVL is not reported".to_string(),
+        );
+    };
+
+    let risk = 1.0 - (tvl / RISKY_TVL).log10() / (SAFE_TVL / RISKY_TVL).log10();
+    factor(
+        "tvl_size",
+        0.2,
+        Some(tvl),
+        Some(risk),
+        format!(
+            "TVL of ${:.0}; risk falls from ${:.0} to ${:.0}",
+            tvl, RISKY_TVL, SAFE_TVL
+        ),
+    )
+}
+
+/// Liquidity that comes and goes leaves LPs exposed to sudden depth loss
+fn tvl_stability_factor(tvl_history: &[f64]) -> RiskFactor {
+    let history: Vec<f64> = tvl_history.iter().copied().filter(|t| *t > 0.0).collect();
+    if history.len() < 3 {
+        return factor(
+            "tvl_stability",
+            0.15,
+            None,
+            None,
+            "Fewer than 3 TVL snapshots".to_string(),
+        );
+    }
+
+    let variation = std_dev(&history) / mean(&history);
+    factor(
+        "tvl_stability",
+        0.15,
+        Some(variation),
+        Some(variation / MAX_TVL_VARIATION),
+        format!(
+            "TVL varied by {:.1}% (coefficient of variation) over {} snapshots",
+            variation * 100.0,
+            history.len()
+        ),
+    )
+}
+
+/// Idle pools earn little and may be abandoned; extreme turnover hints at wash trading
+fn turnover_factor(tvl: Option<f64>, daily_volume: Option<f64>) -> RiskFactor {
+    let (Some(tvl), Some(volume)) = (tvl.filter(|t| *t > 0.0), daily_volume) else {
+        return factor(
+            "volume_tvl_ratio",
+            0.1,
+            None,
+            None,
+            "TVL or volume is not reported".to_string(),
+        );
+    };
+
+    let turnover = volume / tvl;
+    let (risk, reading) = if turnover < MIN_HEALTHY_TURNOVER {
+        (
+            1.0 - turnover / MIN_HEALTHY_TURNOVER,
+            "little trading for its size",
+        )
+    } else if turnover > MAX_HEALTHY_TURNOVER {
+        (
+            (turnover - MAX_HEALTHY_TURNOVER) / (MAX_SUSPICIOUS_TURNOVER - MAX_HEALTHY_TURNOVER),
+            "unusually heavy trading, possibly washed",
+        )
+    } else {
+        (0.0, "healthy turnover")
+    };
+    factor(
+        "volume_tvl_ratio",
+        0.1,
+        Some(turnover),
+        Some(risk),
+        format!("Daily volume is {:.2}x TVL: {}", turnover, reading),
+    )
+}
+
+/// Volatile pairs cause impermanent loss and push positions out of range
+fn volatility_factor(prices: &[f64], interval_minutes: u32) -> RiskFactor {
+    if prices.len() < 3 || interval_minutes == 0 {
+        return factor(
+            "price_volatility",
+            0.2,
+            None,
+            None,
+            "Not enough price history".to_string(),
+        );
+    }
+
+    let volatility = realized_volatility(prices, interval_minutes);
+    factor(
+        "price_volatility",
+        0.2,
+        Some(volatility),
+        Some(volatility / MAX_VOLATILITY),
+        format!(
+            "Annualised volatility of {:.1}% over {} prices",
+            volatility * 100.0,
+            prices.len()
+        ),
+    )
+}
+
+/// Thin liquidity around the price means large impact and fast moves through ranges
+fn concentration_factor(concentration: Option<f64>) -> RiskFactor {
+    let Some(concentration) = concentration else {
+        return factor(
+            "liquidity_concentration",
+            0.15,
+            None,
+            None,
+            "Tick liquidity not loaded; see the pool detail".to_string(),
+        );
+    };
+
+    factor(
+        "liquidity_concentration",
+        0.15,
+        Some(concentration),
+        Some(1.0 - concentration / DEEP_CONCENTRATION),
+        format!(
+            "{:.1}% of liquidity sits within ±2% of the price",
+            concentration * 100.0
+        ),
+    )
+}
+
+/// New or thinly listed tokens are likelier to be illiquid, manipulated or abandoned
+fn token_maturity_factor(tokens: &[TokenMaturity]) -> RiskFactor {
+    let riskiest = tokens
+        .iter()
+        .map(|token| {
+            let risk = match token.age_days {
+                Some(age) => {
+                    (token.listing.risk() + (1.0 - age / MATURE_TOKEN_DAYS).max(0.0)) / 2.0
+                }
+                None => token.listing.risk(),
+            };
+            (token, risk)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    let Some((token, risk)) = riskiest else {
+        return factor(
+            "token_maturity",
+            0.1,
+            None,
+            None,
+            "No token data".to_string(),
+        );
+    };
+
+    let age = match token.age_days {
+        Some(age) => format!("first seen {:.0} days ago", age),
+        None => "age unknown".to_string(),
+    };
+    factor(
+        "token_maturity",
+        0.1,
+        token.age_days,
+        Some(risk),
+        format!("{} {} ({})", token.symbol, token.listing.describe(), age),
+    )
+}
+
+/// A pool that prices the pair away from other venues is being arbitraged or manipulated
+fn divergence_factor(divergence_percent: Option<f64>, note: Option<&str>) -> RiskFactor {
+    let Some(divergence) = divergence_percent else {
+        return factor(
+            "price_divergence",
+            0.1,
+            None,
+            None,
+            note.unwrap_or("Pair is not monitored").to_string(),
+        );
+    };
+
+    factor(
+        "price_divergence",
+        0.1,
+        Some(divergence),
+        Some(divergence / MAX_DIVERGENCE_PERCENT),
+        note.map_or_else(
+            || format!("Spread of {:.2}% against other venues", divergence),
+            str::to_string,
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn risk_of(score: &PoolRiskScore, name: &str) -> Option<f64> {
+        score
+            .factors
+            .iter()
+            .find(|factor| factor.name == name)
+            .unwrap()
+            .risk
+    }
+
+    fn token(listing: TokenListing, age_days: Option<f64>) -> TokenMaturity {
+        TokenMaturity {
+            symbol: "SEI".to_string(),
+            listing,
+            age_days,
+        }
+    }
+
+    #[test]
+    fn scores_nothing_without_inputs() {
+        let score = score_pool_risk(&RiskInputs::default());
+
+        assert_eq!(score.level, "UNKNOWN");
+        assert_eq!(score.score, 0.0);
+        assert_eq!(score.coverage, 0.0);
+        assert_eq!(score.factors.len(), 7);
+        assert!(score.factors.iter().all(|factor| factor.risk.is_none()));
+    }
+
+    #[test]
+    fn skips_zero_tvl_and_a_single_price() {
+        let score = score_pool_risk(&RiskInputs {
+            tvl: Some(0.0),
+            daily_volume: Some(1000.0),
+            tvl_history: vec![0.0, 0.0, 5.0],
+            prices: vec![1.0],
+            price_interval_minutes: 60,
+            ..RiskInputs::default()
+        });
+
+        assert_eq!(score.level, "UNKNOWN");
+        assert_eq!(risk_of(&score, "tvl_size"), None);
+        assert_eq!(risk_of(&score, "tvl_stability"), None);
+        assert_eq!(risk_of(&score, "volume_tvl_ratio"), None);
+        assert_eq!(risk_of(&score, "price_volatility"), None);
+    }
+
+    #[test]
+    fn rescales_the_score_over_covered_factors() {
+        // Only the size factor has data, so it alone sets the score
+        let score = score_pool_risk(&RiskInputs {
+            tvl: Some(RISKY_TVL),
+            ..RiskInputs::default()
+        });
+
+        assert_eq!(risk_of(&score, "tvl_size"), Some(1.0));
+        assert!((score.score - 100.0).abs() < 1e-9);
+        assert!((score.coverage - 0.2).abs() < 1e-9);
+        assert_eq!(score.level, "HIGH");
+    }
+
+    #[test]
+    fn rates_deep_established_pools_low() {
+        let score = score_pool_risk(&RiskInputs {
+            tvl: Some(SAFE_TVL * 2.0),
+            daily_volume: Some(SAFE_TVL),
+            tvl_history: vec![SAFE_TVL * 2.0; 5],
+            prices: vec![1.0; 10],
+            price_interval_minutes: 60,
+            concentration_2_percent: Some(0.8),
+            tokens: vec![token(TokenListing::Exchange, Some(365.0))],
+            divergence_percent: Some(0.0),
+            divergence_note: None,
+        });
+
+        assert!((score.coverage - 1.0).abs() < 1e-9);
+        assert!(score.score.abs() < 1e-9);
+        assert_eq!(score.level, "LOW");
+        assert!(
+            score
+                .factors
+                .iter()
+                .all(|factor| factor.contribution == 0.0)
+        );
+    }
+
+    #[test]
+    fn rates_thin_new_pools_high() {
+        let score = score_pool_risk(&RiskInputs {
+            tvl: Some(1000.0),
+            daily_volume: Some(0.0),
+            tvl_history: vec![100.0, 1000.0, 5000.0],
+            prices: vec![1.0, 2.0, 0.5, 3.0],
+            price_interval_minutes: 60,
+            concentration_2_percent: Some(0.0),
+            tokens: vec![
+                token(TokenListing::Exchange, Some(365.0)),
+                token(TokenListing::SingleDex, Some(0.0)),
+            ],
+            divergence_percent: Some(10.0),
+            divergence_note: None,
+        });
+
+        assert_eq!(score.level, "HIGH");
+        // Risks are clamped at 1, and only the token factor stays below it
+        assert!((score.score - 99.0).abs() < 1e-9);
+        assert_eq!(risk_of(&score, "liquidity_concentration"), Some(1.0));
+        assert_eq!(risk_of(&score, "token_maturity"), Some(0.9));
+        let contributions: f64 = score.factors.iter().map(|f| f.contribution).sum();
+        assert!((contributions - score.score).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flags_both_idle_and_washed_turnover() {
+        let turnover = |volume: f64| {
+            risk_of(
+                &score_pool_risk(&RiskInputs {
+                    tvl: Some(1_000_000.0),
+                    daily_volume: Some(volume),
+                    ..RiskInputs::default()
+                }),
+                "volume_tvl_ratio",
+            )
+            .unwrap()
+        };
+
+        assert_eq!(turnover(0.0), 1.0);
+        assert_eq!(turnover(500_000.0), 0.0);
+        assert!((turnover(3_500_000.0) - 0.5).abs() < 1e-9);
+        assert_eq!(turnover(10_000_000.0), 1.0);
+    }
+}
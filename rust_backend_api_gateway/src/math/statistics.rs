@@ -0,0 +1,299 @@
+use crate::application::dtos::price_history::PricePoint;
+
+const MINUTES_PER_DAY: f64 = 1440.0;
+const MINUTES_PER_YEAR: f64 = 365.0 * MINUTES_PER_DAY;
+
+/// Log returns between consecutive prices
+pub fn log_returns(prices: &[f64]) -> Vec<f64> {
+    prices
+        .windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect()
+}
+
+pub fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Middle value, or the mean of the two middle values; `None` when empty
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    Some(if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    })
+}
+
+/// Sample standard deviation (n - 1 denominator)
+pub fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(values);
+    let variance =
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    variance.sqrt()
+}
+
+/// Number of candles of `interval_minutes` in a year
+pub fn periods_per_year(interval_minutes: u32) -> f64 {
+    if interval_minutes == 0 {
+        return 0.0;
+    }
+    MINUTES_PER_YEAR / interval_minutes as f64
+}
+
+/// Scale a per-candle volatility to a horizon of `horizon_minutes`
+pub fn scale_volatility(per_candle: f64, interval_minutes: u32, horizon_minutes: f64) -> f64 {
+    if interval_minutes == 0 {
+        return 0.0;
+    }
+    per_candle * (horizon_minutes / interval_minutes as f64).sqrt()
+}
+
+/// Close-to-close realised volatility, annualised for the candle interval
+pub fn realized_volatility(closes: &[f64], interval_minutes: u32) -> f64 {
+    std_dev(&log_returns(closes)) * periods_per_year(interval_minutes).sqrt()
+}
+
+/// Close-to-close realised volatility scaled to one day
+pub fn daily_volatility(closes: &[f64], interval_minutes: u32) -> f64 {
+    scale_volatility(
+        std_dev(&log_returns(closes)),
+        interval_minutes,
+        MINUTES_PER_DAY,
+    )
+}
+
+/// Parkinson high/low range estimator, annualised for the candle interval
+pub fn parkinson_volatility(points: &[PricePoint], interval_minutes: u32) -> f64 {
+    let squared_ranges: Vec<f64> = points
+        .iter()
+        .filter(|p| p.high > 0.0 && p.low > 0.0)
+        .map(|p| (p.high / p.low).ln().powi(2))
+        .collect();
+
+    if squared_ranges.is_empty() {
+        return 0.0;
+    }
+
+    let variance = squared_ranges.iter().sum::<f64>()
+        / (4.0 * std::f64::consts::LN_2 * squared_ranges.len() as f64);
+    (variance * periods_per_year(interval_minutes)).sqrt()
+}
+
+/// Garman-Klass OHLC estimator, annualised for the candle interval
+pub fn garman_klass_volatility(points: &[PricePoint], interval_minutes: u32) -> f64 {
+    let terms: Vec<f64> = points
+        .iter()
+        .filter(|p| p.high > 0.0 && p.low > 0.0 && p.open > 0.0 && p.close > 0.0)
+        .map(|p| {
+            let high_low = (p.high / p.low).ln();
+            let close_open = (p.close / p.open).ln();
+            0.5 * high_low.powi(2) - (2.0 * std::f64::consts::LN_2 - 1.0) * close_open.powi(2)
+        })
+        .collect();
+
+    if terms.is_empty() {
+        return 0.0;
+    }
+
+    (mean(&terms).max(0.0) * periods_per_year(interval_minutes)).sqrt()
+}
+
+/// True range of each candle against the previous close
+pub fn true_ranges(points: &[PricePoint]) -> Vec<f64> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let high_low = p.high - p.low;
+            match i.checked_sub(1).map(|prev| points[prev].close) {
+                Some(prev_close) => high_low
+                    .max((p.high - prev_close).abs())
+                    .max((p.low - prev_close).abs()),
+                None => high_low,
+            }
+        })
+        .collect()
+}
+
+/// Wilder's average true range over `period` candles, or `None` with too little data
+pub fn average_true_range(points: &[PricePoint], period: usize) -> Option<f64> {
+    let ranges = true_ranges(points);
+    if period == 0 || ranges.len() < period {
+        return None;
+    }
+
+    let seed = mean(&ranges[..period]);
+    Some(ranges[period..].iter().fold(seed, |atr, tr| {
+        (atr * (period - 1) as f64 + tr) / period as f64
+    }))
+}
+
+/// Annualised close-to-close volatility over each trailing window of `window` returns.
+///
+/// Entry `i` covers the returns ending at close `i + window`.
+pub fn rolling_volatility(closes: &[f64], window: usize, interval_minutes: u32) -> Vec<f64> {
+    let returns = log_returns(closes);
+    if window < 2 || returns.len() < window {
+        return Vec::new();
+    }
+
+    let annualisation = periods_per_year(interval_minutes).sqrt();
+    returns
+        .windows(window)
+        .map(|w| std_dev(w) * annualisation)
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinearRegression {
+    pub slope: f64,
+    pub r_squared: f64,
+}
+
+/// Ordinary least squares fit of `values` against their index
+pub fn linear_regression(values: &[f64]) -> Option<LinearRegression> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let index: Vec<f64> = (0..values.len()).map(|i| i as f64).collect();
+    linear_regression_xy(&index, values)
+}
+
+/// Ordinary least squares fit of `y` against `x`
+pub fn linear_regression_xy(x: &[f64], y: &[f64]) -> Option<LinearRegression> {
+    if x.len() != y.len() || x.len() < 2 {
+        return None;
+    }
+
+    let x_mean = mean(x);
+    let y_mean = mean(y);
+
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (xi, yi) in x.iter().zip(y) {
+        let dx = xi - x_mean;
+        let dy = yi - y_mean;
+        sxy += dx * dy;
+        sxx += dx * dx;
+        syy += dy * dy;
+    }
+
+    if sxx == 0.0 {
+        return None;
+    }
+
+    let slope = sxy / sxx;
+    let r_squared = if syy > 0.0 {
+        (sxy * sxy) / (sxx * syy)
+    } else {
+        0.0
+    };
+
+    Some(LinearRegression { slope, r_squared })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> PricePoint {
+        PricePoint {
+            tick: 0,
+            open,
+            high,
+            low,
+            close,
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn takes_log_returns_between_positive_prices() {
+        let returns = log_returns(&[100.0, 110.0, 0.0, 121.0]);
+        assert_eq!(returns.len(), 1);
+        assert!((returns[0] - 1.1f64.ln()).abs() < 1e-15);
+        assert!(log_returns(&[]).is_empty());
+        assert!(log_returns(&[100.0]).is_empty());
+    }
+
+    #[test]
+    fn uses_the_sample_standard_deviation() {
+        // Mean 5, squared deviations sum to 32, n - 1 = 7
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert!((std_dev(&values) - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert_eq!(std_dev(&[1.0]), 0.0);
+        assert_eq!(mean(&[]), 0.0);
+        assert_eq!(median(&[3.0, 1.0, 2.0, 10.0]), Some(2.5));
+        assert_eq!(median(&[]), None);
+    }
+
+    #[test]
+    fn annualises_by_the_square_root_of_periods() {
+        assert_eq!(periods_per_year(1440), 365.0);
+        assert_eq!(periods_per_year(0), 0.0);
+        assert!((scale_volatility(0.01, 60, 1440.0) - 0.01 * 24f64.sqrt()).abs() < 1e-15);
+
+        let closes = [100.0, 101.0, 99.5, 102.0, 101.0];
+        let per_candle = std_dev(&log_returns(&closes));
+        assert!((realized_volatility(&closes, 1440) - per_candle * 365f64.sqrt()).abs() < 1e-12);
+        assert!((daily_volatility(&closes, 60) - per_candle * 24f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn estimates_range_based_volatility() {
+        // A candle spanning e^0.1 has Parkinson variance 0.01 / (4 ln 2)
+        let candles = [candle(1.0, 0.1f64.exp(), 1.0, 1.0)];
+        let expected = (0.01 / (4.0 * std::f64::consts::LN_2) * 365.0).sqrt();
+        assert!((parkinson_volatility(&candles, 1440) - expected).abs() < 1e-12);
+        assert_eq!(parkinson_volatility(&[], 1440), 0.0);
+        assert_eq!(garman_klass_volatility(&[], 1440), 0.0);
+        assert!(garman_klass_volatility(&candles, 1440) > 0.0);
+    }
+
+    #[test]
+    fn smooths_true_ranges_with_wilder() {
+        let candles = [
+            candle(10.0, 11.0, 9.0, 10.0),
+            candle(10.0, 14.0, 12.0, 13.0),
+            candle(13.0, 13.0, 12.0, 12.5),
+        ];
+        // Second candle gaps up, so its true range runs from the previous close
+        assert_eq!(true_ranges(&candles), vec![2.0, 4.0, 1.0]);
+        assert_eq!(average_true_range(&candles, 2), Some((3.0 + 1.0) / 2.0));
+        assert_eq!(average_true_range(&candles, 4), None);
+        assert_eq!(average_true_range(&candles[..1], 1), Some(2.0));
+    }
+
+    #[test]
+    fn rolls_volatility_over_trailing_windows() {
+        let closes = [100.0, 101.0, 99.0, 102.0, 100.0];
+        let rolling = rolling_volatility(&closes, 3, 1440);
+        assert_eq!(rolling.len(), 2);
+        let last_window = &log_returns(&closes)[1..];
+        assert!((rolling[1] - std_dev(last_window) * 365f64.sqrt()).abs() < 1e-12);
+        assert!(rolling_volatility(&closes, 1, 1440).is_empty());
+        assert!(rolling_volatility(&closes[..2], 3, 1440).is_empty());
+    }
+
+    #[test]
+    fn fits_a_least_squares_line() {
+        let fit = linear_regression(&[1.0, 3.0, 5.0, 7.0]).unwrap();
+        assert!((fit.slope - 2.0).abs() < 1e-12);
+        assert!((fit.r_squared - 1.0).abs() < 1e-12);
+        assert!(linear_regression(&[1.0]).is_none());
+        assert!(linear_regression_xy(&[1.0, 1.0], &[2.0, 3.0]).is_none());
+    }
+}
@@ -1,34 +1,101 @@
 use crate::AppState;
 use crate::application::dtos::ask::PromptRequest;
 use crate::application::dtos::auth::{AuthRequest, AuthResponse};
+use crate::application::dtos::backtest::{BacktestRequest, StrategySimulationRequest};
+use crate::application::dtos::chain::ChainQuery;
 use crate::application::dtos::chat::AddChatRequest;
+use crate::application::dtos::correlation::CorrelationQuery;
+use crate::application::dtos::divergence::{DivergenceHistoryResponse, DivergenceQuery};
+use crate::application::dtos::fee_apr::{FeeAprQuery, FeeAprRequest};
+use crate::application::dtos::indicators::IndicatorQuery;
 use crate::application::dtos::liquidity_data::LiquidityDataQuery;
+use crate::application::dtos::pair_pools::PairPoolsQuery;
+use crate::application::dtos::pool_history::{AprSustainabilityRequest, PoolHistoryQuery};
+use crate::application::dtos::pool_list::PoolListQuery;
 use crate::application::dtos::position::AddPositionRequest;
-use crate::application::dtos::price_history::PriceHistoryRequest;
-use crate::application::service::position_service;
-use crate::config::mcp_client_base_url;
-use actix_web::{HttpResponse, Responder, get, post, web};
+use crate::application::dtos::price_history::{PriceHistoryRequest, RecommendationOptions};
+use crate::application::dtos::range_optimizer::OptimalRangeRequest;
+use crate::application::dtos::stream::{StreamQuery, parse_topics};
+use crate::application::service::divergence_service::{self, DivergenceFilter};
+use crate::application::service::price_oracle::PriceOracle;
+use crate::application::service::stream_service::Subscriptions;
+use crate::config::{ChainConfig, Chains, divergence_threshold_percent, mcp_client_base_url};
+use crate::domain::services::data::LegacyUnifiedPool;
+use crate::domain::services::fee_tier::FeeTier;
+use crate::infrastructure::data::ProviderUnavailable;
+use crate::math::{IndicatorSpec, RETURN_MODELS, RiskProfile};
+use crate::presentation::stream::{run_websocket, sse_response};
+use actix_web::http::header::UPGRADE;
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
 use serde::Deserialize;
 use tracing::{error, info};
 
 use crate::application::use_cases::{
-    forward_prompt_to_backend, get_graph_data, get_kline_data, get_pool_list,
-    get_price_history_analysis, get_token_symbol, handle_auth,
+    DEFAULT_CORRELATION_WINDOW, DEFAULT_PAIR_TRADE_SIZE, MAX_HORIZON_DAYS, PAIR_SORT_FIELDS,
+    POOL_SORT_FIELDS, SNAPSHOT_METRICS, analyze_pair_correlation, analyze_pair_legs,
+    assess_apr_sustainability, cross_check_pool, estimate_fee_apr, forward_prompt_to_backend,
+    get_graph_data, get_indicators, get_kline_data, get_optimal_range, get_pool_detail,
+    get_pool_history, get_pool_list, get_price_history_analysis, get_token_symbol, handle_auth,
+    parse_pool_cursor, rank_pair_pools, run_backtest, simulate_strategies, value_positions,
 };
 
 // --- Authentication Handler ---
 #[post("/verify")]
-pub async fn verify_signature(data: web::Json<AuthRequest>) -> impl Responder {
-    match handle_auth(data.into_inner()) {
+pub async fn verify_signature(
+    state: web::Data<AppState>,
+    data: web::Json<AuthRequest>,
+) -> impl Responder {
+    match handle_auth(&state.chains, data.into_inner()) {
         Ok(token) => HttpResponse::Ok().json(AuthResponse { token }),
         Err(err) => err,
     }
 }
 
+// --- Chain Resolution ---
+/// Chain named by the `chain` query parameter, or the default chain when it is omitted
+#[allow(clippy::result_large_err)]
+fn resolve_chain(chains: &Chains, query: &ChainQuery) -> Result<ChainConfig, HttpResponse> {
+    let Some(chain) = query.chain.as_deref() else {
+        return Ok(chains.default_chain().clone());
+    };
+
+    chains.find(chain).cloned().ok_or_else(|| {
+        let names: Vec<&str> = chains.iter().map(|chain| chain.name.as_str()).collect();
+        HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("chain must be one of {}", names.join(", "))
+        }))
+    })
+}
+
+fn chain_ids(chains: &Chains) -> Vec<String> {
+    chains.iter().map(|chain| chain.id.to_string()).collect()
+}
+
+/// 503 when the chain lacks a provider the use case needs, 500 for any other failure
+fn use_case_error(message: &str, e: &anyhow::Error) -> HttpResponse {
+    let mut response = if e.downcast_ref::<ProviderUnavailable>().is_some() {
+        HttpResponse::ServiceUnavailable()
+    } else {
+        HttpResponse::InternalServerError()
+    };
+    response.json(serde_json::json!({
+        "error": message,
+        "details": e.to_string()
+    }))
+}
+
 // --- Graph Data Handler ---
 #[get("/liquidity-chart")]
-pub async fn get_graph_data_handler(query: web::Query<LiquidityDataQuery>) -> impl Responder {
-    match get_graph_data(&query.pool_address).await {
+pub async fn get_graph_data_handler(
+    data: web::Data<AppState>,
+    query: web::Query<LiquidityDataQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    match get_graph_data(&chain, &query.pool_address).await {
         Ok(data) => HttpResponse::Ok().json(data),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
@@ -37,14 +104,20 @@ pub async fn get_graph_data_handler(query: web::Query<LiquidityDataQuery>) -> im
 // --- Token Pair Price History Handler ---
 #[get("/price-chart/{token0}/{token1}")]
 pub async fn get_token_pair_price_history(
+    data: web::Data<AppState>,
     path: web::Path<(String, String)>,
     query: web::Query<PriceHistoryQuery>,
+    chain: web::Query<ChainQuery>,
 ) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
     let (token0, token1) = path.into_inner();
     let interval = query.interval.unwrap_or(15);
     let limit = query.limit.unwrap_or(200);
 
-    match get_kline_data(&token0, &token1, interval, limit).await {
+    match get_kline_data(&chain, &token0, &token1, interval, limit).await {
         Ok(kline_data) => HttpResponse::Ok().json(kline_data),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
@@ -56,6 +129,62 @@ pub struct PriceHistoryQuery {
     pub limit: Option<u32>,
 }
 
+// --- Stream Handler ---
+/// WebSocket when the request asks for an upgrade, Server-Sent Events otherwise
+#[get("/stream")]
+pub async fn stream_handler(
+    req: HttpRequest,
+    body: web::Payload,
+    data: web::Data<AppState>,
+    query: web::Query<StreamQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let topics = match parse_topics(query.topics.as_deref().unwrap_or_default()) {
+        Ok(topics) => topics,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    let websocket = req
+        .headers()
+        .get(UPGRADE)
+        .and_then(|upgrade| upgrade.to_str().ok())
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+    if !websocket && topics.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "topics is required for Server-Sent Events"
+        }));
+    }
+
+    let mut subscriptions = Subscriptions::new(data.stream_hub.clone(), chain);
+    let initial = match subscriptions.subscribe_all(topics) {
+        Ok(initial) => initial,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    if !websocket {
+        return sse_response(subscriptions, initial);
+    }
+    match actix_ws::handle(&req, body) {
+        Ok((response, session, messages)) => {
+            actix_web::rt::spawn(run_websocket(session, messages, subscriptions, initial));
+            response
+        }
+        Err(e) => HttpResponse::from_error(e),
+    }
+}
+
 // --- Prompt Handler ---
 #[post("/ask")]
 pub async fn prompt_handler(data: web::Json<PromptRequest>) -> impl Responder {
@@ -82,9 +211,89 @@ pub async fn prompt_handler(data: web::Json<PromptRequest>) -> impl Responder {
 
 // --- Pool List Handler ---
 #[get("/pools")]
-pub async fn get_pools_handler() -> impl Responder {
-    match get_pool_list().await {
-        Ok(data) => HttpResponse::Ok().json(data),
+pub async fn get_pools_handler(
+    data: web::Data<AppState>,
+    query: web::Query<PoolListQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let query = query.into_inner();
+
+    if query
+        .sort_by
+        .as_deref()
+        .is_some_and(|field| !POOL_SORT_FIELDS.contains(&field))
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("sort_by must be one of {}", POOL_SORT_FIELDS.join(", "))
+        }));
+    }
+
+    if query
+        .order
+        .as_deref()
+        .is_some_and(|order| order != "asc" && order != "desc")
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "order must be asc or desc"
+        }));
+    }
+
+    let v2 = match query.schema.as_deref() {
+        None | Some("v1") => false,
+        Some("v2") => true,
+        Some(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "schema must be v1 or v2"
+            }));
+        }
+    };
+
+    if query.limit == Some(0)
+        || query
+            .cursor
+            .as_deref()
+            .is_some_and(|c| parse_pool_cursor(c).is_err())
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "limit must be positive and cursor must come from a previous page"
+        }));
+    }
+
+    match get_pool_list(&data.db_connection, &chain, &query).await {
+        Ok(page) => {
+            // Every provider failed, so there is nothing degraded to show; a chain with no pool
+            // providers configured just has an empty list
+            let mut response =
+                if !page.sources.is_empty() && page.sources.iter().all(|s| s.status != "ok") {
+                    HttpResponse::BadGateway()
+                } else {
+                    HttpResponse::Ok()
+                };
+            // Paging metadata travels in headers so the v1 body stays a plain array
+            response.insert_header(("X-Total-Count", page.total.to_string()));
+            if let Some(cursor) = &page.next_cursor {
+                response.insert_header(("X-Next-Cursor", cursor.as_str()));
+            }
+            if v2 {
+                return response.json(page);
+            }
+            let failed: Vec<&str> = page
+                .sources
+                .iter()
+                .filter(|s| s.status != "ok")
+                .map(|s| s.provider.as_str())
+                .collect();
+            if !failed.is_empty() {
+                response.insert_header(("X-Failed-Sources", failed.join(",")));
+            }
+            response
+                .insert_header(("Deprecation", "true"))
+                .json(page.pools.iter().map(LegacyUnifiedPool).collect::<Vec<_>>())
+        }
         Err(e) => {
             // Add this log to see the real error in your terminal
             eprintln!("Error fetching pool list: {:?}", e);
@@ -94,10 +303,311 @@ pub async fn get_pools_handler() -> impl Responder {
     }
 }
 
+// --- Price Divergence Handler ---
+const DEFAULT_DIVERGENCE_LIMIT: u64 = 100;
+const MAX_DIVERGENCE_LIMIT: u64 = 1000;
+
+#[get("/divergences")]
+pub async fn get_divergences_handler(
+    data: web::Data<AppState>,
+    query: web::Query<DivergenceQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let query = query.into_inner();
+
+    let pair = match query
+        .pair
+        .as_deref()
+        .map(|pair| pair.split_once(['/', '-']))
+    {
+        None => None,
+        Some(Some((a, b))) if !a.is_empty() && !b.is_empty() => {
+            Some((a.to_uppercase(), b.to_uppercase()))
+        }
+        Some(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "pair must look like SEI/USDC"
+            }));
+        }
+    };
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from > to
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "from must be before to"
+        }));
+    }
+
+    let filter = DivergenceFilter {
+        chain_id: chain.id,
+        pair,
+        from: query.from,
+        to: query.to,
+        min_spread: query.min_spread,
+        limit: query
+            .limit
+            .unwrap_or(DEFAULT_DIVERGENCE_LIMIT)
+            .clamp(1, MAX_DIVERGENCE_LIMIT),
+    };
+
+    match divergence_service::get_divergences(&data.db_connection, &filter).await {
+        Ok(divergences) => HttpResponse::Ok().json(DivergenceHistoryResponse {
+            threshold_percent: divergence_threshold_percent(),
+            divergences,
+        }),
+        Err(e) => {
+            error!("❌ Failed to read divergences: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to read divergences",
+                "details": e.to_string()
+            }))
+        }
+    }
+}
+
+// --- Pair Pools Handler ---
+#[get("/pairs/{token_a}/{token_b}")]
+pub async fn get_pair_pools_handler(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<PairPoolsQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let (token_a, token_b) = path.into_inner();
+    if token_a.eq_ignore_ascii_case(&token_b) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "token_a and token_b must differ"
+        }));
+    }
+
+    let trade_size = query.trade_size.unwrap_or(DEFAULT_PAIR_TRADE_SIZE);
+    if !trade_size.is_finite() || trade_size <= 0.0 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "trade_size must be a positive number"
+        }));
+    }
+
+    let sort_by = query.sort_by.as_deref().unwrap_or("score");
+    if !PAIR_SORT_FIELDS.contains(&sort_by) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("sort_by must be one of {}", PAIR_SORT_FIELDS.join(", "))
+        }));
+    }
+
+    match rank_pair_pools(&chain, &token_a, &token_b, trade_size, sort_by).await {
+        Ok(data) => HttpResponse::Ok().json(data),
+        Err(e) => {
+            error!("❌ Failed to rank pools for {}/{}: {}", token_a, token_b, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to rank pools for pair",
+                "details": e.to_string()
+            }))
+        }
+    }
+}
+
+// --- Pool Detail Handler ---
+#[get("/pools/{address}")]
+pub async fn get_pool_detail_handler(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let address = path.into_inner();
+    match get_pool_detail(&data.db_connection, &chain, &address, &data.price_oracle).await {
+        Ok(detail) => HttpResponse::Ok().json(detail),
+        Err(e) => {
+            error!("❌ Failed to fetch pool detail for {}: {}", address, e);
+            use_case_error("Failed to fetch pool detail", &e)
+        }
+    }
+}
+
+// --- On-chain Pool Cross-check Handler ---
+#[get("/pools/{address}/onchain")]
+pub async fn get_pool_onchain_handler(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let address = path.into_inner();
+    match cross_check_pool(&chain, &address).await {
+        Ok(state) => HttpResponse::Ok().json(state),
+        Err(e) => {
+            error!("❌ Failed to read pool {} on-chain: {}", address, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to read pool on-chain",
+                "details": e.to_string()
+            }))
+        }
+    }
+}
+
+// --- Pool History Handler ---
+const DEFAULT_HISTORY_DAYS: i64 = 30;
+
+#[get("/pools/{address}/history")]
+pub async fn get_pool_history_handler(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<PoolHistoryQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let address = path.into_inner();
+    let metric = query.metric.as_deref().unwrap_or("tvl");
+    if !SNAPSHOT_METRICS.contains(&metric) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("metric must be one of {}", SNAPSHOT_METRICS.join(", "))
+        }));
+    }
+
+    let to = query.to.unwrap_or_else(chrono::Utc::now);
+    let from = query
+        .from
+        .unwrap_or(to - chrono::Duration::days(DEFAULT_HISTORY_DAYS));
+    if from > to {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "from must be before to"
+        }));
+    }
+
+    match get_pool_history(&data.db_connection, chain.id, &address, metric, from, to).await {
+        Ok(history) => HttpResponse::Ok().json(history),
+        Err(e) => {
+            error!(
+                "❌ Failed to fetch {} history for {}: {}",
+                metric, address, e
+            );
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch pool history",
+                "details": e.to_string()
+            }))
+        }
+    }
+}
+
+// --- Position Fee APR Handler ---
+#[get("/pools/{address}/fee-apr")]
+pub async fn get_pool_fee_apr_handler(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<FeeAprQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let query = query.into_inner();
+    fee_apr_response(
+        &data.price_oracle,
+        &chain,
+        FeeAprRequest {
+            pool_address: path.into_inner(),
+            price_lower: query.price_lower,
+            price_upper: query.price_upper,
+            capital: query.capital,
+            current_price: query.current_price,
+            token1_usd_price: query.token1_usd_price,
+        },
+    )
+    .await
+}
+
+async fn fee_apr_response(
+    price_oracle: &PriceOracle,
+    chain: &ChainConfig,
+    request: FeeAprRequest,
+) -> HttpResponse {
+    if request.pool_address.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "pool_address is required"
+        }));
+    }
+
+    if request.price_lower <= 0.0 || request.price_lower >= request.price_upper {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "price_lower must be positive and below price_upper"
+        }));
+    }
+
+    if request.capital.is_some_and(|c| c <= 0.0)
+        || request.current_price.is_some_and(|p| p <= 0.0)
+        || request.token1_usd_price.is_some_and(|p| p <= 0.0)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "capital, current_price and token1_usd_price must be positive"
+        }));
+    }
+
+    match estimate_fee_apr(chain, &request, price_oracle).await {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(e) => {
+            error!("❌ Failed to estimate fee APR: {}", e);
+            use_case_error("Failed to estimate fee APR", &e)
+        }
+    }
+}
+
+// --- Token Price Handler ---
+#[get("/tokens/{address}/price")]
+pub async fn get_token_price_handler(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let address = path.into_inner();
+    match data.price_oracle.token_price(&chain, &address).await {
+        Ok(Some(price)) => HttpResponse::Ok().json(price),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Token {} is not listed by any provider", address)
+        })),
+        Err(e) => {
+            error!("❌ Failed to price token {}: {}", address, e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch token price",
+                "details": e.to_string()
+            }))
+        }
+    }
+}
+
 #[get("/token/{address}")]
-pub async fn get_token_symbol_handler(path: web::Path<String>) -> impl Responder {
+pub async fn get_token_symbol_handler(
+    data: web::Data<AppState>,
+    path: web::Path<String>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
     let address = path.into_inner();
-    match get_token_symbol(&address).await {
+    match get_token_symbol(&chain, &address).await {
         Ok(symbol) => HttpResponse::Ok().json(symbol),
         Err(e) => {
             eprintln!("Error fetching token symbol: {:?}", e);
@@ -109,7 +619,15 @@ pub async fn get_token_symbol_handler(path: web::Path<String>) -> impl Responder
 
 // --- Price History Tool for AI Agent Handler ---
 #[get("/price-history")]
-pub async fn get_price_history_tool(query: web::Query<PriceHistoryRequest>) -> impl Responder {
+pub async fn get_price_history_tool(
+    data: web::Data<AppState>,
+    query: web::Query<PriceHistoryRequest>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
     info!(
         "📊 Price history request: {}/{} (interval: {}min, limit: {})",
         query.token0,
@@ -131,11 +649,26 @@ pub async fn get_price_history_tool(query: web::Query<PriceHistoryRequest>) -> i
         }));
     }
 
+    if let Err(e) =
+        validate_simulation_options(query.model.as_deref(), query.risk_profile.as_deref())
+    {
+        return e;
+    }
+
     match get_price_history_analysis(
+        &chain,
         &query.token0,
         &query.token1,
         query.interval.unwrap_or(1440),
         query.limit.unwrap_or(200),
+        &RecommendationOptions {
+            model: query.model.clone(),
+            paths: query.paths,
+            seed: query.seed,
+            risk_profile: query.risk_profile.clone(),
+            pool_apr: query.pool_apr,
+            pool_address: query.pool_address.clone(),
+        },
     )
     .await
     {
@@ -148,14 +681,331 @@ pub async fn get_price_history_tool(query: web::Query<PriceHistoryRequest>) -> i
         }
         Err(e) => {
             error!("❌ Failed to process price history: {}", e);
+            use_case_error("Failed to process price history", &e)
+        }
+    }
+}
+
+/// 400 for a Monte Carlo model or risk profile the simulators do not know
+#[allow(clippy::result_large_err)]
+fn validate_simulation_options(
+    model: Option<&str>,
+    risk_profile: Option<&str>,
+) -> Result<(), HttpResponse> {
+    if model.is_some_and(|model| !RETURN_MODELS.contains(&model)) {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("model must be one of {}", RETURN_MODELS.join(", "))
+        })));
+    }
+    if let Some(Err(e)) = risk_profile.map(str::parse::<RiskProfile>) {
+        return Err(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": e.to_string()
+        })));
+    }
+    Ok(())
+}
+
+// --- Technical Indicators Tool for AI Agent Handler ---
+#[get("/indicators/{token0}/{token1}")]
+pub async fn get_indicators_tool(
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<IndicatorQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let (token0, token1) = path.into_inner();
+    let interval = query.interval.unwrap_or(60);
+    let limit = query.limit.unwrap_or(200);
+
+    let specs: Result<Vec<IndicatorSpec>, _> = query
+        .set
+        .as_deref()
+        .unwrap_or("ema20,rsi14,bb20,macd")
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.parse::<IndicatorSpec>())
+        .collect();
+
+    let specs = match specs {
+        Ok(specs) if !specs.is_empty() => specs,
+        Ok(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "At least one indicator is required in 'set'"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
+    match get_indicators(&chain, &token0, &token1, interval, limit, &specs).await {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(e) => {
+            error!("❌ Failed to compute indicators: {}", e);
+            use_case_error("Failed to compute indicators", &e)
+        }
+    }
+}
+
+// --- Pair Correlation Tool for AI Agent Handler ---
+#[get("/correlation")]
+pub async fn get_correlation_tool(
+    data: web::Data<AppState>,
+    query: web::Query<CorrelationQuery>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let query = query.into_inner();
+    let interval = query.interval.unwrap_or(60);
+    let limit = query.limit.unwrap_or(500);
+    let window = query.window.unwrap_or(DEFAULT_CORRELATION_WINDOW);
+
+    if window < 2 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "window must be at least 2"
+        }));
+    }
+
+    let result = match (&query.pair_a, &query.pair_b, &query.token0, &query.token1) {
+        (Some(pair_a), Some(pair_b), _, _) => match (split_pair(pair_a), split_pair(pair_b)) {
+            (Some(a), Some(b)) => {
+                analyze_pair_correlation(&chain, a, b, interval, limit, window).await
+            }
+            _ => {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Pairs must be formatted as BASE/QUOTE, e.g. SEI/USDC"
+                }));
+            }
+        },
+        (None, None, Some(token0), Some(token1)) if !token0.is_empty() && !token1.is_empty() => {
+            analyze_pair_legs(&chain, token0, token1, interval, limit, window).await
+        }
+        _ => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Provide either pair_a and pair_b, or token0 and token1"
+            }));
+        }
+    };
+
+    match result {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("❌ Failed to analyze pair correlation: {}", e);
+            use_case_error("Failed to analyze pair correlation", &e)
+        }
+    }
+}
+
+fn split_pair(pair: &str) -> Option<(&str, &str)> {
+    let (base, quote) = pair.split_once('/')?;
+    (!base.is_empty() && !quote.is_empty()).then_some((base, quote))
+}
+
+// --- Optimal Range Tool for AI Agent Handler ---
+#[post("/optimal-range")]
+pub async fn get_optimal_range_tool(
+    state: web::Data<AppState>,
+    data: web::Json<OptimalRangeRequest>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&state.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let request = data.into_inner();
+
+    if request.token0.is_empty() || request.token1.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Both token0 and token1 are required"
+        }));
+    }
+
+    if FeeTier::from_percent(request.fee_tier).is_none() || request.pool_apr < 0.0 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "fee_tier must be at least 0.0001% and pool_apr cannot be negative"
+        }));
+    }
+
+    if request.capital.is_some_and(|c| c <= 0.0) || request.interval == Some(0) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "capital and interval must be positive"
+        }));
+    }
+
+    if request
+        .horizon_days
+        .is_some_and(|days| days == 0 || days > MAX_HORIZON_DAYS)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("horizon_days must be between 1 and {}", MAX_HORIZON_DAYS)
+        }));
+    }
+
+    // ERC-20 decimals are a uint8
+    if [request.token0_decimals, request.token1_decimals]
+        .into_iter()
+        .flatten()
+        .any(|decimals| decimals > u8::MAX as u32)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("token decimals must be at most {}", u8::MAX)
+        }));
+    }
+
+    if let Err(e) =
+        validate_simulation_options(request.model.as_deref(), request.risk_profile.as_deref())
+    {
+        return e;
+    }
+
+    match get_optimal_range(&chain, &request).await {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(e) => {
+            error!("❌ Failed to optimize range: {}", e);
+            use_case_error("Failed to optimize range", &e)
+        }
+    }
+}
+
+// --- Fee APR Tool for AI Agent Handler ---
+#[post("/fee-apr")]
+pub async fn estimate_fee_apr_tool(
+    state: web::Data<AppState>,
+    data: web::Json<FeeAprRequest>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&state.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    fee_apr_response(&state.price_oracle, &chain, data.into_inner()).await
+}
+
+// --- APR Sustainability Tool for AI Agent Handler ---
+const MAX_SUSTAINABILITY_DAYS: u32 = 365;
+
+#[post("/apr-sustainability")]
+pub async fn apr_sustainability_tool(
+    data: web::Data<AppState>,
+    request: web::Json<AprSustainabilityRequest>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&data.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let request = request.into_inner();
+
+    if request.pool_address.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "pool_address is required"
+        }));
+    }
+    if request
+        .days
+        .is_some_and(|days| days == 0 || days > MAX_SUSTAINABILITY_DAYS)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("days must be between 1 and {}", MAX_SUSTAINABILITY_DAYS)
+        }));
+    }
+
+    match assess_apr_sustainability(&data.db_connection, chain.id, &request).await {
+        Ok(result) => {
+            info!(
+                "✅ APR of pool {} judged {}",
+                result.pool_address, result.verdict
+            );
+            HttpResponse::Ok().json(result)
+        }
+        Err(e) => {
+            error!(
+                "❌ Failed to assess APR sustainability for {}: {}",
+                request.pool_address, e
+            );
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to process price history",
+                "error": "Failed to assess APR sustainability",
                 "details": e.to_string()
             }))
         }
     }
 }
 
+// --- Range Backtest Tool for AI Agent Handler ---
+#[post("/backtest")]
+pub async fn run_backtest_tool(
+    state: web::Data<AppState>,
+    data: web::Json<BacktestRequest>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&state.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let request = data.into_inner();
+    if let Err(e) = request.validate() {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    match run_backtest(&chain, &request).await {
+        Ok(result) => {
+            info!(
+                "✅ Successfully backtested {} ranges for {}/{}",
+                result.results.len(),
+                request.token0,
+                request.token1
+            );
+            HttpResponse::Ok().json(result)
+        }
+        Err(e) => {
+            error!("❌ Failed to run backtest: {}", e);
+            use_case_error("Failed to run backtest", &e)
+        }
+    }
+}
+
+// --- Rebalancing Strategy Simulator Tool for AI Agent Handler ---
+#[post("/backtest/strategies")]
+pub async fn simulate_strategies_tool(
+    state: web::Data<AppState>,
+    data: web::Json<StrategySimulationRequest>,
+    chain: web::Query<ChainQuery>,
+) -> impl Responder {
+    let chain = match resolve_chain(&state.chains, &chain) {
+        Ok(chain) => chain,
+        Err(err) => return err,
+    };
+    let request = data.into_inner();
+    if let Err(e) = request.validate() {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    match simulate_strategies(&chain, &request).await {
+        Ok(result) => {
+            info!(
+                "✅ Successfully simulated {} strategies for {}/{}",
+                result.results.len(),
+                request.token0,
+                request.token1
+            );
+            HttpResponse::Ok().json(result)
+        }
+        Err(e) => {
+            error!("❌ Failed to simulate strategies: {}", e);
+            use_case_error("Failed to simulate strategies", &e)
+        }
+    }
+}
+
 // --- Position Handlers ---
 
 // GET /positions/{pb_key}
@@ -164,9 +1014,9 @@ pub async fn get_positions_for_wallet(
     path: web::Path<String>,
 ) -> HttpResponse {
     let pb_key = path.into_inner();
-    // Use the db_connection from the AppState
-    match position_service::get_all_positions_for_wallet(&data.db_connection, pb_key).await {
-        Ok(positions) => HttpResponse::Ok().json(positions),
+    match data.positions.list_for_wallet(&pb_key).await {
+        Ok(positions) => HttpResponse::Ok()
+            .json(value_positions(&data.price_oracle, &data.chains, positions).await),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
 }
@@ -177,30 +1027,37 @@ pub async fn add_position_handler(
     req: web::Json<AddPositionRequest>,
 ) -> HttpResponse {
     let r = req.into_inner();
-    // Use the db_connection from the AppState
-    match position_service::add_position(
-        &data.db_connection,
-        r.pb_key,
-        r.trans_id,
-        r.left,
-        r.right,
-        r.value_locker,
-    )
-    .await
-    {
-        Ok(pos) => HttpResponse::Ok().json(pos),
+    if let Err(e) = r.validate() {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+    let chain = match r.chain_id {
+        Some(chain_id) => match data.chains.find_by_id(chain_id) {
+            Some(chain) => chain,
+            None => {
+                return HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("chain_id must be one of {}", chain_ids(&data.chains).join(", "))
+                }));
+            }
+        },
+        None => data.chains.default_chain(),
+    };
+    match data.positions.add(r.into_new_position(chain.id)).await {
+        Ok(pos) => HttpResponse::Ok().json(
+            value_positions(&data.price_oracle, &data.chains, vec![pos])
+                .await
+                .pop(),
+        ),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
 }
 
-// DELETE /positions/{pb_key}/{trans_id}
+// DELETE /positions/{pb_key}/{id}
 pub async fn delete_position_handler(
     data: web::Data<AppState>, // <-- FIX: Request the whole AppState
-    path: web::Path<(String, i32)>,
+    path: web::Path<(String, i64)>,
 ) -> HttpResponse {
-    let (pb_key, trans_id) = path.into_inner();
-    // Use the db_connection from the AppState
-    match position_service::delete_position(&data.db_connection, pb_key, trans_id).await {
+    let (pb_key, id) = path.into_inner();
+    match data.positions.delete(&pb_key, id).await {
         Ok(_) => HttpResponse::Ok().body("Position deleted"),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
@@ -1,12 +1,26 @@
 use crate::presentation::handlers::{
-    add_chat, add_position_handler, delete_position_handler, get_chat, get_graph_data_handler,
-    get_pools_handler, get_positions_for_wallet, get_price_history_tool,
-    get_token_pair_price_history, get_token_symbol_handler, prompt_handler, verify_signature,
+    add_chat, add_position_handler, apr_sustainability_tool, delete_position_handler,
+    estimate_fee_apr_tool, get_chat, get_correlation_tool, get_divergences_handler,
+    get_graph_data_handler, get_indicators_tool, get_optimal_range_tool, get_pair_pools_handler,
+    get_pool_detail_handler, get_pool_fee_apr_handler, get_pool_history_handler,
+    get_pool_onchain_handler, get_pools_handler, get_positions_for_wallet, get_price_history_tool,
+    get_token_pair_price_history, get_token_price_handler, get_token_symbol_handler,
+    prompt_handler, run_backtest_tool, simulate_strategies_tool, stream_handler, verify_signature,
 };
 use actix_web::web;
 
 pub fn init_routes(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::scope("/tools").service(get_price_history_tool));
+    cfg.service(
+        web::scope("/tools")
+            .service(get_price_history_tool)
+            .service(get_indicators_tool)
+            .service(get_correlation_tool)
+            .service(get_optimal_range_tool)
+            .service(estimate_fee_apr_tool)
+            .service(apr_sustainability_tool)
+            .service(run_backtest_tool)
+            .service(simulate_strategies_tool),
+    );
 
     // Authentication routes
     cfg.service(web::scope("/auth").service(verify_signature));
@@ -16,10 +30,20 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/data")
             .service(get_graph_data_handler)
             .service(get_pools_handler)
+            .service(get_pool_fee_apr_handler)
+            .service(get_pool_history_handler)
+            .service(get_pool_onchain_handler)
+            .service(get_pool_detail_handler)
+            .service(get_pair_pools_handler)
+            .service(get_divergences_handler)
             .service(get_token_pair_price_history)
+            .service(get_token_price_handler)
             .service(get_token_symbol_handler),
     );
 
+    // Live klines, pool stats and ticks over WebSocket or Server-Sent Events
+    cfg.service(stream_handler);
+
     // Agent/LLM routes
     cfg.service(web::scope("/agent").service(prompt_handler));
 
@@ -28,8 +52,7 @@ pub fn init_routes(cfg: &mut web::ServiceConfig) {
     );
     cfg.service(web::resource("/positions").route(web::post().to(add_position_handler)));
     cfg.service(
-        web::resource("/positions/{pb_key}/{trans_id}")
-            .route(web::delete().to(delete_position_handler)),
+        web::resource("/positions/{pb_key}/{id}").route(web::delete().to(delete_position_handler)),
     );
     cfg.service(web::resource("/chat").route(web::put().to(add_chat)));
     cfg.service(web::resource("/chat/{public_key}").route(web::get().to(get_chat)));
@@ -0,0 +1,161 @@
+use crate::application::dtos::stream::{ClientMessage, StreamEvent, Topic};
+use crate::application::service::stream_service::Subscriptions;
+use crate::config::stream_heartbeat_secs;
+use actix_web::HttpResponse;
+use actix_web::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use actix_web::web::Bytes;
+use actix_ws::{Message, MessageStream, Session};
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::{Interval, interval_at};
+use tracing::{info, warn};
+
+fn heartbeat() -> (Interval, Duration) {
+    let period = Duration::from_secs(stream_heartbeat_secs());
+    (
+        interval_at(tokio::time::Instant::now() + period, period),
+        period,
+    )
+}
+
+/// Server-Sent Events response pushing `initial` and then every update of the subscriptions,
+/// with a comment line as heartbeat. Actix only polls the body as fast as the client reads it,
+/// so a slow client falls behind on its subscriptions rather than buffering here.
+pub fn sse_response(subscriptions: Subscriptions, initial: Vec<Arc<StreamEvent>>) -> HttpResponse {
+    let (heartbeat, _) = heartbeat();
+    let events = futures::stream::unfold(
+        (subscriptions, heartbeat, Some(initial)),
+        |(mut subscriptions, mut heartbeat, initial)| async move {
+            let frame = match initial {
+                Some(events) => sse_frames(&events),
+                None => tokio::select! {
+                    events = subscriptions.next() => sse_frames(&events),
+                    _ = heartbeat.tick() => Bytes::from_static(b": heartbeat\n\n"),
+                },
+            };
+            Some((
+                Ok::<_, actix_web::Error>(frame),
+                (subscriptions, heartbeat, None),
+            ))
+        },
+    );
+
+    HttpResponse::Ok()
+        .insert_header((CONTENT_TYPE, "text/event-stream"))
+        .insert_header((CACHE_CONTROL, "no-cache"))
+        .streaming(events)
+}
+
+fn sse_frames(events: &[Arc<StreamEvent>]) -> Bytes {
+    let mut frames = String::new();
+    for event in events {
+        match serde_json::to_string(event.as_ref()) {
+            Ok(data) => frames.push_str(&format!("event: {}\ndata: {}\n\n", event.event, data)),
+            Err(e) => warn!("⚠️ Failed to encode stream event: {}", e),
+        }
+    }
+    Bytes::from(frames)
+}
+
+/// Drive one WebSocket connection: push updates, apply subscribe and unsubscribe messages,
+/// ping every heartbeat and drop clients silent for two heartbeats. Sends wait while the
+/// connection's outgoing buffer is full, which is where a slow client starts lagging.
+pub async fn run_websocket(
+    mut session: Session,
+    mut messages: MessageStream,
+    mut subscriptions: Subscriptions,
+    initial: Vec<Arc<StreamEvent>>,
+) {
+    let (mut heartbeat, period) = heartbeat();
+    let mut last_seen = Instant::now();
+
+    if send_all(&mut session, &initial).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            message = messages.recv() => {
+                last_seen = Instant::now();
+                let replies = match message {
+                    Some(Ok(Message::Text(text))) => handle_client_message(&mut subscriptions, &text),
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            return;
+                        }
+                        Vec::new()
+                    }
+                    Some(Ok(Message::Close(reason))) => {
+                        let _ = session.close(reason).await;
+                        return;
+                    }
+                    Some(Ok(_)) => Vec::new(),
+                    Some(Err(e)) => {
+                        warn!("⚠️ WebSocket protocol error: {}", e);
+                        break;
+                    }
+                    None => return,
+                };
+                if send_all(&mut session, &replies).await.is_err() {
+                    return;
+                }
+            }
+            events = subscriptions.next() => {
+                if send_all(&mut session, &events).await.is_err() {
+                    return;
+                }
+            }
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > period * 2 {
+                    info!("💤 Closing silent WebSocket client");
+                    break;
+                }
+                if session.ping(b"").await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    let _ = session.close(None).await;
+}
+
+async fn send_all(
+    session: &mut Session,
+    events: &[Arc<StreamEvent>],
+) -> Result<(), actix_ws::Closed> {
+    for event in events {
+        match serde_json::to_string(event.as_ref()) {
+            Ok(text) => session.text(text).await?,
+            Err(e) => warn!("⚠️ Failed to encode stream event: {}", e),
+        }
+    }
+    Ok(())
+}
+
+fn handle_client_message(subscriptions: &mut Subscriptions, text: &str) -> Vec<Arc<StreamEvent>> {
+    let message = match serde_json::from_str::<ClientMessage>(text) {
+        Ok(message) => message,
+        Err(e) => {
+            return vec![subscriptions.error(format!(
+                "Expected {{\"action\": \"subscribe\" | \"unsubscribe\", \"topics\": [...]}}: {}",
+                e
+            ))];
+        }
+    };
+
+    let result = match message {
+        ClientMessage::Subscribe { topics } => {
+            parse_all(&topics).and_then(|topics| subscriptions.subscribe_all(topics))
+        }
+        ClientMessage::Unsubscribe { topics } => {
+            parse_all(&topics).map(|topics| vec![subscriptions.unsubscribe_all(topics)])
+        }
+    };
+    result.unwrap_or_else(|e| vec![subscriptions.error(e.to_string())])
+}
+
+fn parse_all(topics: &[String]) -> Result<Vec<Topic>> {
+    topics.iter().map(|topic| topic.parse()).collect()
+}
@@ -0,0 +1,99 @@
+//! The position indexer end to end against a local anvil fork. Ignored by default: it needs a
+//! running fork with LiquidityManager deployed and a position minted through it, as described
+//! under "Against a local anvil node" in the README. Then run
+//!
+//! ```bash
+//! ANVIL_RPC_URL=http://127.0.0.1:8545 LIQUIDITY_MANAGER_ADDRESS=0x... \
+//!   LIQUIDITY_MANAGER_START_BLOCK=<deployment block> cargo test --test anvil_indexer -- --ignored
+//! ```
+//!
+//! `MINT_RECIPIENT` overrides the wallet the mint script sends from.
+
+use rust_backend_api_gateway::application::service::position_indexer::index_next_range;
+use rust_backend_api_gateway::config::Chains;
+use rust_backend_api_gateway::domain::repositories::position_repository::PositionRepository;
+use rust_backend_api_gateway::infrastructure::data::liquidity_manager_logs::LiquidityManagerLogs;
+use rust_backend_api_gateway::infrastructure::repositories::InMemoryPositionRepository;
+use std::env;
+
+const MINT_RECIPIENT: &str = "0x79dAa774769334aF120f6CAA57E828FBBF56b39a";
+
+/// Index every confirmed block of the fork, returning how many ranges it took
+async fn index_to_head(
+    chains: &Chains,
+    logs: &LiquidityManagerLogs,
+    positions: &InMemoryPositionRepository,
+) -> usize {
+    let mut ranges = 0;
+    while index_next_range(chains.default_chain(), logs, positions)
+        .await
+        .expect("indexing the anvil fork failed")
+    {
+        ranges += 1;
+    }
+    ranges + 1
+}
+
+#[tokio::test]
+#[ignore = "needs an anvil fork with LiquidityManager deployed and a position minted"]
+async fn indexes_a_position_minted_on_an_anvil_fork() {
+    let rpc_url = env::var("ANVIL_RPC_URL").unwrap_or_else(|_| "http://127.0.0.1:8545".into());
+    // SAFETY: set before anything in this test binary reads the environment
+    unsafe {
+        env::set_var("CHAINS", "anvil");
+        env::set_var("ANVIL_RPC_URL", rpc_url);
+        if env::var("ANVIL_CHAIN_ID").is_err() {
+            env::set_var("ANVIL_CHAIN_ID", "1329");
+        }
+        env::set_var("INDEXER_CONFIRMATIONS", "0");
+        // Small ranges, so the cursor is carried across several calls
+        env::set_var("INDEXER_BLOCK_RANGE", "5");
+    }
+    let chains = Chains::from_env();
+    let chain = chains.default_chain();
+    assert!(
+        chain.liquidity_manager_address.is_some(),
+        "set LIQUIDITY_MANAGER_ADDRESS to the LiquidityManager deployed on the fork"
+    );
+    let logs = LiquidityManagerLogs::for_chain(chain)
+        .expect("LIQUIDITY_MANAGER_ADDRESS is not an address")
+        .expect("the anvil chain has no LiquidityManager");
+    let recipient = env::var("MINT_RECIPIENT")
+        .unwrap_or_else(|_| MINT_RECIPIENT.to_string())
+        .to_lowercase();
+
+    let positions = InMemoryPositionRepository::new();
+    index_to_head(&chains, &logs, &positions).await;
+
+    let head = logs.latest_block().await.expect("anvil is not reachable");
+    let indexed = positions
+        .indexed_block(chain.id, &logs.contract())
+        .await
+        .unwrap();
+    assert_eq!(indexed, Some(head));
+
+    let minted = positions.list_for_wallet(&recipient).await.unwrap();
+    let position = minted
+        .iter()
+        .find(|position| position.status == "open")
+        .unwrap_or_else(|| panic!("no open position was indexed for {}", recipient));
+    assert_eq!(position.chain_id, chain.id as i64);
+    assert!(position.pool_address.is_some());
+    assert!(position.tick_lower < position.tick_upper);
+    let liquidity: u128 = position
+        .liquidity
+        .as_deref()
+        .expect("the minted position has no liquidity")
+        .parse()
+        .unwrap();
+    assert!(liquidity > 0);
+    assert!(
+        position
+            .block_number
+            .is_some_and(|block| block as u64 <= head)
+    );
+
+    // A second pass from the stored cursor finds nothing new and applies no log twice
+    assert_eq!(index_to_head(&chains, &logs, &positions).await, 1);
+    assert_eq!(positions.list_for_wallet(&recipient).await.unwrap(), minted);
+}
@@ -0,0 +1,715 @@
+//! `/data`, `/tools` and `/stream` end to end against upstream responses replayed from
+//! `tests/fixtures/upstream`, plus `/positions` over an in-memory repository. Needs no network,
+//! Postgres or MongoDB: the database points at a closed port, so every query fails fast and
+//! history-backed fields fall back the way they do when Postgres is down.
+
+use actix_web::body::MessageBody;
+use actix_web::http::StatusCode;
+use actix_web::{App, test, web};
+use rust_backend_api_gateway::AppState;
+use rust_backend_api_gateway::application::service::chat_service::ChatService;
+use rust_backend_api_gateway::application::service::price_oracle::PriceOracle;
+use rust_backend_api_gateway::application::service::stream_service::StreamHub;
+use rust_backend_api_gateway::config::Chains;
+use rust_backend_api_gateway::infrastructure::repositories::InMemoryPositionRepository;
+use rust_backend_api_gateway::presentation::routes::init_routes;
+use sea_orm::SqlxPostgresConnector;
+use serde_json::{Value, json};
+use sqlx::postgres::PgPoolOptions;
+use std::pin::Pin;
+use std::sync::{Arc, Once};
+use std::time::Duration;
+
+const WSEI: &str = "0xE30fEDd158A2e3b13e9badaeABAFc5516e95e8C7";
+const USDC: &str = "0x3894085Ef7Ff0f0aeDf52E2A2704928d1Ec074F1";
+const DRAGONSWAP_POOL: &str = "0x882f62fe8e9594470d1da0f70bc85096f6c60423";
+const SAILOR_POOL: &str = "0x80fe558c54f1f43263e08f0e1fa3e02d8b897f93";
+
+/// Pin the upstream configuration the fixtures were recorded with
+fn replay_upstream() {
+    static REPLAY: Once = Once::new();
+    REPLAY.call_once(|| {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/upstream");
+        // SAFETY: set once, before any test reads the environment
+        unsafe {
+            std::env::set_var("UPSTREAM_MODE", "replay");
+            std::env::set_var("UPSTREAM_FIXTURES_DIR", fixtures);
+            std::env::set_var("CHAINS", "sei");
+            std::env::set_var("SUBGRAPH_DEXES", "");
+            std::env::set_var("BINANCE_API_BASE_URL", "https://api.binance.com/api/v3");
+            std::env::set_var(
+                "DRAGONSWAP_API_BASE_URL",
+                "https://sei-api.dragonswap.app/api/v1",
+            );
+            std::env::set_var(
+                "SAILOR_API_BASE_URL",
+                "https://asia-southeast1-ktx-finance-2.cloudfunctions.net",
+            );
+        }
+    });
+}
+
+async fn app_state() -> AppState {
+    replay_upstream();
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_lazy("postgres://gateway@127.0.0.1:1/gateway")
+        .unwrap();
+    let db = SqlxPostgresConnector::from_sqlx_postgres_pool(pool);
+    AppState {
+        db_connection: db.clone(),
+        chains: Chains::from_env(),
+        // Connects lazily; no chat route is exercised
+        chat_service: ChatService::new("mongodb://127.0.0.1:27017").await.unwrap(),
+        price_oracle: PriceOracle::new(db),
+        positions: Arc::new(InMemoryPositionRepository::new()),
+        stream_hub: StreamHub::new(),
+    }
+}
+
+async fn call(request: test::TestRequest) -> (StatusCode, Value) {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state().await))
+            .configure(init_routes),
+    )
+    .await;
+
+    let response = test::call_service(&app, request.to_request()).await;
+    let status = response.status();
+    let body = test::read_body(response).await;
+    let body = serde_json::from_slice(&body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()));
+    (status, body)
+}
+
+async fn get(uri: &str) -> Value {
+    let (status, body) = call(test::TestRequest::get().uri(uri)).await;
+    assert_eq!(status, StatusCode::OK, "GET {}: {}", uri, body);
+    body
+}
+
+async fn post(uri: &str, request: Value) -> Value {
+    let (status, body) = call(test::TestRequest::post().uri(uri).set_json(request)).await;
+    assert_eq!(status, StatusCode::OK, "POST {}: {}", uri, body);
+    body
+}
+
+#[actix_web::test]
+async fn lists_pools_from_every_provider() {
+    let body = get("/data/pools?schema=v2").await;
+
+    let sources = body["sources"].as_array().unwrap();
+    assert_eq!(sources.len(), 2);
+    assert!(sources.iter().all(|source| source["status"] == "ok"));
+
+    let pools = body["pools"].as_array().unwrap();
+    let protocols: Vec<&str> = pools
+        .iter()
+        .map(|pool| pool["protocol"].as_str().unwrap())
+        .collect();
+    assert!(protocols.contains(&"DragonSwap"));
+    assert!(protocols.contains(&"Sailor"));
+    assert!(pools.iter().all(|pool| pool["chain_id"] == 1329));
+    assert!(pools.iter().all(|pool| pool["risk"]["score"].is_number()));
+}
+
+#[actix_web::test]
+async fn pages_v1_pools_as_a_plain_array() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state().await))
+            .configure(init_routes),
+    )
+    .await;
+
+    let request = test::TestRequest::get().uri("/data/pools?limit=1");
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_string())
+    };
+    let total: usize = header("X-Total-Count").unwrap().parse().unwrap();
+    let cursor = header("X-Next-Cursor");
+    assert!(header("X-Failed-Sources").is_none());
+    assert_eq!(cursor.is_some(), total > 1);
+
+    let body: Value = test::read_body_json(response).await;
+    let pools = body.as_array().unwrap();
+    assert_eq!(pools.len(), 1);
+    let mut fields: Vec<&str> = pools[0]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    fields.sort_unstable();
+    assert_eq!(
+        fields,
+        [
+            "apr",
+            "daily_volume",
+            "fee_tier",
+            "id",
+            "protocol",
+            "token0",
+            "token1",
+            "tvl"
+        ]
+    );
+    assert!(pools[0]["fee_tier"].is_string());
+    assert!(pools[0]["token0"]["decimals"].is_string());
+}
+
+#[actix_web::test]
+async fn returns_every_v1_pool_without_paging_parameters() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state().await))
+            .configure(init_routes),
+    )
+    .await;
+
+    let request = test::TestRequest::get().uri("/data/pools?min_tvl=0&min_volume=0");
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("X-Next-Cursor").is_none());
+    let total: usize = response
+        .headers()
+        .get("X-Total-Count")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body.as_array().unwrap().len(), total);
+}
+
+#[actix_web::test]
+async fn rejects_unknown_chains() {
+    let (status, body) = call(test::TestRequest::get().uri("/data/pools?chain=solana")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("chain"));
+
+    let request = test::TestRequest::post()
+        .uri("/tools/apr-sustainability?chain=solana")
+        .set_json(json!({ "pool_address": SAILOR_POOL }));
+    let (status, body) = call(request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("chain"));
+
+    let uri = "/tools/correlation?token0=SEI&token1=ETH&chain=solana";
+    let (status, body) = call(test::TestRequest::get().uri(uri)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("chain"));
+}
+
+#[actix_web::test]
+async fn details_a_pool_with_liquidity_and_prices() {
+    let body = get(&format!("/data/pools/{}", SAILOR_POOL)).await;
+
+    assert_eq!(body["chain_id"], 1329);
+    assert_eq!(body["protocol"], "Sailor");
+    assert_eq!(body["fee_tier"], 0.3);
+    assert_eq!(body["liquidity"]["initialized_ticks"], 5);
+    assert!(body["risk"]["score"].is_number());
+}
+
+#[actix_web::test]
+async fn charts_pair_prices() {
+    let candles = get("/data/price-chart/SEI/USDC?interval=60&limit=48").await;
+
+    let candles = candles.as_array().unwrap();
+    assert_eq!(candles.len(), 48);
+    assert!(candles[0]["tick"].as_i64() < candles[47]["tick"].as_i64());
+}
+
+#[actix_web::test]
+async fn charts_liquidity_from_the_pool_provider() {
+    // DragonSwap answers 404 for Sailor pools, so the chart falls through to Sailor
+    let sailor = get(&format!(
+        "/data/liquidity-chart?pool_address={}",
+        SAILOR_POOL
+    ))
+    .await;
+    let dragonswap = get(&format!(
+        "/data/liquidity-chart?pool_address={}",
+        DRAGONSWAP_POOL
+    ))
+    .await;
+
+    assert!(!sailor["data"].as_array().unwrap().is_empty());
+    assert!(!dragonswap["data"].as_array().unwrap().is_empty());
+    assert_ne!(sailor["data"], dragonswap["data"]);
+}
+
+#[actix_web::test]
+async fn resolves_token_symbols() {
+    let token = get(&format!("/data/token/{}", USDC)).await;
+
+    assert_eq!(token["symbol"], "USDC");
+    assert_eq!(token["decimals"], 6);
+}
+
+#[actix_web::test]
+async fn prices_tokens_across_sources() {
+    let price = get(&format!("/data/tokens/{}/price", WSEI)).await;
+
+    assert_eq!(price["symbol"], "WSEI");
+    let usd_price = price["usd_price"].as_f64().unwrap();
+    assert!(usd_price > 0.2 && usd_price < 0.3, "{}", usd_price);
+    assert!(price["sources"].as_array().unwrap().len() >= 2);
+}
+
+#[actix_web::test]
+async fn ranks_pair_venues() {
+    let body = get("/data/pairs/WSEI/USDC").await;
+
+    let pools = body["pools"].as_array().unwrap();
+    assert_eq!(pools.len(), 2);
+    assert!(pools[0]["score"].as_f64() >= pools[1]["score"].as_f64());
+}
+
+#[actix_web::test]
+async fn analyzes_price_history() {
+    let body = get("/tools/price-history?token0=SEI&token1=USDC&interval=60&limit=48&seed=7").await;
+
+    assert_eq!(body["pair"], "SEI/USDC");
+    assert_eq!(body["data_points"], 48);
+    assert!(body["volatility"]["realized_annualized"].as_f64().unwrap() > 0.0);
+
+    // Ranges are centred on the latest close rather than the window average
+    let context = &body["recommendation_context"];
+    let last_close = body["recent_prices"][0]["price"].as_f64().unwrap();
+    assert_eq!(context["center_price"].as_f64(), Some(last_close));
+    let survival = &context["range_survival"][0];
+    let width = survival["range_width_percent"].as_f64().unwrap();
+    assert!(
+        (survival["lower_price"].as_f64().unwrap() - last_close * (1.0 - width / 100.0)).abs()
+            < 1e-9
+    );
+}
+
+#[actix_web::test]
+async fn seeds_price_history_simulations() {
+    let uri = "/tools/price-history?token0=SEI&token1=USDC&interval=60&limit=48&seed=7";
+    let (first, second) = (get(uri).await, get(uri).await);
+
+    assert!(
+        !first["recommendation_context"]["range_survival"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        first["recommendation_context"]["range_survival"],
+        second["recommendation_context"]["range_survival"]
+    );
+}
+
+#[actix_web::test]
+async fn rejects_unknown_simulation_models() {
+    for query in ["model=garch", "risk_profile=reckless"] {
+        let uri = format!(
+            "/tools/price-history?token0=SEI&token1=USDC&interval=60&limit=48&{}",
+            query
+        );
+        let (status, body) = call(test::TestRequest::get().uri(&uri)).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
+#[actix_web::test]
+async fn computes_indicators() {
+    let body =
+        get("/tools/indicators/SEI/USDC?interval=60&limit=48&set=ema20,rsi14&chain=1329").await;
+
+    assert_eq!(body["close"].as_array().unwrap().len(), 48);
+    assert_eq!(body["indicators"].as_array().unwrap().len(), 2);
+
+    // Warm-up slots stay null until the window fills
+    let ema = body["indicators"][0]["lines"].as_object().unwrap();
+    let ema = ema.values().next().unwrap().as_array().unwrap();
+    assert_eq!(ema.len(), 48);
+    assert!(ema[..19].iter().all(Value::is_null));
+    assert!(ema[19..].iter().all(Value::is_number));
+}
+
+#[actix_web::test]
+async fn rejects_invalid_indicator_sets() {
+    let (status, _) =
+        call(test::TestRequest::get().uri("/tools/indicators/SEI/USDC?set=ema20,macd26_12_9"))
+            .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn correlates_pair_legs() {
+    let body = get("/tools/correlation?token0=SEI&token1=ETH&interval=60&limit=48").await;
+
+    assert!(body["data_points"].as_u64().unwrap() > 2);
+    assert!(body["relationship"].is_object());
+}
+
+#[actix_web::test]
+async fn optimizes_ranges() {
+    let body = post(
+        "/tools/optimal-range",
+        json!({
+            "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+            "fee_tier": 0.3, "pool_apr": 25.0, "paths": 50, "seed": 7
+        }),
+    )
+    .await;
+
+    assert_eq!(body["pair"], "SEI/USDC");
+    assert_eq!(body["tick_spacing"], 60);
+    let (lower, upper) = (
+        body["price_lower"].as_f64().unwrap(),
+        body["price_upper"].as_f64().unwrap(),
+    );
+    assert!(lower < body["current_price"].as_f64().unwrap());
+    assert!(upper > body["current_price"].as_f64().unwrap());
+}
+
+#[actix_web::test]
+async fn rejects_unbounded_optimizer_horizons() {
+    for overrides in [
+        json!({ "horizon_days": 4_000_000 }),
+        json!({ "horizon_days": 366 }),
+        json!({ "interval": 0 }),
+        json!({ "token0_decimals": 300 }),
+        json!({ "model": "garch" }),
+    ] {
+        let mut request = json!({
+            "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+            "fee_tier": 0.3, "pool_apr": 25.0, "paths": 50, "seed": 7
+        });
+        request
+            .as_object_mut()
+            .unwrap()
+            .extend(overrides.as_object().unwrap().clone());
+        let (status, body) = call(
+            test::TestRequest::post()
+                .uri("/tools/optimal-range")
+                .set_json(request),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
+#[actix_web::test]
+async fn sizes_mint_amounts_for_long_horizons() {
+    let body = post(
+        "/tools/optimal-range",
+        json!({
+            "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+            "fee_tier": 0.3, "pool_apr": 25.0, "paths": 20, "seed": 7,
+            "horizon_days": 365, "capital": 1000.0
+        }),
+    )
+    .await;
+
+    assert_eq!(body["horizon_days"], 365);
+    let amount1_max = body["mint"]["amount1_max"].as_str().unwrap();
+    assert!(amount1_max.chars().all(|c| c.is_ascii_digit()));
+}
+
+#[actix_web::test]
+async fn estimates_fee_apr() {
+    let body = post(
+        "/tools/fee-apr?chain=sei",
+        json!({
+            "pool_address": DRAGONSWAP_POOL,
+            "price_lower": 0.2,
+            "price_upper": 0.3,
+            "capital": 1000.0
+        }),
+    )
+    .await;
+
+    assert_eq!(body["protocol"], "DragonSwap");
+    assert_eq!(body["pair"], "WSEI/USDC");
+    assert_eq!(body["in_range"], true);
+    assert!(body["position_liquidity"].as_f64().unwrap() > 0.0);
+}
+
+#[actix_web::test]
+async fn backtests_ranges_and_strategies() {
+    let backtest = post(
+        "/tools/backtest",
+        json!({
+            "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+            "capital": 1000.0, "fee_tier": 0.3, "pool_tvl": 400000.0
+        }),
+    )
+    .await;
+    let strategies = post(
+        "/tools/backtest/strategies",
+        json!({
+            "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+            "capital": 1000.0, "fee_tier": 0.3, "pool_tvl": 400000.0
+        }),
+    )
+    .await;
+
+    assert_eq!(backtest["data_points"], 48);
+    assert!(!backtest["results"].as_array().unwrap().is_empty());
+    assert!(!strategies["results"].as_array().unwrap().is_empty());
+}
+
+#[actix_web::test]
+async fn rejects_invalid_backtest_requests() {
+    for request in [
+        json!({ "limit": 0 }),
+        json!({ "limit": 100_000 }),
+        json!({ "capital": 0.0 }),
+        json!({ "range_widths_percent": [] }),
+    ] {
+        let mut body = json!({
+            "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+            "capital": 1000.0, "fee_tier": 0.3, "pool_tvl": 400000.0
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(request.as_object().unwrap().clone());
+        let (status, body) = call(
+            test::TestRequest::post()
+                .uri("/tools/backtest")
+                .set_json(body),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
+#[actix_web::test]
+async fn rejects_invalid_strategy_parameters() {
+    for strategy in [
+        json!({ "type": "fixed", "width_percent": 100.0 }),
+        json!({ "type": "trailing", "width_percent": 10.0, "inner_fraction": 0.0 }),
+        json!({ "type": "volatility_scaled", "min_width_percent": 40.0, "max_width_percent": 5.0 }),
+        json!({ "type": "volatility_scaled", "window": 1 }),
+    ] {
+        let (status, body) = call(
+            test::TestRequest::post()
+                .uri("/tools/backtest/strategies")
+                .set_json(json!({
+                    "token0": "SEI", "token1": "USDC", "interval": 60, "limit": 48,
+                    "capital": 1000.0, "fee_tier": 0.3, "pool_tvl": 400000.0,
+                    "strategies": [strategy]
+                })),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
+#[actix_web::test]
+async fn rejects_empty_strategy_lists_and_long_replays() {
+    for (limit, strategies) in [(48, json!([])), (100_000, json!(null))] {
+        let (status, body) = call(
+            test::TestRequest::post()
+                .uri("/tools/backtest/strategies")
+                .set_json(json!({
+                    "token0": "SEI", "token1": "USDC", "interval": 60, "limit": limit,
+                    "capital": 1000.0, "fee_tier": 0.3, "pool_tvl": 400000.0,
+                    "strategies": strategies
+                })),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", body);
+    }
+}
+
+#[actix_web::test]
+async fn reports_unrecorded_upstream_calls() {
+    let (status, body) =
+        call(test::TestRequest::get().uri("/tools/indicators/NOPE/USDC?interval=60&limit=48"))
+            .await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(
+        body["details"]
+            .as_str()
+            .unwrap()
+            .contains("No recorded response")
+    );
+}
+
+#[actix_web::test]
+async fn streams_klines_and_ticks_over_sse() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state().await))
+            .configure(init_routes),
+    )
+    .await;
+    let uri = format!("/stream?topics=klines:WSEI/USDC:60,ticks:{}", SAILOR_POOL);
+    let response = test::call_service(&app, test::TestRequest::get().uri(&uri).to_request()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+
+    // The stream never ends, so read events until both topics have pushed their first update
+    let mut body = response.into_body();
+    let mut events: Vec<Value> = Vec::new();
+    while events.iter().filter(|e| e["event"] == "update").count() < 2 {
+        let chunk = tokio::time::timeout(
+            Duration::from_secs(5),
+            futures::future::poll_fn(|cx| Pin::new(&mut body).poll_next(cx)),
+        )
+        .await
+        .expect("no stream update within 5s")
+        .unwrap()
+        .unwrap();
+        events.extend(
+            String::from_utf8_lossy(&chunk)
+                .lines()
+                .filter_map(|line| line.strip_prefix("data: "))
+                .map(|data| serde_json::from_str::<Value>(data).unwrap()),
+        );
+    }
+
+    assert_eq!(events[0]["event"], "subscribed");
+    let update = |topic: &str| {
+        events
+            .iter()
+            .find(|e| e["event"] == "update" && e["topic"] == topic)
+            .unwrap_or_else(|| panic!("no update on {}", topic))
+    };
+    assert!(
+        update("klines:WSEI/USDC:60")["data"]["close"]
+            .as_f64()
+            .unwrap()
+            > 0.0
+    );
+    let ticks = &update(&format!("ticks:{}", SAILOR_POOL))["data"];
+    assert_eq!(ticks["snapshot"], true);
+    assert_eq!(ticks["changed"].as_array().unwrap().len(), 5);
+}
+
+#[actix_web::test]
+async fn rejects_sse_without_valid_topics() {
+    let (status, _) = call(test::TestRequest::get().uri("/stream")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = call(test::TestRequest::get().uri("/stream?topics=trades:SEI/USDC")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("Unsupported topic")
+    );
+}
+
+#[actix_web::test]
+async fn rejects_invalid_positions_before_storing_them() {
+    let position = json!({
+        "pb_key": "0xabc",
+        "pool_address": DRAGONSWAP_POOL,
+        "token0_address": WSEI,
+        "token1_address": USDC,
+        "tick_lower": 600,
+        "tick_upper": -600,
+        "liquidity": "340282366920938463463374607431768211455",
+        "amount0": "1000000000000000000",
+        "amount1": "250000",
+        "tx_hash": format!("0x{}", "ab".repeat(32))
+    });
+    let (status, body) = call(
+        test::TestRequest::post()
+            .uri("/positions")
+            .set_json(&position),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("tick_lower"));
+
+    let mut position = position;
+    position["tick_lower"] = json!(-600);
+    position["tick_upper"] = json!(600);
+    position["liquidity"] = json!("1.5e18");
+    let (status, body) = call(
+        test::TestRequest::post()
+            .uri("/positions")
+            .set_json(&position),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("liquidity"));
+
+    // One above the uint128 maximum
+    position["liquidity"] = json!("340282366920938463463374607431768211456");
+    let (status, body) = call(
+        test::TestRequest::post()
+            .uri("/positions")
+            .set_json(&position),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("uint128"));
+}
+
+#[actix_web::test]
+async fn records_lists_and_deletes_positions() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(app_state().await))
+            .configure(init_routes),
+    )
+    .await;
+    let position = json!({
+        "pb_key": "0xabc",
+        "pool_address": DRAGONSWAP_POOL,
+        "protocol": "DragonSwap",
+        "token0_address": WSEI,
+        "token1_address": USDC,
+        "tick_lower": -600,
+        "tick_upper": 600,
+        "liquidity": "340282366920938463463374607431768211455",
+        "amount0": "1000000000000000000",
+        "amount1": "250000",
+        "tx_hash": format!("0x{}", "ab".repeat(32))
+    });
+
+    let request = test::TestRequest::post()
+        .uri("/positions")
+        .set_json(&position);
+    let created: Value = test::call_and_read_body_json(&app, request.to_request()).await;
+    assert_eq!(created["status"], "open");
+    assert_eq!(created["token0_address"], WSEI.to_lowercase());
+    assert_eq!(created["liquidity"], position["liquidity"]);
+    // 1 WSEI at roughly $0.25 plus 0.25 USDC
+    let value = created["deposit_value_usd"].as_f64().unwrap();
+    assert!(value > 0.45 && value < 0.55, "{}", value);
+
+    let request = test::TestRequest::get().uri("/positions/0xabc");
+    let listed: Value = test::call_and_read_body_json(&app, request.to_request()).await;
+    assert_eq!(listed, json!([created]));
+
+    let uri = format!("/positions/0xabc/{}", created["id"]);
+    let response =
+        test::call_service(&app, test::TestRequest::delete().uri(&uri).to_request()).await;
+    assert!(response.status().is_success());
+
+    let request = test::TestRequest::get().uri("/positions/0xabc");
+    let listed: Value = test::call_and_read_body_json(&app, request.to_request()).await;
+    assert_eq!(listed, json!([]));
+}